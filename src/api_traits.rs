@@ -1,4 +1,4 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, io::Write, str::FromStr};
 
 use serde::Deserialize;
 
@@ -6,15 +6,24 @@ use crate::{
     cli::browse::BrowseOptions,
     cmds::{
         cicd::{
-            Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, Runner,
-            RunnerListBodyArgs, RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse,
-            YamlBytes,
+            Job, JobArtifact, JobArtifactBodyArgs, JobListBodyArgs, JobLogBodyArgs, LintResponse,
+            Pipeline, PipelineBodyArgs, PipelineCreateBodyArgs, Runner, RunnerListBodyArgs,
+            RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse, YamlBytes,
         },
         docker::{DockerListBodyArgs, ImageMetadata, RegistryRepository, RepositoryTag},
         gist::{Gist, GistListBodyArgs},
+        issue::{
+            CommentIssueBodyArgs, CommentIssueListBodyArgs, Issue, IssueBodyArgs, IssueListBodyArgs,
+        },
         merge_request::{
+            commit::{Commit, MergeRequestCommitListBodyArgs},
+            review::MergeRequestReviewBodyArgs,
+            status::{Approvals, Mergeability},
+            thread::Thread,
             Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs,
-            MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestResponse,
+            CommentMergeRequestUpdateBodyArgs, MergeRequestBodyArgs, MergeRequestDiff,
+            MergeRequestListBodyArgs, MergeRequestMergeBodyArgs, MergeRequestResponse,
+            MergeRequestUpdateBodyArgs, Reaction,
         },
         project::{Member, Project, ProjectListBodyArgs, Tag},
         release::{Release, ReleaseAssetListBodyArgs, ReleaseAssetMetadata, ReleaseBodyArgs},
@@ -28,10 +37,21 @@ use crate::{
 pub trait MergeRequest {
     fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse>;
     fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>>;
-    fn merge(&self, id: i64) -> Result<MergeRequestResponse>;
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse>;
     fn get(&self, id: i64) -> Result<MergeRequestResponse>;
     fn close(&self, id: i64) -> Result<MergeRequestResponse>;
+    fn reopen(&self, id: i64) -> Result<MergeRequestResponse>;
+    fn update(&self, args: MergeRequestUpdateBodyArgs) -> Result<MergeRequestResponse>;
     fn approve(&self, id: i64) -> Result<MergeRequestResponse>;
+    /// Files changed by the merge request, with their unified diff.
+    fn diff(&self, id: i64) -> Result<Vec<MergeRequestDiff>>;
+    /// Git reference under which the remote publishes the head of the merge
+    /// request. It can be fetched from the target repository even when the
+    /// source branch lives in a fork.
+    fn head_ref(&self, id: i64) -> String;
+    /// Asks the remote to update the source branch of the merge request with
+    /// the latest changes of its target branch.
+    fn rebase(&self, id: i64) -> Result<()>;
     /// Queries the remote API to get the number of pages available for a given
     /// resource based on list arguments.
     fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>>;
@@ -68,6 +88,13 @@ pub trait Cicd {
     /// Lints ci/cd pipeline file contents. In gitlab this is the .gitlab-ci.yml
     /// file. Checks that the file is valid and has no syntax errors.
     fn lint(&self, body: YamlBytes) -> Result<LintResponse>;
+    /// Triggers a new pipeline on the given ref. Gitlab returns the newly
+    /// created pipeline, whereas Github dispatches a workflow and does not
+    /// report back the run that it will start, hence the optional pipeline.
+    fn create(&self, args: PipelineCreateBodyArgs) -> Result<Option<Pipeline>>;
+    /// Retries the failed or canceled jobs of a pipeline.
+    fn retry(&self, id: i64) -> Result<()>;
+    fn cancel(&self, id: i64) -> Result<()>;
 }
 
 pub trait CicdRunner {
@@ -80,10 +107,26 @@ pub trait CicdRunner {
 
 pub trait CicdJob {
     fn list(&self, args: JobListBodyArgs) -> Result<Vec<Job>>;
+    fn get(&self, id: i64) -> Result<Job>;
+    /// Gets the log (trace) of a job. Github only makes the log available
+    /// once the job has completed, an empty log is returned until then.
+    fn log(&self, args: JobLogBodyArgs) -> Result<String>;
     fn num_pages(&self, args: JobListBodyArgs) -> Result<Option<u32>>;
     fn num_resources(&self, args: JobListBodyArgs) -> Result<Option<NumberDeltaErr>>;
 }
 
+pub trait CicdJobArtifact {
+    fn list(&self, job_id: i64) -> Result<Vec<JobArtifact>>;
+    /// Downloads the artifacts of a job into the writer. In Gitlab, the whole
+    /// archive or a single file in it if a path is provided. In Github,
+    /// artifacts belong to the workflow run of the job and the path is the
+    /// name of the artifact, optional if the run has only one.
+    fn download(&self, args: JobArtifactBodyArgs, writer: &mut dyn Write) -> Result<()>;
+    /// Whether downloading the given path yields a zip archive rather than a
+    /// single file.
+    fn downloads_archive(&self, path: Option<&str>) -> bool;
+}
+
 pub trait Deploy {
     fn list(&self, args: ReleaseBodyArgs) -> Result<Vec<Release>>;
     fn num_pages(&self) -> Result<Option<u32>>;
@@ -122,8 +165,39 @@ pub trait ContainerRegistry {
     fn get_image_metadata(&self, repository_id: i64, tag: &str) -> Result<ImageMetadata>;
 }
 
+/// Line-anchored review comments submitted in a single review along with a
+/// verdict.
+pub trait ReviewMergeRequest {
+    fn review(&self, args: MergeRequestReviewBodyArgs) -> Result<()>;
+}
+
+pub trait MergeRequestCommit {
+    fn list(&self, args: MergeRequestCommitListBodyArgs) -> Result<Vec<Commit>>;
+    fn num_pages(&self, args: MergeRequestCommitListBodyArgs) -> Result<Option<u32>>;
+    fn num_resources(&self, args: MergeRequestCommitListBodyArgs)
+        -> Result<Option<NumberDeltaErr>>;
+}
+
+/// Checks gathered before merging a merge request.
+pub trait MergeRequestReadiness {
+    /// Draft flag, conflicts, commits behind the target branch and CI status.
+    fn mergeability(&self, id: i64) -> Result<Mergeability>;
+    fn approvals(&self, id: i64) -> Result<Approvals>;
+}
+
+/// Discussion threads on a merge request. Thread ids are opaque strings as
+/// given by the remote.
+pub trait MergeRequestThread {
+    fn list(&self, id: i64) -> Result<Vec<Thread>>;
+    fn reply(&self, id: i64, thread_id: &str, body: String) -> Result<()>;
+    fn resolve(&self, id: i64, thread_id: &str, resolved: bool) -> Result<()>;
+}
+
 pub trait CommentMergeRequest {
     fn create(&self, args: CommentMergeRequestBodyArgs) -> Result<()>;
+    fn update(&self, args: CommentMergeRequestUpdateBodyArgs) -> Result<()>;
+    fn delete(&self, id: i64, comment_id: i64) -> Result<()>;
+    fn react(&self, id: i64, comment_id: i64, reaction: Reaction) -> Result<()>;
     fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>>;
     fn num_pages(&self, args: CommentMergeRequestListBodyArgs) -> Result<Option<u32>>;
     fn num_resources(
@@ -132,6 +206,22 @@ pub trait CommentMergeRequest {
     ) -> Result<Option<NumberDeltaErr>>;
 }
 
+pub trait RemoteIssue {
+    fn create(&self, args: IssueBodyArgs) -> Result<Issue>;
+    fn list(&self, args: IssueListBodyArgs) -> Result<Vec<Issue>>;
+    fn get(&self, id: i64) -> Result<Issue>;
+    fn close(&self, id: i64) -> Result<Issue>;
+    fn num_pages(&self, args: IssueListBodyArgs) -> Result<Option<u32>>;
+    fn num_resources(&self, args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>>;
+}
+
+pub trait CommentIssue {
+    fn create(&self, args: CommentIssueBodyArgs) -> Result<()>;
+    fn list(&self, args: CommentIssueListBodyArgs) -> Result<Vec<Comment>>;
+    fn num_pages(&self, args: CommentIssueListBodyArgs) -> Result<Option<u32>>;
+    fn num_resources(&self, args: CommentIssueListBodyArgs) -> Result<Option<NumberDeltaErr>>;
+}
+
 pub trait TrendingProjectURL {
     fn list(&self, language: String) -> Result<Vec<TrendingProject>>;
 }
@@ -187,6 +277,7 @@ pub enum ApiOperation {
     // Gists
     Gist,
     RepositoryTag,
+    Issue,
 }
 
 impl Display for ApiOperation {
@@ -200,6 +291,7 @@ impl Display for ApiOperation {
             ApiOperation::SinglePage => write!(f, "single_page"),
             ApiOperation::Gist => write!(f, "gist"),
             ApiOperation::RepositoryTag => write!(f, "repository_tag"),
+            ApiOperation::Issue => write!(f, "issue"),
         }
     }
 }
@@ -227,6 +319,7 @@ impl FromStr for ApiOperation {
             "single_page" => Ok(ApiOperation::SinglePage),
             "gist" => Ok(ApiOperation::Gist),
             "repository_tag" => Ok(ApiOperation::RepositoryTag),
+            "issue" => Ok(ApiOperation::Issue),
             _ => Err(format!("Unknown ApiOperation: {}", s)),
         }
     }
@@ -255,7 +348,8 @@ impl Iterator for ApiOperationIterator {
             Some(ApiOperation::Release) => Some(ApiOperation::SinglePage),
             Some(ApiOperation::SinglePage) => Some(ApiOperation::Gist),
             Some(ApiOperation::Gist) => Some(ApiOperation::RepositoryTag),
-            Some(ApiOperation::RepositoryTag) => None,
+            Some(ApiOperation::RepositoryTag) => Some(ApiOperation::Issue),
+            Some(ApiOperation::Issue) => None,
         };
         self.current = next.clone();
         next
@@ -283,6 +377,7 @@ mod tests {
         );
         assert_eq!(format!("{}", ApiOperation::Release), "release");
         assert_eq!(format!("{}", ApiOperation::SinglePage), "single_page");
+        assert_eq!(format!("{}", ApiOperation::Issue), "issue");
     }
 
     #[test]
@@ -300,8 +395,9 @@ mod tests {
     #[test]
     fn test_api_operation_iterator() {
         let operations: Vec<ApiOperation> = ApiOperation::iter().collect();
-        assert_eq!(operations.len(), 8);
+        assert_eq!(operations.len(), 9);
         assert_eq!(operations[0], ApiOperation::MergeRequest);
         assert_eq!(operations[7], ApiOperation::RepositoryTag);
+        assert_eq!(operations[8], ApiOperation::Issue);
     }
 }
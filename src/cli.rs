@@ -5,6 +5,7 @@ pub mod cicd;
 pub mod common;
 pub mod docker;
 pub mod init;
+pub mod issue;
 pub mod merge_request;
 pub mod my;
 pub mod project;
@@ -19,6 +20,7 @@ use self::cicd::{PipelineCommand, PipelineOptions};
 use self::common::validate_domain_project_repo_path;
 use self::docker::{DockerCommand, DockerOptions};
 use self::init::{InitCommand, InitCommandOptions};
+use self::issue::{IssueCommand, IssueOptions};
 use self::my::MyCommand;
 use self::my::MyOptions;
 use self::project::{ProjectCommand, ProjectOptions};
@@ -74,6 +76,8 @@ struct Args {
 enum Command {
     #[clap(name = "mr", about = "Merge request operations")]
     MergeRequest(MergeRequestCommand),
+    #[clap(name = "is", about = "Issue operations")]
+    Issue(IssueCommand),
     #[clap(name = "br", about = "Open the remote using your browser")]
     Browse(BrowseCommand),
     #[clap(name = "pp", about = "CI/CD Pipeline operations")]
@@ -116,6 +120,7 @@ pub fn parse_cli() -> OptionArgs {
     let args = Args::parse();
     let options = match args.command {
         Command::MergeRequest(sub_matches) => Some(CliOptions::MergeRequest(sub_matches.into())),
+        Command::Issue(sub_matches) => Some(CliOptions::Issue(sub_matches.into())),
         Command::Browse(sub_matches) => Some(CliOptions::Browse(sub_matches.into())),
         Command::Pipeline(sub_matches) => Some(CliOptions::Pipeline(sub_matches.into())),
         Command::Project(sub_matches) => Some(CliOptions::Project(sub_matches.into())),
@@ -137,6 +142,7 @@ pub fn parse_cli() -> OptionArgs {
 
 pub enum CliOptions {
     MergeRequest(MergeRequestOptions),
+    Issue(IssueOptions),
     Browse(BrowseOptions),
     Pipeline(PipelineOptions),
     Project(ProjectOptions),
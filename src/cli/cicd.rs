@@ -2,13 +2,16 @@ use clap::{Parser, ValueEnum};
 
 use crate::{
     cmds::cicd::{
-        mermaid::ChartType, JobListCliArgs, LintFilePathArgs, RunnerListCliArgs,
-        RunnerMetadataGetCliArgs, RunnerPostDataCliArgs, RunnerStatus, RunnerType,
+        mermaid::ChartType, ChartCliArgs, JobArtifactCliArgs, JobListCliArgs, JobLogCliArgs,
+        JobStatus, LintFilePathArgs, PipelineCreateCliArgs, PipelineWatchCliArgs,
+        RunnerListCliArgs, RunnerMetadataGetCliArgs, RunnerPostDataCliArgs, RunnerStatus,
+        RunnerType,
     },
-    remote::ListRemoteCliArgs,
+    remote::{GetRemoteCliArgs, ListRemoteCliArgs},
+    time::Seconds,
 };
 
-use super::common::{GetArgs, ListArgs};
+use super::common::{CacheArgs, GetArgs, ListArgs, RetryArgs};
 
 #[derive(Parser)]
 pub struct PipelineCommand {
@@ -18,16 +21,26 @@ pub struct PipelineCommand {
 
 #[derive(Parser)]
 enum PipelineSubcommand {
-    #[clap(about = "Lint ci yml files. Default is .gitlab-ci.yml")]
+    #[clap(about = "Lint ci yml files. Default is .gitlab-ci.yml or all the GitHub workflows")]
     Lint(FilePathArgs),
     #[clap(
         about = "Get merged .gitlab-ci.yml. Total .gitlab-ci.yml result of merging included yaml pipeline files in the repository"
     )]
-    MergedCi,
-    #[clap(about = "Create a Mermaid diagram of the .gitlab-ci.yml pipeline")]
+    MergedCi(MergedCiArgs),
+    #[clap(about = "Create a Mermaid diagram of the .gitlab-ci.yml pipeline or GitHub workflow")]
     Chart(ChartArgs),
     #[clap(about = "List pipelines")]
     List(ListArgs),
+    #[clap(about = "Run a new pipeline on a branch or ref")]
+    Run(RunPipeline),
+    #[clap(about = "Retry the failed jobs of a pipeline")]
+    Retry(PipelineId),
+    #[clap(about = "Cancel a running pipeline")]
+    Cancel(PipelineId),
+    #[clap(
+        about = "Watch a pipeline until it finishes. Fails if the pipeline fails or waits for a manual action"
+    )]
+    Watch(WatchPipeline),
     #[clap(subcommand, name = "jb", about = "Job operations")]
     Jobs(JobsSubCommand),
     #[clap(subcommand, name = "rn", about = "Runner operations")]
@@ -38,26 +51,174 @@ enum PipelineSubcommand {
 enum JobsSubCommand {
     #[clap(about = "List jobs")]
     List(ListJob),
+    #[clap(about = "Get the log of a job")]
+    Log(JobLog),
+    #[clap(about = "List or download the artifacts of a job")]
+    Artifacts(JobArtifacts),
+}
+
+#[derive(Parser)]
+struct JobArtifacts {
+    /// Job ID
+    #[clap()]
+    id: i64,
+    /// Gitlab: path of a single file inside the artifacts archive. Github:
+    /// name of the artifact to download, required if the run has more than one
+    #[clap(long)]
+    path: Option<String>,
+    /// Directory to save the downloaded artifacts to
+    #[clap(long, short, default_value = ".", value_name = "DIR")]
+    output_dir: String,
+    /// List the job's artifacts instead of downloading them
+    #[clap(long, conflicts_with_all = ["path", "output_dir"])]
+    list: bool,
+    // Formatting options are left out, `-o` is the output directory here.
+    #[clap(flatten)]
+    cache_args: CacheArgs,
+    #[clap(flatten)]
+    retry_args: RetryArgs,
+}
+
+#[derive(Parser)]
+struct JobLog {
+    /// Job ID
+    #[clap()]
+    id: i64,
+    /// Keep polling and printing new log output until the job finishes
+    #[clap(long, short)]
+    follow: bool,
+    /// Seconds to wait in between polls when following the log
+    #[clap(long, default_value = "5", value_name = "SECONDS", requires = "follow")]
+    poll_interval: u64,
+    #[clap(flatten)]
+    retry_args: RetryArgs,
 }
 
 #[derive(Parser)]
 struct ListJob {
+    /// List the jobs of the given pipeline ID
+    #[clap(long, value_name = "ID")]
+    pipeline: Option<i64>,
+    /// Filter by status. Can be specified multiple times or comma separated
+    #[clap(long, value_delimiter = ',')]
+    status: Vec<JobStatusCli>,
+    /// Filter by stage. In Github, the workflow name
+    #[clap(long, conflicts_with = "stream")]
+    stage: Option<String>,
+    /// Filter by job name matching the regular expression
+    #[clap(long, value_name = "REGEX", conflicts_with = "stream")]
+    name: Option<String>,
     #[command(flatten)]
     list_args: ListArgs,
 }
 
+#[derive(ValueEnum, Clone, PartialEq, Debug)]
+enum JobStatusCli {
+    Created,
+    Pending,
+    Running,
+    Failed,
+    Success,
+    Canceled,
+    Skipped,
+    Manual,
+}
+
+impl From<JobStatusCli> for JobStatus {
+    fn from(status: JobStatusCli) -> Self {
+        match status {
+            JobStatusCli::Created => JobStatus::Created,
+            JobStatusCli::Pending => JobStatus::Pending,
+            JobStatusCli::Running => JobStatus::Running,
+            JobStatusCli::Failed => JobStatus::Failed,
+            JobStatusCli::Success => JobStatus::Success,
+            JobStatusCli::Canceled => JobStatus::Canceled,
+            JobStatusCli::Skipped => JobStatus::Skipped,
+            JobStatusCli::Manual => JobStatus::Manual,
+        }
+    }
+}
+
+#[derive(Parser)]
+struct RunPipeline {
+    /// Branch or ref to run the pipeline on. Defaults to the current branch
+    #[clap(long = "ref", value_name = "REF")]
+    ref_name: Option<String>,
+    /// Pipeline variable. In Github, passed as a workflow input. Can be
+    /// specified multiple times
+    #[clap(long = "var", value_name = "KEY=VALUE", value_parser = parse_variable)]
+    variables: Vec<(String, String)>,
+    /// Workflow file name or ID to dispatch. Github only
+    #[clap(long)]
+    workflow: Option<String>,
+}
+
+fn parse_variable(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid KEY=VALUE: no `=` found in `{}`", s)),
+    }
+}
+
+#[derive(Parser)]
+struct WatchPipeline {
+    /// Pipeline ID. Defaults to the latest pipeline of the current branch
+    #[clap(conflicts_with = "branch")]
+    id: Option<i64>,
+    /// Watch the latest pipeline of the branch
+    #[clap(long)]
+    branch: Option<String>,
+    /// Seconds to wait in between polls
+    #[clap(long, default_value = "10", value_name = "SECONDS")]
+    poll_interval: u64,
+}
+
+impl From<WatchPipeline> for PipelineOptions {
+    fn from(options: WatchPipeline) -> Self {
+        PipelineOptions::Watch(
+            PipelineWatchCliArgs::builder()
+                .id(options.id)
+                .branch(options.branch)
+                .poll_interval(Seconds::new(options.poll_interval))
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+#[derive(Parser)]
+struct PipelineId {
+    /// Pipeline ID
+    #[clap()]
+    id: i64,
+}
+
 #[derive(Parser)]
 struct FilePathArgs {
-    /// Path to the ci yml file.
-    #[clap(default_value = ".gitlab-ci.yml")]
-    path: String,
+    /// Path to the ci yml file. Defaults to .gitlab-ci.yml or, on GitHub, to
+    /// all the workflows in .github/workflows
+    #[clap()]
+    path: Option<String>,
+}
+
+#[derive(Parser)]
+struct MergedCiArgs {
+    /// Resolve local includes, extends and !reference tags offline instead of
+    /// using the remote lint API. Remote includes are not resolved.
+    #[clap(long)]
+    local: bool,
 }
 
 #[derive(Parser)]
 struct ChartArgs {
-    /// Chart variant. Stages with jobs, stages or just jobs
+    /// Chart variant. Stages with jobs, stages, just jobs or the jobs `needs`
+    /// dependency graph
     #[clap(long, default_value = "stageswithjobs")]
     chart_type: ChartTypeCli,
+    /// Path to the ci yml file. Defaults to .gitlab-ci.yml or, on GitHub, to
+    /// the only workflow in .github/workflows
+    #[clap()]
+    path: Option<String>,
 }
 
 #[derive(ValueEnum, Clone, PartialEq, Debug)]
@@ -66,6 +227,8 @@ enum ChartTypeCli {
     StagesWithJobs,
     Jobs,
     Stages,
+    /// Job dependencies given by `needs`
+    Dag,
 }
 
 #[derive(Parser)]
@@ -166,13 +329,18 @@ impl From<ChartTypeCli> for ChartType {
             ChartTypeCli::StagesWithJobs => ChartType::StagesWithJobs,
             ChartTypeCli::Jobs => ChartType::Jobs,
             ChartTypeCli::Stages => ChartType::Stages,
+            ChartTypeCli::Dag => ChartType::Dag,
         }
     }
 }
 
-impl From<ChartArgs> for ChartType {
+impl From<ChartArgs> for ChartCliArgs {
     fn from(args: ChartArgs) -> Self {
-        args.chart_type.into()
+        ChartCliArgs::builder()
+            .chart_type(args.chart_type.into())
+            .path(args.path)
+            .build()
+            .unwrap()
     }
 }
 
@@ -186,9 +354,15 @@ impl From<PipelineCommand> for PipelineOptions {
     fn from(options: PipelineCommand) -> Self {
         match options.subcommand {
             PipelineSubcommand::Lint(options) => options.into(),
-            PipelineSubcommand::MergedCi => PipelineOptions::MergedCi,
+            PipelineSubcommand::MergedCi(options) => PipelineOptions::MergedCi {
+                local: options.local,
+            },
             PipelineSubcommand::Chart(options) => PipelineOptions::Chart(options.into()),
             PipelineSubcommand::List(options) => options.into(),
+            PipelineSubcommand::Run(options) => options.into(),
+            PipelineSubcommand::Retry(options) => PipelineOptions::Retry { id: options.id },
+            PipelineSubcommand::Cancel(options) => PipelineOptions::Cancel { id: options.id },
+            PipelineSubcommand::Watch(options) => options.into(),
             PipelineSubcommand::Runners(options) => options.into(),
             PipelineSubcommand::Jobs(options) => options.into(),
         }
@@ -216,6 +390,19 @@ impl From<ListArgs> for PipelineOptions {
     }
 }
 
+impl From<RunPipeline> for PipelineOptions {
+    fn from(options: RunPipeline) -> Self {
+        PipelineOptions::Run(
+            PipelineCreateCliArgs::builder()
+                .ref_name(options.ref_name)
+                .variables(options.variables)
+                .workflow(options.workflow)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 impl From<RunnerSubCommand> for PipelineOptions {
     fn from(options: RunnerSubCommand) -> Self {
         match options {
@@ -299,6 +486,47 @@ impl From<ListJob> for JobOptions {
         JobOptions::List(
             JobListCliArgs::builder()
                 .list_args(options.list_args.into())
+                .pipeline_id(options.pipeline)
+                .status(options.status.into_iter().map(|s| s.into()).collect())
+                .stage(options.stage)
+                .name(options.name)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<JobLog> for JobOptions {
+    fn from(options: JobLog) -> Self {
+        JobOptions::Log(
+            JobLogCliArgs::builder()
+                .id(options.id)
+                .follow(options.follow)
+                .poll_interval(Seconds::new(options.poll_interval))
+                .backoff_max_retries(options.retry_args.max_retries)
+                .backoff_retry_after(options.retry_args.retry_after)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<JobArtifacts> for JobOptions {
+    fn from(options: JobArtifacts) -> Self {
+        JobOptions::Artifacts(
+            JobArtifactCliArgs::builder()
+                .job_id(options.id)
+                .path(options.path)
+                .output_dir(options.output_dir)
+                .list(options.list)
+                .get_args(
+                    GetRemoteCliArgs::builder()
+                        .cache_args(options.cache_args.into())
+                        .backoff_max_retries(options.retry_args.max_retries)
+                        .backoff_retry_after(options.retry_args.retry_after)
+                        .build()
+                        .unwrap(),
+                )
                 .build()
                 .unwrap(),
         )
@@ -309,6 +537,8 @@ impl From<JobsSubCommand> for PipelineOptions {
     fn from(options: JobsSubCommand) -> Self {
         match options {
             JobsSubCommand::List(options) => PipelineOptions::Jobs(options.into()),
+            JobsSubCommand::Log(options) => PipelineOptions::Jobs(options.into()),
+            JobsSubCommand::Artifacts(options) => PipelineOptions::Jobs(options.into()),
         }
     }
 }
@@ -316,14 +546,20 @@ impl From<JobsSubCommand> for PipelineOptions {
 pub enum PipelineOptions {
     Lint(LintFilePathArgs),
     List(ListRemoteCliArgs),
+    Run(PipelineCreateCliArgs),
+    Retry { id: i64 },
+    Cancel { id: i64 },
+    Watch(PipelineWatchCliArgs),
     Runners(RunnerOptions),
-    MergedCi,
-    Chart(ChartType),
+    MergedCi { local: bool },
+    Chart(ChartCliArgs),
     Jobs(JobOptions),
 }
 
 pub enum JobOptions {
     List(JobListCliArgs),
+    Log(JobLogCliArgs),
+    Artifacts(JobArtifactCliArgs),
 }
 
 pub enum RunnerOptions {
@@ -368,6 +604,170 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_pipeline_cli_run() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "run",
+            "--ref",
+            "develop",
+            "--var",
+            "DEPLOY=true",
+            "--var",
+            "URL=https://host/?a=b",
+        ]);
+        let run_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Run(options),
+            }) => options,
+            _ => panic!("Expected PipelineSubcommand::Run"),
+        };
+        let options: PipelineOptions = run_args.into();
+        match options {
+            PipelineOptions::Run(args) => {
+                assert_eq!(Some("develop".to_string()), args.ref_name);
+                assert_eq!(
+                    vec![
+                        ("DEPLOY".to_string(), "true".to_string()),
+                        ("URL".to_string(), "https://host/?a=b".to_string())
+                    ],
+                    args.variables
+                );
+                assert_eq!(None, args.workflow);
+            }
+            _ => panic!("Expected PipelineOptions::Run"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_cli_run_invalid_variable_is_error() {
+        let result = Args::try_parse_from(vec!["gr", "pp", "run", "--var", "DEPLOY"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pipeline_cli_retry_and_cancel() {
+        let args = Args::parse_from(vec!["gr", "pp", "retry", "123"]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Retry { id } => assert_eq!(123, id),
+            _ => panic!("Expected PipelineOptions::Retry"),
+        }
+        let args = Args::parse_from(vec!["gr", "pp", "cancel", "456"]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Cancel { id } => assert_eq!(456, id),
+            _ => panic!("Expected PipelineOptions::Cancel"),
+        }
+    }
+
+    #[test]
+    fn test_watch_pipeline_cli_args() {
+        let args = Args::parse_from(vec!["gr", "pp", "watch", "--branch", "feature"]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Watch(args) => {
+                assert_eq!(None, args.id);
+                assert_eq!(Some("feature".to_string()), args.branch);
+                assert_eq!(Seconds::new(10), args.poll_interval);
+            }
+            _ => panic!("Expected PipelineOptions::Watch"),
+        }
+    }
+
+    #[test]
+    fn test_watch_pipeline_id_conflicts_with_branch() {
+        let result = Args::try_parse_from(vec!["gr", "pp", "watch", "12", "--branch", "main"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_job_log_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "jb",
+            "log",
+            "7",
+            "--follow",
+            "--poll-interval",
+            "10",
+        ]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Jobs(JobOptions::Log(args)) => {
+                assert_eq!(7, args.id);
+                assert!(args.follow);
+                assert_eq!(Seconds::new(10), args.poll_interval);
+                assert_eq!(0, args.backoff_max_retries);
+            }
+            _ => panic!("Expected JobOptions::Log"),
+        }
+    }
+
+    #[test]
+    fn test_job_artifacts_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "jb",
+            "artifacts",
+            "7",
+            "--path",
+            "target/junit.xml",
+            "-o",
+            "/tmp/artifacts",
+        ]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Jobs(JobOptions::Artifacts(args)) => {
+                assert_eq!(7, args.job_id);
+                assert_eq!(Some("target/junit.xml".to_string()), args.path);
+                assert_eq!("/tmp/artifacts", args.output_dir);
+                assert!(!args.list);
+            }
+            _ => panic!("Expected JobOptions::Artifacts"),
+        }
+    }
+
+    #[test]
+    fn test_job_artifacts_list_conflicts_with_download_args() {
+        let result = Args::try_parse_from(vec![
+            "gr",
+            "pp",
+            "jb",
+            "artifacts",
+            "7",
+            "--list",
+            "-o",
+            "out",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_job_log_poll_interval_requires_follow() {
+        let result =
+            Args::try_parse_from(vec!["gr", "pp", "jb", "log", "7", "--poll-interval", "10"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_pipeline_cli_runners_list() {
         let args = Args::parse_from(vec![
@@ -480,7 +880,7 @@ mod test {
             Command::Pipeline(PipelineCommand {
                 subcommand: PipelineSubcommand::Lint(options),
             }) => {
-                assert_eq!(options.path, ".gitlab-ci.yml");
+                assert_eq!(options.path, None);
                 options
             }
             _ => panic!("Expected PipelineCommand"),
@@ -488,7 +888,7 @@ mod test {
         let options: PipelineOptions = options.into();
         match options {
             PipelineOptions::Lint(args) => {
-                assert_eq!(args.path, ".gitlab-ci.yml");
+                assert_eq!(args.path, None);
             }
             _ => panic!("Expected PipelineOptions::Lint"),
         }
@@ -501,7 +901,7 @@ mod test {
             Command::Pipeline(PipelineCommand {
                 subcommand: PipelineSubcommand::Lint(options),
             }) => {
-                assert_eq!(options.path, "path/to/ci.yml");
+                assert_eq!(options.path, Some("path/to/ci.yml".to_string()));
                 options
             }
             _ => panic!("Expected PipelineCommand"),
@@ -509,7 +909,7 @@ mod test {
         let options: PipelineOptions = options.into();
         match options {
             PipelineOptions::Lint(args) => {
-                assert_eq!(args.path, "path/to/ci.yml");
+                assert_eq!(args.path, Some("path/to/ci.yml".to_string()));
             }
             _ => panic!("Expected PipelineOptions::Lint"),
         }
@@ -519,13 +919,24 @@ mod test {
     fn test_merged_ci_file_args() {
         let args = Args::parse_from(vec!["gr", "pp", "merged-ci"]);
         let options = match args.command {
-            Command::Pipeline(PipelineCommand {
-                subcommand: PipelineSubcommand::MergedCi,
-            }) => PipelineOptions::MergedCi,
+            Command::Pipeline(options) => PipelineOptions::from(options),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::MergedCi { local } => assert!(!local),
+            _ => panic!("Expected PipelineOptions::MergedCi"),
+        }
+    }
+
+    #[test]
+    fn test_merged_ci_local_args() {
+        let args = Args::parse_from(vec!["gr", "pp", "merged-ci", "--local"]);
+        let options = match args.command {
+            Command::Pipeline(options) => PipelineOptions::from(options),
             _ => panic!("Expected PipelineCommand"),
         };
         match options {
-            PipelineOptions::MergedCi => {}
+            PipelineOptions::MergedCi { local } => assert!(local),
             _ => panic!("Expected PipelineOptions::MergedCi"),
         }
     }
@@ -545,7 +956,31 @@ mod test {
         let options: PipelineOptions = options.into();
         match options {
             PipelineOptions::Chart(args) => {
-                assert_eq!(args, ChartType::StagesWithJobs);
+                assert_eq!(args.chart_type, ChartType::StagesWithJobs);
+                assert_eq!(args.path, None);
+            }
+            _ => panic!("Expected PipelineOptions::Chart"),
+        }
+    }
+
+    #[test]
+    fn test_chart_dag_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "chart",
+            "--chart-type",
+            "dag",
+            ".github/workflows/ci.yml",
+        ]);
+        let options = match args.command {
+            Command::Pipeline(options) => PipelineOptions::from(options),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Chart(args) => {
+                assert_eq!(args.chart_type, ChartType::Dag);
+                assert_eq!(Some(".github/workflows/ci.yml".to_string()), args.path);
             }
             _ => panic!("Expected PipelineOptions::Chart"),
         }
@@ -580,9 +1015,54 @@ mod test {
                 assert_eq!(args.list_args.from_page, Some(1));
                 assert_eq!(args.list_args.to_page, Some(2));
             }
+            _ => panic!("Expected JobOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_cli_jobs_list_filters() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "jb",
+            "list",
+            "--pipeline",
+            "123",
+            "--status",
+            "failed,manual",
+            "--status",
+            "running",
+            "--stage",
+            "test",
+            "--name",
+            "^clippy",
+        ]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Jobs(JobOptions::List(args)) => {
+                assert_eq!(Some(123), args.pipeline_id);
+                assert_eq!(
+                    vec![JobStatus::Failed, JobStatus::Manual, JobStatus::Running],
+                    args.status
+                );
+                assert_eq!(Some("test".to_string()), args.stage);
+                assert_eq!(Some("^clippy".to_string()), args.name);
+            }
+            _ => panic!("Expected JobOptions::List"),
         }
     }
 
+    #[test]
+    fn test_pipeline_cli_jobs_list_name_filter_conflicts_with_stream() {
+        let result = Args::try_parse_from(vec![
+            "gr", "pp", "jb", "list", "--name", "build", "--stream",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_project_runner_with_project_id() {
         let data = RunnerPostData {
@@ -0,0 +1,289 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cmds::issue::{
+    CommentIssueCliArgs, CommentIssueListCliArgs, IssueCliArgs, IssueGetCliArgs, IssueListCliArgs,
+    IssueState,
+};
+
+use super::common::{GetArgs, ListArgs};
+
+#[derive(Parser)]
+pub struct IssueCommand {
+    #[clap(subcommand)]
+    subcommand: IssueSubcommand,
+}
+
+#[derive(Parser)]
+enum IssueSubcommand {
+    #[clap(about = "Creates an issue", visible_alias = "cr")]
+    Create(CreateIssue),
+    #[clap(about = "Close an issue")]
+    Close(CloseIssue),
+    #[clap(subcommand, about = "Issue comment operations", visible_alias = "cm")]
+    Comment(CommentSubCommand),
+    /// Get an issue
+    Get(GetIssue),
+    #[clap(about = "List issues", visible_alias = "ls")]
+    List(ListIssue),
+}
+
+#[derive(Parser)]
+struct CreateIssue {
+    /// Title of the issue
+    #[clap(long)]
+    pub title: String,
+    /// Description of the issue
+    #[clap(long, group = "description_input")]
+    pub description: Option<String>,
+    /// Gather issue description from the specified file. If "-" is provided,
+    /// read from STDIN
+    #[clap(long, group = "description_input", value_name = "FILE")]
+    pub description_from_file: Option<String>,
+}
+
+#[derive(Parser)]
+struct CloseIssue {
+    /// Id of the issue
+    #[clap()]
+    pub id: i64,
+}
+
+#[derive(Parser)]
+struct GetIssue {
+    /// Id of the issue
+    #[clap()]
+    id: i64,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(ValueEnum, Clone, PartialEq, Debug)]
+pub enum IssueStateCli {
+    Opened,
+    Closed,
+}
+
+impl From<IssueStateCli> for IssueState {
+    fn from(state: IssueStateCli) -> Self {
+        match state {
+            IssueStateCli::Opened => IssueState::Opened,
+            IssueStateCli::Closed => IssueState::Closed,
+        }
+    }
+}
+
+#[derive(Parser)]
+struct ListIssue {
+    #[clap()]
+    pub state: IssueStateCli,
+    #[command(flatten)]
+    pub list_args: ListArgs,
+}
+
+#[derive(Parser)]
+enum CommentSubCommand {
+    /// Create a comment to a given issue
+    Create(CreateCommentIssue),
+    /// List comments of a given issue
+    List(ListCommentIssue),
+}
+
+#[derive(Parser)]
+struct CreateCommentIssue {
+    /// Id of the issue
+    #[clap(long)]
+    pub id: i64,
+    /// Comment to add to the issue
+    #[clap(group = "comment_msg")]
+    pub comment: Option<String>,
+    /// Gather comment from the specified file. If "-" is provided, read from STDIN
+    #[clap(long, value_name = "FILE", group = "comment_msg")]
+    pub comment_from_file: Option<String>,
+}
+
+#[derive(Parser)]
+struct ListCommentIssue {
+    /// Id of the issue
+    #[clap()]
+    pub id: i64,
+    #[command(flatten)]
+    pub list_args: ListArgs,
+}
+
+impl From<IssueCommand> for IssueOptions {
+    fn from(options: IssueCommand) -> Self {
+        match options.subcommand {
+            IssueSubcommand::Create(options) => options.into(),
+            IssueSubcommand::Close(options) => options.into(),
+            IssueSubcommand::Comment(options) => options.into(),
+            IssueSubcommand::Get(options) => options.into(),
+            IssueSubcommand::List(options) => options.into(),
+        }
+    }
+}
+
+impl From<CreateIssue> for IssueOptions {
+    fn from(options: CreateIssue) -> Self {
+        IssueOptions::Create(
+            IssueCliArgs::builder()
+                .title(options.title)
+                .description(options.description)
+                .description_from_file(options.description_from_file)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<CloseIssue> for IssueOptions {
+    fn from(options: CloseIssue) -> Self {
+        IssueOptions::Close { id: options.id }
+    }
+}
+
+impl From<GetIssue> for IssueOptions {
+    fn from(options: GetIssue) -> Self {
+        IssueOptions::Get(
+            IssueGetCliArgs::builder()
+                .id(options.id)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<ListIssue> for IssueOptions {
+    fn from(options: ListIssue) -> Self {
+        IssueOptions::List(
+            IssueListCliArgs::builder()
+                .state(options.state.into())
+                .list_args(options.list_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<CommentSubCommand> for IssueOptions {
+    fn from(options: CommentSubCommand) -> Self {
+        match options {
+            CommentSubCommand::Create(options) => IssueOptions::CreateComment(
+                CommentIssueCliArgs::builder()
+                    .id(options.id)
+                    .comment(options.comment)
+                    .comment_from_file(options.comment_from_file)
+                    .build()
+                    .unwrap(),
+            ),
+            CommentSubCommand::List(options) => IssueOptions::ListComment(
+                CommentIssueListCliArgs::builder()
+                    .id(options.id)
+                    .list_args(options.list_args.into())
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+}
+
+pub enum IssueOptions {
+    Create(IssueCliArgs),
+    Get(IssueGetCliArgs),
+    List(IssueListCliArgs),
+    Close { id: i64 },
+    CreateComment(CommentIssueCliArgs),
+    ListComment(CommentIssueListCliArgs),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_list_issues_cli_args() {
+        let args = Args::parse_from(vec!["gr", "is", "list", "closed", "--num-pages"]);
+        let list_issue = match args.command {
+            Command::Issue(IssueCommand {
+                subcommand: IssueSubcommand::List(options),
+            }) => {
+                assert_eq!(options.state, IssueStateCli::Closed);
+                options
+            }
+            _ => panic!("Expected IssueSubcommand::List"),
+        };
+        let options: IssueOptions = list_issue.into();
+        match options {
+            IssueOptions::List(args) => {
+                assert_eq!(args.state, IssueState::Closed);
+                assert!(args.list_args.num_pages);
+            }
+            _ => panic!("Expected IssueOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_create_issue_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "is",
+            "cr",
+            "--title",
+            "Crash on startup",
+            "--description",
+            "It crashes",
+        ]);
+        let create_issue = match args.command {
+            Command::Issue(IssueCommand {
+                subcommand: IssueSubcommand::Create(options),
+            }) => options,
+            _ => panic!("Expected IssueSubcommand::Create"),
+        };
+        let options: IssueOptions = create_issue.into();
+        match options {
+            IssueOptions::Create(args) => {
+                assert_eq!("Crash on startup", args.title);
+                assert_eq!(Some("It crashes".to_string()), args.description);
+                assert_eq!(None, args.description_from_file);
+            }
+            _ => panic!("Expected IssueOptions::Create"),
+        }
+    }
+
+    #[test]
+    fn test_close_issue_cli_args() {
+        let args = Args::parse_from(vec!["gr", "is", "close", "12"]);
+        let close_issue = match args.command {
+            Command::Issue(IssueCommand {
+                subcommand: IssueSubcommand::Close(options),
+            }) => options,
+            _ => panic!("Expected IssueSubcommand::Close"),
+        };
+        let options: IssueOptions = close_issue.into();
+        match options {
+            IssueOptions::Close { id } => assert_eq!(12, id),
+            _ => panic!("Expected IssueOptions::Close"),
+        }
+    }
+
+    #[test]
+    fn test_comment_issue_cli_args() {
+        let args = Args::parse_from(vec!["gr", "is", "cm", "create", "--id", "12", "LGTM"]);
+        let comment = match args.command {
+            Command::Issue(IssueCommand {
+                subcommand: IssueSubcommand::Comment(options),
+            }) => options,
+            _ => panic!("Expected IssueSubcommand::Comment"),
+        };
+        let options: IssueOptions = comment.into();
+        match options {
+            IssueOptions::CreateComment(args) => {
+                assert_eq!(12, args.id);
+                assert_eq!(Some("LGTM".to_string()), args.comment);
+            }
+            _ => panic!("Expected IssueOptions::CreateComment"),
+        }
+    }
+}
@@ -1,10 +1,19 @@
 use std::option::Option;
 
-use clap::{Parser, ValueEnum};
-
+use clap::{ArgGroup, Parser, ValueEnum};
+
+use crate::cmds::merge_request::commit::MergeRequestCommitListCliArgs;
+use crate::cmds::merge_request::rebase::{MergeRequestRebaseCliArgs, RebaseMode};
+use crate::cmds::merge_request::review::{MergeRequestReviewCliArgs, ReviewVerdict};
+use crate::cmds::merge_request::stack::MergeRequestStackCliArgs;
+use crate::cmds::merge_request::thread::{
+    MergeRequestThreadListCliArgs, MergeRequestThreadReplyCliArgs,
+};
 use crate::cmds::merge_request::{
-    CommentMergeRequestCliArgs, CommentMergeRequestListCliArgs, MergeRequestCliArgs,
-    MergeRequestGetCliArgs, MergeRequestListCliArgs, MergeRequestState, SummaryOptions,
+    CommentMergeRequestCliArgs, CommentMergeRequestEditCliArgs, CommentMergeRequestListCliArgs,
+    MergeMethod, MergeRequestCheckoutCliArgs, MergeRequestCliArgs, MergeRequestDiffCliArgs,
+    MergeRequestGetCliArgs, MergeRequestListCliArgs, MergeRequestMergeCliArgs, MergeRequestState,
+    MergeRequestUpdateCliArgs, Reaction, SummaryOptions,
 };
 
 use super::common::{validate_project_repo_path, CacheArgs, GetArgs, ListArgs};
@@ -31,10 +40,36 @@ enum MergeRequestSubcommand {
         visible_alias = "cm"
     )]
     Comment(CommentSubCommand),
+    #[clap(about = "Review a merge request with line comments and a verdict")]
+    Review(ReviewMergeRequest),
+    #[clap(
+        subcommand,
+        about = "Merge request discussion thread operations",
+        visible_alias = "th"
+    )]
+    Thread(ThreadSubCommand),
     #[clap(about = "Close a merge request")]
     Close(CloseMergeRequest),
+    #[clap(about = "Reopen a closed merge request")]
+    Reopen(ReopenMergeRequest),
+    #[clap(about = "Edit an existing merge request")]
+    Edit(EditMergeRequest),
     /// Get a merge request
     Get(GetMergeRequest),
+    #[clap(about = "Show the changes of a merge request")]
+    Diff(DiffMergeRequest),
+    /// List the commits of a merge request. The CI status of each commit is
+    /// shown along the optional columns
+    Commits(ListCommitsMergeRequest),
+    /// Open or update one merge request per branch of a stack of dependent
+    /// branches, each one targeting the previous branch
+    Stack(StackMergeRequest),
+    /// Check whether a merge request is ready to be merged. Exits with an
+    /// error if it is not, including while its pipeline is still running
+    Status(StatusMergeRequest),
+    /// Update a merge request with the latest changes of its target branch
+    #[clap(visible_alias = "update-branch")]
+    Rebase(RebaseMergeRequest),
     #[clap(about = "List merge requests", visible_alias = "ls")]
     List(ListMergeRequest),
 }
@@ -48,12 +83,200 @@ struct GetMergeRequest {
     get_args: GetArgs,
 }
 
+#[derive(Parser)]
+struct RebaseMergeRequest {
+    /// Id of the merge request. Defaults to the open merge request of the
+    /// current branch
+    #[clap()]
+    id: Option<i64>,
+    /// Ask the remote to update the branch. Gitlab rebases it and Github merges
+    /// the target branch into it. This is the default
+    #[clap(long, group = "rebase_mode")]
+    remote: bool,
+    /// Rebase the current branch onto its target branch locally and force push
+    /// it
+    #[clap(long, group = "rebase_mode")]
+    local: bool,
+}
+
+#[derive(Parser)]
+struct StatusMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct DiffMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Show the number of changed lines per file instead of the diff
+    #[clap(long, group = "diff_output")]
+    stat: bool,
+    /// Show only the paths of the changed files
+    #[clap(long, group = "diff_output")]
+    name_only: bool,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct ReviewMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Gather review comments from the specified file, one per line in the
+    /// format path:line: message. A negative line comments on the old side of
+    /// the diff. If "-" is provided, read from STDIN
+    #[clap(long, value_name = "FILE")]
+    comments_from_file: Option<String>,
+    /// Summary of the review
+    #[clap(long, short)]
+    body: Option<String>,
+    /// Approve the merge request
+    #[clap(long, group = "verdict")]
+    approve: bool,
+    /// Request changes before the merge request can be merged. Github only
+    #[clap(long, group = "verdict")]
+    request_changes: bool,
+}
+
+#[derive(Parser)]
+enum ThreadSubCommand {
+    /// List discussion threads of a given merge request
+    List(ListThreadMergeRequest),
+    /// Reply to a discussion thread
+    Reply(ReplyThreadMergeRequest),
+    /// Resolve a discussion thread
+    Resolve(ResolveThreadMergeRequest),
+    /// Unresolve a discussion thread
+    Unresolve(ResolveThreadMergeRequest),
+}
+
+#[derive(Parser)]
+struct ListThreadMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Show only unresolved threads
+    #[clap(long)]
+    unresolved: bool,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct ReplyThreadMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Id of the thread
+    #[clap()]
+    thread: String,
+    /// Reply to add to the thread
+    #[clap(group = "comment_msg")]
+    comment: Option<String>,
+    /// Gather the reply from the specified file. If "-" is provided, read from STDIN
+    #[clap(long, value_name = "FILE", group = "comment_msg")]
+    comment_from_file: Option<String>,
+}
+
+#[derive(Parser)]
+struct ResolveThreadMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Id of the thread
+    #[clap()]
+    thread: String,
+}
+
 #[derive(Parser)]
 enum CommentSubCommand {
     /// Create a comment to a given merge request
     Create(CreateCommentMergeRequest),
     /// List comments of a given merge request
     List(ListCommentMergeRequest),
+    /// Edit a comment of a given merge request
+    Edit(EditCommentMergeRequest),
+    /// Delete a comment of a given merge request
+    Delete(DeleteCommentMergeRequest),
+    /// React with an emoji to a comment of a given merge request
+    React(ReactCommentMergeRequest),
+}
+
+#[derive(Parser)]
+#[clap(group(ArgGroup::new("comment_msg").required(true)))]
+struct EditCommentMergeRequest {
+    /// Id of the merge request
+    #[clap(long)]
+    pub id: i64,
+    /// Id of the comment
+    #[clap()]
+    pub comment_id: i64,
+    /// New body of the comment
+    #[clap(group = "comment_msg")]
+    pub comment: Option<String>,
+    /// Gather the new body from the specified file. If "-" is provided, read
+    /// from STDIN
+    #[clap(long, value_name = "FILE", group = "comment_msg")]
+    pub comment_from_file: Option<String>,
+}
+
+#[derive(Parser)]
+struct DeleteCommentMergeRequest {
+    /// Id of the merge request
+    #[clap(long)]
+    pub id: i64,
+    /// Id of the comment
+    #[clap()]
+    pub comment_id: i64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReactionCli {
+    #[value(name = "thumbsup", alias = "+1")]
+    ThumbsUp,
+    #[value(name = "thumbsdown", alias = "-1")]
+    ThumbsDown,
+    Laugh,
+    Confused,
+    Heart,
+    #[value(alias = "tada")]
+    Hooray,
+    Rocket,
+    Eyes,
+}
+
+impl From<ReactionCli> for Reaction {
+    fn from(reaction: ReactionCli) -> Self {
+        match reaction {
+            ReactionCli::ThumbsUp => Reaction::ThumbsUp,
+            ReactionCli::ThumbsDown => Reaction::ThumbsDown,
+            ReactionCli::Laugh => Reaction::Laugh,
+            ReactionCli::Confused => Reaction::Confused,
+            ReactionCli::Heart => Reaction::Heart,
+            ReactionCli::Hooray => Reaction::Hooray,
+            ReactionCli::Rocket => Reaction::Rocket,
+            ReactionCli::Eyes => Reaction::Eyes,
+        }
+    }
+}
+
+#[derive(Parser)]
+struct ReactCommentMergeRequest {
+    /// Id of the merge request
+    #[clap(long)]
+    pub id: i64,
+    /// Id of the comment
+    #[clap()]
+    pub comment_id: i64,
+    /// Emoji to react with
+    #[clap(allow_hyphen_values = true)]
+    pub emoji: ReactionCli,
 }
 
 #[derive(Parser)]
@@ -69,6 +292,34 @@ struct CreateCommentMergeRequest {
     pub comment_from_file: Option<String>,
 }
 
+#[derive(Parser)]
+struct StackMergeRequest {
+    /// Branch the bottom of the stack targets instead of the default project's
+    /// upstream branch
+    #[clap(long)]
+    base: Option<String>,
+    /// Set up new merge requests as draft
+    #[clap(long, visible_alias = "wip")]
+    draft: bool,
+    /// Force push the branches of the stack to the remote repository. Commits
+    /// pushed by someone else since the last fetch are never overwritten
+    #[clap(long, short)]
+    force: bool,
+    /// Dry run. Shows the stack without rebasing, pushing or opening merge
+    /// requests
+    #[clap(long)]
+    dry_run: bool,
+}
+
+#[derive(Parser)]
+struct ListCommitsMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    pub id: i64,
+    #[command(flatten)]
+    pub list_args: ListArgs,
+}
+
 #[derive(Parser)]
 struct ListCommentMergeRequest {
     /// Id of the merge request
@@ -126,9 +377,10 @@ struct CreateMergeRequest {
     /// Assignee username
     #[clap(long, short = 'A', value_name = "USERNAME")]
     pub assignee: Option<String>,
-    /// Reviewer username
+    /// Reviewer username. Can be specified multiple times. Overrides the
+    /// reviewer policy in the merge requests configuration section
     #[clap(long, short = 'R', value_name = "USERNAME", group = "reviewer_args")]
-    pub reviewer: Option<String>,
+    pub reviewer: Vec<String>,
     /// Randomly assigns a reviewer from the list of members defined in the merge requests
     /// configuration section.
     #[clap(long, group = "reviewer_args")]
@@ -181,6 +433,17 @@ struct CreateMergeRequest {
     /// Set up the merge request as draft
     #[clap(long, visible_alias = "wip")]
     pub draft: bool,
+    /// Label to add to the merge request. Can be specified multiple times.
+    /// Defaults to the labels in the merge requests configuration section.
+    #[clap(long = "label", short = 'L', value_name = "LABEL")]
+    pub labels: Vec<String>,
+    /// Milestone title
+    #[clap(long)]
+    pub milestone: Option<String>,
+    /// Fill in the description with the given merge request template of the
+    /// repository. Templates are prompted for if there are several ones
+    #[clap(long, value_name = "NAME")]
+    pub template: Option<String>,
     /// Dry run. Does not push the branch and does not create the merge request
     #[clap(long)]
     pub dry_run: bool,
@@ -193,6 +456,7 @@ pub enum MergeRequestStateStateCli {
     Opened,
     Closed,
     Merged,
+    All,
 }
 
 impl From<MergeRequestStateStateCli> for MergeRequestState {
@@ -201,6 +465,7 @@ impl From<MergeRequestStateStateCli> for MergeRequestState {
             MergeRequestStateStateCli::Opened => MergeRequestState::Opened,
             MergeRequestStateStateCli::Closed => MergeRequestState::Closed,
             MergeRequestStateStateCli::Merged => MergeRequestState::Merged,
+            MergeRequestStateStateCli::All => MergeRequestState::All,
         }
     }
 }
@@ -209,15 +474,63 @@ impl From<MergeRequestStateStateCli> for MergeRequestState {
 pub struct ListMergeRequest {
     #[clap()]
     pub state: MergeRequestStateStateCli,
+    /// Filter merge requests by label. Can be specified multiple times, all
+    /// labels must match.
+    #[clap(long = "label", short = 'L', value_name = "LABEL")]
+    pub labels: Vec<String>,
+    /// Add a column with the number of unresolved threads. Takes one extra
+    /// API call per merge request
+    #[clap(long)]
+    pub unresolved_threads: bool,
     #[command(flatten)]
     pub list_args: ListArgs,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum MergeMethodCli {
+    Merge,
+    Rebase,
+    Squash,
+}
+
+impl From<MergeMethodCli> for MergeMethod {
+    fn from(method: MergeMethodCli) -> Self {
+        match method {
+            MergeMethodCli::Merge => MergeMethod::Merge,
+            MergeMethodCli::Rebase => MergeMethod::Rebase,
+            MergeMethodCli::Squash => MergeMethod::Squash,
+        }
+    }
+}
+
 #[derive(Parser)]
 struct MergeMergeRequest {
     /// Id of the merge request
     #[clap()]
     pub id: i64,
+    /// Squash the commits into a single commit. Same as --method squash
+    #[clap(long, group = "merge_method")]
+    pub squash: bool,
+    /// Commit message of the squashed commit. Implies --squash
+    #[clap(long, value_name = "MESSAGE", group = "merge_method")]
+    pub squash_message: Option<String>,
+    /// Merge method. Gitlab sets it at the project level and only supports
+    /// squash. Defaults to the merge_method in the merge requests configuration
+    #[clap(long, value_name = "METHOD", group = "merge_method")]
+    pub method: Option<MergeMethodCli>,
+    /// Delete the source branch after merging
+    #[clap(long, conflicts_with = "keep_branch")]
+    pub delete_branch: bool,
+    /// Keep the source branch after merging, overriding delete_branch in the
+    /// merge requests configuration
+    #[clap(long)]
+    pub keep_branch: bool,
+    /// Only merge if the head of the source branch matches the given SHA
+    #[clap(long)]
+    pub sha: Option<String>,
+    /// Merge when the pipeline succeeds. Auto-merge in Github
+    #[clap(long, visible_alias = "when-pipeline-succeeds")]
+    pub auto_merge: bool,
 }
 
 #[derive(Parser)]
@@ -225,6 +538,14 @@ struct CheckoutMergeRequest {
     /// Id of the merge request
     #[clap()]
     pub id: i64,
+    /// Check out the merge request into a new git worktree at the given
+    /// directory
+    #[clap(long, value_name = "DIR")]
+    pub worktree: Option<String>,
+    /// Check out the head of the merge request in detached HEAD mode, without
+    /// creating a local branch. Useful for read-only reviews
+    #[clap(long)]
+    pub detach: bool,
 }
 
 #[derive(Parser)]
@@ -234,6 +555,55 @@ struct CloseMergeRequest {
     pub id: i64,
 }
 
+#[derive(Parser)]
+struct ReopenMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    pub id: i64,
+}
+
+#[derive(Parser)]
+struct EditMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    pub id: i64,
+    /// New title of the merge request
+    #[clap(long)]
+    pub title: Option<String>,
+    /// New description of the merge request
+    #[clap(long, group = "description_input")]
+    pub description: Option<String>,
+    /// Gather merge request description from the specified file. If "-" is
+    /// provided, read from STDIN
+    #[clap(long, group = "description_input", value_name = "FILE")]
+    pub description_from_file: Option<String>,
+    /// New target branch of the merge request
+    #[clap(long)]
+    pub target_branch: Option<String>,
+    /// Assignee username
+    #[clap(long, short = 'A', value_name = "USERNAME")]
+    pub assignee: Option<String>,
+    /// Reviewer username, replaces the current reviewers. Can be specified
+    /// multiple times
+    #[clap(long, short = 'R', value_name = "USERNAME")]
+    pub reviewer: Vec<String>,
+    /// Convert the merge request to draft
+    #[clap(long, visible_alias = "wip", conflicts_with = "ready")]
+    pub draft: bool,
+    /// Mark the merge request as ready for review
+    #[clap(long)]
+    pub ready: bool,
+    /// Replace the labels of the merge request. Can be specified multiple times
+    #[clap(long = "label", short = 'L', value_name = "LABEL")]
+    pub labels: Vec<String>,
+    /// Milestone title
+    #[clap(long)]
+    pub milestone: Option<String>,
+    /// Prompt for the title and description, prefilled with the current ones
+    #[clap(long, short)]
+    pub interactive: bool,
+}
+
 #[derive(Parser)]
 struct ApproveMergeRequest {
     /// Id of the merge request
@@ -243,22 +613,56 @@ struct ApproveMergeRequest {
 
 impl From<ListMergeRequest> for MergeRequestOptions {
     fn from(options: ListMergeRequest) -> Self {
-        MergeRequestOptions::List(MergeRequestListCliArgs::new(
-            options.state.into(),
-            options.list_args.into(),
-        ))
+        MergeRequestOptions::List(
+            MergeRequestListCliArgs::builder()
+                .state(options.state.into())
+                .list_args(options.list_args.into())
+                .labels(options.labels)
+                .unresolved_threads(options.unresolved_threads)
+                .build()
+                .unwrap(),
+        )
     }
 }
 
 impl From<MergeMergeRequest> for MergeRequestOptions {
     fn from(options: MergeMergeRequest) -> Self {
-        MergeRequestOptions::Merge { id: options.id }
+        let merge_method = if options.squash {
+            Some(MergeMethod::Squash)
+        } else {
+            options.method.map(|method| method.into())
+        };
+        let delete_branch = if options.delete_branch {
+            Some(true)
+        } else if options.keep_branch {
+            Some(false)
+        } else {
+            None
+        };
+        MergeRequestOptions::Merge(
+            MergeRequestMergeCliArgs::builder()
+                .id(options.id)
+                .merge_method(merge_method)
+                .squash_message(options.squash_message)
+                .delete_branch(delete_branch)
+                .sha(options.sha)
+                .auto_merge(options.auto_merge)
+                .build()
+                .unwrap(),
+        )
     }
 }
 
 impl From<CheckoutMergeRequest> for MergeRequestOptions {
     fn from(options: CheckoutMergeRequest) -> Self {
-        MergeRequestOptions::Checkout { id: options.id }
+        MergeRequestOptions::Checkout(
+            MergeRequestCheckoutCliArgs::builder()
+                .id(options.id)
+                .worktree(options.worktree)
+                .detach(options.detach)
+                .build()
+                .unwrap(),
+        )
     }
 }
 
@@ -268,6 +672,38 @@ impl From<CloseMergeRequest> for MergeRequestOptions {
     }
 }
 
+impl From<ReopenMergeRequest> for MergeRequestOptions {
+    fn from(options: ReopenMergeRequest) -> Self {
+        MergeRequestOptions::Reopen { id: options.id }
+    }
+}
+
+impl From<EditMergeRequest> for MergeRequestOptions {
+    fn from(options: EditMergeRequest) -> Self {
+        let draft = match (options.draft, options.ready) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        };
+        MergeRequestOptions::Update(
+            MergeRequestUpdateCliArgs::builder()
+                .id(options.id)
+                .title(options.title)
+                .description(options.description)
+                .description_from_file(options.description_from_file)
+                .target_branch(options.target_branch)
+                .assignee(options.assignee)
+                .reviewers(options.reviewer)
+                .draft(draft)
+                .labels(options.labels)
+                .milestone(options.milestone)
+                .interactive(options.interactive)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 impl From<ApproveMergeRequest> for MergeRequestOptions {
     fn from(options: ApproveMergeRequest) -> Self {
         MergeRequestOptions::Approve { id: options.id }
@@ -282,8 +718,17 @@ impl From<MergeRequestCommand> for MergeRequestOptions {
             MergeRequestSubcommand::Merge(options) => options.into(),
             MergeRequestSubcommand::Checkout(options) => options.into(),
             MergeRequestSubcommand::Close(options) => options.into(),
+            MergeRequestSubcommand::Reopen(options) => options.into(),
+            MergeRequestSubcommand::Edit(options) => options.into(),
             MergeRequestSubcommand::Comment(options) => options.into(),
+            MergeRequestSubcommand::Review(options) => options.into(),
+            MergeRequestSubcommand::Thread(options) => options.into(),
             MergeRequestSubcommand::Get(options) => options.into(),
+            MergeRequestSubcommand::Diff(options) => options.into(),
+            MergeRequestSubcommand::Commits(options) => options.into(),
+            MergeRequestSubcommand::Stack(options) => options.into(),
+            MergeRequestSubcommand::Status(options) => options.into(),
+            MergeRequestSubcommand::Rebase(options) => options.into(),
             MergeRequestSubcommand::Approve(options) => options.into(),
         }
     }
@@ -294,6 +739,16 @@ impl From<CommentSubCommand> for MergeRequestOptions {
         match options {
             CommentSubCommand::Create(options) => options.into(),
             CommentSubCommand::List(options) => options.into(),
+            CommentSubCommand::Edit(options) => options.into(),
+            CommentSubCommand::Delete(options) => MergeRequestOptions::DeleteComment {
+                id: options.id,
+                comment_id: options.comment_id,
+            },
+            CommentSubCommand::React(options) => MergeRequestOptions::ReactComment {
+                id: options.id,
+                comment_id: options.comment_id,
+                reaction: options.emoji.into(),
+            },
         }
     }
 }
@@ -308,7 +763,7 @@ impl From<CreateMergeRequest> for MergeRequestOptions {
                 .description(options.description)
                 .description_from_file(options.description_from_file)
                 .assignee(options.assignee)
-                .reviewer(options.reviewer)
+                .reviewers(options.reviewer)
                 .rand_reviewer(options.rand_reviewer)
                 .target_branch(options.target_branch)
                 .target_repo(options.target_repo)
@@ -326,6 +781,35 @@ impl From<CreateMergeRequest> for MergeRequestOptions {
                 .summary(options.summary.into())
                 .patch(options.patch)
                 .gpt_prompt(options.gpt_prompt)
+                .labels(options.labels)
+                .milestone(options.milestone)
+                .template(options.template)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<StackMergeRequest> for MergeRequestOptions {
+    fn from(options: StackMergeRequest) -> Self {
+        MergeRequestOptions::Stack(
+            MergeRequestStackCliArgs::builder()
+                .base(options.base)
+                .draft(options.draft)
+                .force(options.force)
+                .dry_run(options.dry_run)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<ListCommitsMergeRequest> for MergeRequestOptions {
+    fn from(options: ListCommitsMergeRequest) -> Self {
+        MergeRequestOptions::ListCommits(
+            MergeRequestCommitListCliArgs::builder()
+                .id(options.id)
+                .list_args(options.list_args.into())
                 .build()
                 .unwrap(),
         )
@@ -357,6 +841,20 @@ impl From<CreateCommentMergeRequest> for MergeRequestOptions {
     }
 }
 
+impl From<EditCommentMergeRequest> for MergeRequestOptions {
+    fn from(options: EditCommentMergeRequest) -> Self {
+        MergeRequestOptions::EditComment(
+            CommentMergeRequestEditCliArgs::builder()
+                .id(options.id)
+                .comment_id(options.comment_id)
+                .comment(options.comment)
+                .comment_from_file(options.comment_from_file)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 impl From<GetMergeRequest> for MergeRequestOptions {
     fn from(options: GetMergeRequest) -> Self {
         MergeRequestOptions::Get(
@@ -369,22 +867,152 @@ impl From<GetMergeRequest> for MergeRequestOptions {
     }
 }
 
+impl From<RebaseMergeRequest> for MergeRequestOptions {
+    fn from(options: RebaseMergeRequest) -> Self {
+        let mode = if options.local {
+            RebaseMode::Local
+        } else {
+            RebaseMode::Remote
+        };
+        MergeRequestOptions::Rebase(
+            MergeRequestRebaseCliArgs::builder()
+                .id(options.id)
+                .mode(mode)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<StatusMergeRequest> for MergeRequestOptions {
+    fn from(options: StatusMergeRequest) -> Self {
+        MergeRequestOptions::Status(
+            MergeRequestGetCliArgs::builder()
+                .id(options.id)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<DiffMergeRequest> for MergeRequestOptions {
+    fn from(options: DiffMergeRequest) -> Self {
+        MergeRequestOptions::Diff(
+            MergeRequestDiffCliArgs::builder()
+                .id(options.id)
+                .stat(options.stat)
+                .name_only(options.name_only)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<ThreadSubCommand> for MergeRequestOptions {
+    fn from(options: ThreadSubCommand) -> Self {
+        match options {
+            ThreadSubCommand::List(options) => MergeRequestOptions::ListThread(
+                MergeRequestThreadListCliArgs::builder()
+                    .id(options.id)
+                    .unresolved(options.unresolved)
+                    .get_args(options.get_args.into())
+                    .build()
+                    .unwrap(),
+            ),
+            ThreadSubCommand::Reply(options) => MergeRequestOptions::ReplyThread(
+                MergeRequestThreadReplyCliArgs::builder()
+                    .id(options.id)
+                    .thread_id(options.thread)
+                    .comment(options.comment)
+                    .comment_from_file(options.comment_from_file)
+                    .build()
+                    .unwrap(),
+            ),
+            ThreadSubCommand::Resolve(options) => MergeRequestOptions::ResolveThread {
+                id: options.id,
+                thread_id: options.thread,
+                resolved: true,
+            },
+            ThreadSubCommand::Unresolve(options) => MergeRequestOptions::ResolveThread {
+                id: options.id,
+                thread_id: options.thread,
+                resolved: false,
+            },
+        }
+    }
+}
+
+impl From<ReviewMergeRequest> for MergeRequestOptions {
+    fn from(options: ReviewMergeRequest) -> Self {
+        let verdict = if options.approve {
+            ReviewVerdict::Approve
+        } else if options.request_changes {
+            ReviewVerdict::RequestChanges
+        } else {
+            ReviewVerdict::Comment
+        };
+        MergeRequestOptions::Review(
+            MergeRequestReviewCliArgs::builder()
+                .id(options.id)
+                .comments_from_file(options.comments_from_file)
+                .verdict(verdict)
+                .body(options.body)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 pub enum MergeRequestOptions {
     Create(MergeRequestCliArgs),
     Get(MergeRequestGetCliArgs),
+    Diff(MergeRequestDiffCliArgs),
+    ListCommits(MergeRequestCommitListCliArgs),
+    Stack(MergeRequestStackCliArgs),
+    Status(MergeRequestGetCliArgs),
+    Rebase(MergeRequestRebaseCliArgs),
     List(MergeRequestListCliArgs),
     CreateComment(CommentMergeRequestCliArgs),
     ListComment(CommentMergeRequestListCliArgs),
-    Approve { id: i64 },
-    Merge { id: i64 },
+    EditComment(CommentMergeRequestEditCliArgs),
+    DeleteComment {
+        id: i64,
+        comment_id: i64,
+    },
+    ReactComment {
+        id: i64,
+        comment_id: i64,
+        reaction: Reaction,
+    },
+    Review(MergeRequestReviewCliArgs),
+    ListThread(MergeRequestThreadListCliArgs),
+    ReplyThread(MergeRequestThreadReplyCliArgs),
+    ResolveThread {
+        id: i64,
+        thread_id: String,
+        resolved: bool,
+    },
+    Approve {
+        id: i64,
+    },
+    Merge(MergeRequestMergeCliArgs),
     // TODO: Checkout is a read operation, so we should propagate MergeRequestGetCliArgs
-    Checkout { id: i64 },
-    Close { id: i64 },
+    Checkout(MergeRequestCheckoutCliArgs),
+    Close {
+        id: i64,
+    },
+    Reopen {
+        id: i64,
+    },
+    Update(MergeRequestUpdateCliArgs),
 }
 
 #[cfg(test)]
 mod test {
     use crate::cli::{Args, Command};
+    use crate::display::Format;
 
     use super::*;
 
@@ -425,13 +1053,54 @@ mod test {
 
         let options: MergeRequestOptions = merge_merge_request.into();
         match options {
-            MergeRequestOptions::Merge { id } => {
-                assert_eq!(id, 123);
+            MergeRequestOptions::Merge(args) => {
+                assert_eq!(args.id, 123);
+                assert_eq!(None, args.merge_method);
+                assert_eq!(None, args.delete_branch);
+                assert!(!args.auto_merge);
+            }
+            _ => panic!("Expected MergeRequestOptions::Merge"),
+        }
+    }
+
+    #[test]
+    fn test_merge_merge_request_with_options() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "merge",
+            "123",
+            "--squash",
+            "--delete-branch",
+            "--sha",
+            "6dcb09b",
+            "--when-pipeline-succeeds",
+        ]);
+        let options = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Merge(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Merge"),
+        };
+        match MergeRequestOptions::from(options) {
+            MergeRequestOptions::Merge(args) => {
+                assert_eq!(Some(MergeMethod::Squash), args.merge_method);
+                assert_eq!(Some(true), args.delete_branch);
+                assert_eq!(Some("6dcb09b".to_string()), args.sha);
+                assert!(args.auto_merge);
             }
             _ => panic!("Expected MergeRequestOptions::Merge"),
         }
     }
 
+    #[test]
+    fn test_merge_merge_request_squash_conflicts_with_method() {
+        let result = Args::try_parse_from(vec![
+            "gr", "mr", "merge", "123", "--squash", "--method", "rebase",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_checkout_merge_request_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "checkout", "123"]);
@@ -447,8 +1116,37 @@ mod test {
 
         let options: MergeRequestOptions = checkout_merge_request.into();
         match options {
-            MergeRequestOptions::Checkout { id } => {
-                assert_eq!(id, 123);
+            MergeRequestOptions::Checkout(args) => {
+                assert_eq!(args.id, 123);
+                assert_eq!(None, args.worktree);
+                assert!(!args.detach);
+            }
+            _ => panic!("Expected MergeRequestOptions::Checkout"),
+        }
+    }
+
+    #[test]
+    fn test_checkout_merge_request_into_worktree_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "checkout",
+            "123",
+            "--worktree",
+            "../review",
+            "--detach",
+        ]);
+        let checkout_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Checkout(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Checkout"),
+        };
+        let options: MergeRequestOptions = checkout_merge_request.into();
+        match options {
+            MergeRequestOptions::Checkout(args) => {
+                assert_eq!(Some("../review".to_string()), args.worktree);
+                assert!(args.detach);
             }
             _ => panic!("Expected MergeRequestOptions::Checkout"),
         }
@@ -476,6 +1174,87 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_reopen_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "reopen", "123"]);
+        let reopen_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Reopen(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Reopen"),
+        };
+        let options: MergeRequestOptions = reopen_merge_request.into();
+        match options {
+            MergeRequestOptions::Reopen { id } => assert_eq!(123, id),
+            _ => panic!("Expected MergeRequestOptions::Reopen"),
+        }
+    }
+
+    #[test]
+    fn test_list_all_merge_requests_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "list", "all", "-L", "bug", "-L", "ui"]);
+        let list_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::List"),
+        };
+        let options: MergeRequestOptions = list_merge_request.into();
+        match options {
+            MergeRequestOptions::List(args) => {
+                assert_eq!(args.state, MergeRequestState::All);
+                assert_eq!(vec!["bug", "ui"], args.labels);
+            }
+            _ => panic!("Expected MergeRequestOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_edit_merge_request_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "edit",
+            "123",
+            "--title",
+            "Fix typo",
+            "-R",
+            "tom",
+            "-R",
+            "huck",
+            "--ready",
+            "--label",
+            "bug",
+            "--milestone",
+            "v1.0",
+        ]);
+        let edit_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Edit(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Edit"),
+        };
+        let options: MergeRequestOptions = edit_merge_request.into();
+        match options {
+            MergeRequestOptions::Update(args) => {
+                assert_eq!(123, args.id);
+                assert_eq!(Some("Fix typo".to_string()), args.title);
+                assert_eq!(vec!["tom", "huck"], args.reviewers);
+                assert_eq!(Some(false), args.draft);
+                assert_eq!(vec!["bug"], args.labels);
+                assert_eq!(Some("v1.0".to_string()), args.milestone);
+                assert!(!args.interactive);
+            }
+            _ => panic!("Expected MergeRequestOptions::Update"),
+        }
+    }
+
+    #[test]
+    fn test_edit_merge_request_draft_and_ready_conflict() {
+        let result = Args::try_parse_from(vec!["gr", "mr", "edit", "123", "--draft", "--ready"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_comment_merge_request_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "comment", "create", "--id", "123", "LGTM"]);
@@ -503,6 +1282,102 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_edit_comment_merge_request_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr", "mr", "comment", "edit", "--id", "123", "456", "LGTM!",
+        ]);
+        let comment_options = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Comment(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Comment"),
+        };
+        match MergeRequestOptions::from(comment_options) {
+            MergeRequestOptions::EditComment(args) => {
+                assert_eq!(args.id, 123);
+                assert_eq!(args.comment_id, 456);
+                assert_eq!(args.comment, Some("LGTM!".to_string()));
+            }
+            _ => panic!("Expected MergeRequestOptions::EditComment"),
+        }
+    }
+
+    #[test]
+    fn test_edit_comment_merge_request_requires_new_body() {
+        let result =
+            Args::try_parse_from(vec!["gr", "mr", "comment", "edit", "--id", "123", "456"]);
+        assert!(result.is_err());
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "comment",
+            "edit",
+            "--id",
+            "123",
+            "456",
+            "--comment-from-file",
+            "-",
+        ]);
+        let comment_options = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Comment(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Comment"),
+        };
+        match MergeRequestOptions::from(comment_options) {
+            MergeRequestOptions::EditComment(args) => {
+                assert_eq!(args.comment, None);
+                assert_eq!(args.comment_from_file, Some("-".to_string()));
+            }
+            _ => panic!("Expected MergeRequestOptions::EditComment"),
+        }
+    }
+
+    #[test]
+    fn test_delete_comment_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "comment", "delete", "--id", "123", "456"]);
+        let comment_options = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Comment(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Comment"),
+        };
+        match MergeRequestOptions::from(comment_options) {
+            MergeRequestOptions::DeleteComment { id, comment_id } => {
+                assert_eq!(id, 123);
+                assert_eq!(comment_id, 456);
+            }
+            _ => panic!("Expected MergeRequestOptions::DeleteComment"),
+        }
+    }
+
+    #[test]
+    fn test_react_comment_merge_request_cli_args() {
+        for (emoji, expected) in [
+            ("+1", Reaction::ThumbsUp),
+            ("-1", Reaction::ThumbsDown),
+            ("tada", Reaction::Hooray),
+            ("eyes", Reaction::Eyes),
+        ] {
+            let args = Args::parse_from(vec![
+                "gr", "mr", "comment", "react", "--id", "123", "456", emoji,
+            ]);
+            let comment_options = match args.command {
+                Command::MergeRequest(MergeRequestCommand {
+                    subcommand: MergeRequestSubcommand::Comment(options),
+                }) => options,
+                _ => panic!("Expected MergeRequestCommand::Comment"),
+            };
+            match MergeRequestOptions::from(comment_options) {
+                MergeRequestOptions::ReactComment { reaction, .. } => {
+                    assert_eq!(expected, reaction)
+                }
+                _ => panic!("Expected MergeRequestOptions::ReactComment"),
+            }
+        }
+    }
+
     #[test]
     fn test_list_all_comments_in_merge_request_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "comment", "list", "123"]);
@@ -576,6 +1451,144 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_diff_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "diff", "123", "--stat"]);
+        let diff_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Diff(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Diff"),
+        };
+        match MergeRequestOptions::from(diff_merge_request) {
+            MergeRequestOptions::Diff(args) => {
+                assert_eq!(args.id, 123);
+                assert!(args.stat);
+                assert!(!args.name_only);
+            }
+            _ => panic!("Expected MergeRequestOptions::Diff"),
+        }
+    }
+
+    #[test]
+    fn test_diff_merge_request_stat_conflicts_with_name_only() {
+        let result = Args::try_parse_from(vec!["gr", "mr", "diff", "123", "--stat", "--name-only"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_thread_resolve_and_unresolve_cli_args() {
+        for (subcommand, expected) in [("resolve", true), ("unresolve", false)] {
+            let args = Args::parse_from(vec!["gr", "mr", "thread", subcommand, "12", "abc"]);
+            let thread_options = match args.command {
+                Command::MergeRequest(MergeRequestCommand {
+                    subcommand: MergeRequestSubcommand::Thread(options),
+                }) => options,
+                _ => panic!("Expected MergeRequestCommand::Thread"),
+            };
+            match MergeRequestOptions::from(thread_options) {
+                MergeRequestOptions::ResolveThread {
+                    id,
+                    thread_id,
+                    resolved,
+                } => {
+                    assert_eq!(12, id);
+                    assert_eq!("abc", thread_id);
+                    assert_eq!(expected, resolved);
+                }
+                _ => panic!("Expected MergeRequestOptions::ResolveThread"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_thread_reply_requires_comment_or_file() {
+        let args = Args::parse_from(vec!["gr", "mr", "th", "reply", "12", "abc", "Done"]);
+        let thread_options = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Thread(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Thread"),
+        };
+        match MergeRequestOptions::from(thread_options) {
+            MergeRequestOptions::ReplyThread(args) => {
+                assert_eq!("abc", args.thread_id);
+                assert_eq!(Some("Done".to_string()), args.comment);
+            }
+            _ => panic!("Expected MergeRequestOptions::ReplyThread"),
+        }
+        let result = Args::try_parse_from(vec![
+            "gr",
+            "mr",
+            "th",
+            "reply",
+            "12",
+            "abc",
+            "Done",
+            "--comment-from-file",
+            "reply.txt",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_list_merge_requests_unresolved_threads_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "list", "opened", "--unresolved-threads"]);
+        let list_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::List"),
+        };
+        match MergeRequestOptions::from(list_merge_request) {
+            MergeRequestOptions::List(args) => assert!(args.unresolved_threads),
+            _ => panic!("Expected MergeRequestOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_review_merge_request_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "review",
+            "123",
+            "--comments-from-file",
+            "lint.txt",
+            "--request-changes",
+            "-b",
+            "Some nits",
+        ]);
+        let review_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Review(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestCommand::Review"),
+        };
+        match MergeRequestOptions::from(review_merge_request) {
+            MergeRequestOptions::Review(args) => {
+                assert_eq!(args.id, 123);
+                assert_eq!(args.comments_from_file, Some("lint.txt".to_string()));
+                assert_eq!(args.verdict, ReviewVerdict::RequestChanges);
+                assert_eq!(args.body, Some("Some nits".to_string()));
+            }
+            _ => panic!("Expected MergeRequestOptions::Review"),
+        }
+    }
+
+    #[test]
+    fn test_review_merge_request_approve_conflicts_with_request_changes() {
+        let result = Args::try_parse_from(vec![
+            "gr",
+            "mr",
+            "review",
+            "123",
+            "--approve",
+            "--request-changes",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_wip_alias_as_draft() {
         let args = Args::parse_from(vec!["gr", "mr", "create", "--auto", "--wip"]);
@@ -717,13 +1730,13 @@ mod test {
             Command::MergeRequest(MergeRequestCommand {
                 subcommand: MergeRequestSubcommand::Create(options),
             }) => {
-                assert_eq!(options.reviewer, Some("john_doe".to_string()));
+                assert_eq!(options.reviewer, vec!["john_doe"]);
                 assert!(!options.rand_reviewer);
 
                 let mr_options: MergeRequestOptions = options.into();
                 match mr_options {
                     MergeRequestOptions::Create(args) => {
-                        assert_eq!(args.reviewer, Some("john_doe".to_string()));
+                        assert_eq!(args.reviewers, vec!["john_doe"]);
                         assert!(!args.rand_reviewer);
                     }
                     _ => panic!("Expected MergeRequestOptions::Create"),
@@ -742,13 +1755,13 @@ mod test {
                 subcommand: MergeRequestSubcommand::Create(options),
             }) => {
                 assert!(options.rand_reviewer);
-                assert_eq!(options.reviewer, None);
+                assert!(options.reviewer.is_empty());
 
                 let mr_options: MergeRequestOptions = options.into();
                 match mr_options {
                     MergeRequestOptions::Create(args) => {
                         assert!(args.rand_reviewer);
-                        assert_eq!(args.reviewer, None);
+                        assert!(args.reviewers.is_empty());
                     }
                     _ => panic!("Expected MergeRequestOptions::Create"),
                 }
@@ -779,13 +1792,13 @@ mod test {
             Command::MergeRequest(MergeRequestCommand {
                 subcommand: MergeRequestSubcommand::Create(options),
             }) => {
-                assert_eq!(options.reviewer, Some("jane_doe".to_string()));
+                assert_eq!(options.reviewer, vec!["jane_doe"]);
                 assert!(!options.rand_reviewer);
 
                 let mr_options: MergeRequestOptions = options.into();
                 match mr_options {
                     MergeRequestOptions::Create(args) => {
-                        assert_eq!(args.reviewer, Some("jane_doe".to_string()));
+                        assert_eq!(args.reviewers, vec!["jane_doe"]);
                         assert!(!args.rand_reviewer);
                     }
                     _ => panic!("Expected MergeRequestOptions::Create"),
@@ -794,4 +1807,132 @@ mod test {
             _ => panic!("Expected MergeRequestCommand::Create"),
         }
     }
+
+    #[test]
+    fn test_list_merge_request_commits_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "commits", "123", "--from-page", "2", "-o"]);
+        let list_commits_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Commits(options),
+            }) => {
+                assert_eq!(options.id, 123);
+                options
+            }
+            _ => panic!("Expected MergeRequestSubcommand::Commits"),
+        };
+        let options: MergeRequestOptions = list_commits_merge_request.into();
+        match options {
+            MergeRequestOptions::ListCommits(args) => {
+                assert_eq!(args.id, 123);
+                assert_eq!(Some(2), args.list_args.from_page);
+                assert!(args.list_args.get_args.display_optional);
+            }
+            _ => panic!("Expected MergeRequestOptions::ListCommits"),
+        }
+    }
+
+    #[test]
+    fn test_stack_merge_requests_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "stack", "--base", "develop", "--draft"]);
+        let stack_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Stack(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestSubcommand::Stack"),
+        };
+        let options: MergeRequestOptions = stack_merge_request.into();
+        match options {
+            MergeRequestOptions::Stack(args) => {
+                assert_eq!(Some("develop".to_string()), args.base);
+                assert!(args.draft);
+                assert!(!args.force);
+                assert!(!args.dry_run);
+            }
+            _ => panic!("Expected MergeRequestOptions::Stack"),
+        }
+    }
+
+    #[test]
+    fn test_merge_request_status_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "status", "42", "--format", "json"]);
+        let status_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Status(options),
+            }) => {
+                assert_eq!(options.id, 42);
+                options
+            }
+            _ => panic!("Expected MergeRequestSubcommand::Status"),
+        };
+        let options: MergeRequestOptions = status_merge_request.into();
+        match options {
+            MergeRequestOptions::Status(args) => {
+                assert_eq!(args.id, 42);
+                assert!(matches!(args.get_args.format, Format::JSON));
+            }
+            _ => panic!("Expected MergeRequestOptions::Status"),
+        }
+    }
+
+    #[test]
+    fn test_rebase_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "rebase", "42"]);
+        let rebase_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Rebase(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestSubcommand::Rebase"),
+        };
+        let options: MergeRequestOptions = rebase_merge_request.into();
+        match options {
+            MergeRequestOptions::Rebase(args) => {
+                assert_eq!(Some(42), args.id);
+                assert_eq!(RebaseMode::Remote, args.mode);
+            }
+            _ => panic!("Expected MergeRequestOptions::Rebase"),
+        }
+    }
+
+    #[test]
+    fn test_rebase_merge_request_local_current_branch_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "update-branch", "--local"]);
+        let rebase_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Rebase(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestSubcommand::Rebase"),
+        };
+        let options: MergeRequestOptions = rebase_merge_request.into();
+        match options {
+            MergeRequestOptions::Rebase(args) => {
+                assert_eq!(None, args.id);
+                assert_eq!(RebaseMode::Local, args.mode);
+            }
+            _ => panic!("Expected MergeRequestOptions::Rebase"),
+        }
+    }
+
+    #[test]
+    fn test_rebase_merge_request_remote_and_local_are_exclusive() {
+        let result = Args::try_parse_from(vec!["gr", "mr", "rebase", "--remote", "--local"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_merge_request_with_template_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "create", "--template", "bugfix"]);
+        let create_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Create(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestSubcommand::Create"),
+        };
+        let options: MergeRequestOptions = create_merge_request.into();
+        match options {
+            MergeRequestOptions::Create(args) => {
+                assert_eq!(Some("bugfix".to_string()), args.template);
+            }
+            _ => panic!("Expected MergeRequestOptions::Create"),
+        }
+    }
 }
@@ -38,7 +38,7 @@ struct ListMyMergeRequest {
     /// provided. Gitlab and Github.
     #[clap(long, group = "merge_request")]
     author: bool,
-    /// Filter merge requests where you are the reviewer. Gitlab only.
+    /// Filter merge requests where you are the reviewer.
     #[clap(long, group = "merge_request")]
     reviewer: bool,
     #[clap(flatten)]
@@ -68,6 +68,7 @@ impl From<ListMyMergeRequest> for MyOptions {
             MergeRequestListCliArgs::builder()
                 .state(options.list_merge_request.state.into())
                 .list_args(options.list_merge_request.list_args.into())
+                .labels(options.list_merge_request.labels)
                 .assignee(if options.assignee {
                     Some(MergeRequestUser::Me)
                 } else {
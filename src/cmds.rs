@@ -5,6 +5,7 @@ pub mod cicd;
 pub mod common;
 pub mod docker;
 pub mod gist;
+pub mod issue;
 pub mod merge_request;
 pub mod my;
 pub mod project;
@@ -17,7 +17,7 @@ pub fn execute(options: AmpsOptions, config_file: ConfigFilePath) -> Result<()>
             if amp_name_args.is_empty() {
                 let runner = shell::BlockingCommand;
                 let amps = list_amps(runner, amps_scripts.to_str().unwrap())?;
-                let amp_script = dialog::fuzzy_select(amps)?;
+                let amp_script = dialog::fuzzy_select("amp:", amps)?;
                 let stream_runner = shell::StreamingCommand;
                 let amp_runner = Amp::new(dialog::prompt_args, &stream_runner);
                 amp_runner.exec_amps(amp_script, base_path)?;
@@ -1,17 +1,26 @@
-use mermaid::{generate_mermaid_stages_diagram, YamlParser};
-use yaml::load_yaml;
+use mermaid::{generate_mermaid_stages_diagram, ChartType, WorkflowParser, YamlParser};
 
-use crate::api_traits::{Cicd, CicdJob, CicdRunner, Timestamp};
+use crate::api_traits::{Cicd, CicdJob, CicdJobArtifact, CicdRunner, Timestamp};
 use crate::cli::cicd::{JobOptions, PipelineOptions, RunnerOptions};
 use crate::config::ConfigProperties;
 use crate::display::{Column, DisplayBody};
+use crate::git;
+use crate::http::throttle::{self, ThrottleStrategy};
+use crate::io::CmdInfo;
 use crate::remote::{CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
-use crate::{display, error, remote, Result};
+use crate::shell::BlockingCommand;
+use crate::time::{self, Seconds};
+use crate::{display, error, list_remote_objs, remote, Result};
+use regex::Regex;
 use std::fmt::Display;
-use std::io::{Read, Write};
+use std::fs::{self, File};
+use std::io::{BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+pub mod include;
 pub mod mermaid;
+pub mod workflow;
 pub mod yaml;
 
 use super::common::{
@@ -21,7 +30,7 @@ use super::common::{
 
 #[derive(Builder, Clone, Debug)]
 pub struct Pipeline {
-    id: i64,
+    pub id: i64,
     pub status: String,
     web_url: String,
     branch: String,
@@ -35,6 +44,27 @@ impl Pipeline {
     pub fn builder() -> PipelineBuilder {
         PipelineBuilder::default()
     }
+
+    /// Covers both Gitlab pipeline statuses and Github workflow run
+    /// conclusions.
+    pub fn is_finished(&self) -> bool {
+        is_final_status(&self.status)
+    }
+
+    /// A finished pipeline that did not succeed.
+    pub fn is_failed(&self) -> bool {
+        self.is_finished()
+            && !matches!(
+                self.status.as_str(),
+                "success" | "skipped" | "neutral" | "completed"
+            )
+    }
+
+    /// A Gitlab pipeline that is not finished, but will not make progress
+    /// until someone plays one of its manual jobs.
+    pub fn is_blocked(&self) -> bool {
+        self.status == "manual"
+    }
 }
 
 impl Timestamp for Pipeline {
@@ -63,6 +93,9 @@ impl From<Pipeline> for DisplayBody {
 #[derive(Builder, Clone)]
 pub struct PipelineBodyArgs {
     pub from_to_page: Option<ListBodyArgs>,
+    /// Pipelines that ran on the given branch or ref only.
+    #[builder(default)]
+    pub ref_name: Option<String>,
 }
 
 impl PipelineBodyArgs {
@@ -71,9 +104,64 @@ impl PipelineBodyArgs {
     }
 }
 
+#[derive(Builder, Clone)]
+pub struct PipelineCreateCliArgs {
+    /// Branch or ref to run the pipeline on. Defaults to the current branch.
+    #[builder(default)]
+    pub ref_name: Option<String>,
+    #[builder(default)]
+    pub variables: Vec<(String, String)>,
+    /// Github workflow file name or ID to dispatch. Ignored in Gitlab.
+    #[builder(default)]
+    pub workflow: Option<String>,
+}
+
+impl PipelineCreateCliArgs {
+    pub fn builder() -> PipelineCreateCliArgsBuilder {
+        PipelineCreateCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct PipelineWatchCliArgs {
+    /// Pipeline to watch. If not provided, the latest pipeline of the branch.
+    #[builder(default)]
+    pub id: Option<i64>,
+    /// Defaults to the current branch.
+    #[builder(default)]
+    pub branch: Option<String>,
+    #[builder(default = "Seconds::new(10)")]
+    pub poll_interval: Seconds,
+}
+
+impl PipelineWatchCliArgs {
+    pub fn builder() -> PipelineWatchCliArgsBuilder {
+        PipelineWatchCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct PipelineCreateBodyArgs {
+    pub ref_name: String,
+    #[builder(default)]
+    pub variables: Vec<(String, String)>,
+    #[builder(default)]
+    pub workflow: Option<String>,
+}
+
+impl PipelineCreateBodyArgs {
+    pub fn builder() -> PipelineCreateBodyArgsBuilder {
+        PipelineCreateBodyArgsBuilder::default()
+    }
+}
+
+const GITLAB_CI_FILE: &str = ".gitlab-ci.yml";
+
 #[derive(Builder, Clone)]
 pub struct LintFilePathArgs {
-    pub path: String,
+    /// Defaults to .gitlab-ci.yml or, on GitHub, to all the workflows.
+    #[builder(default)]
+    pub path: Option<String>,
 }
 
 impl LintFilePathArgs {
@@ -82,6 +170,20 @@ impl LintFilePathArgs {
     }
 }
 
+#[derive(Builder, Clone)]
+pub struct ChartCliArgs {
+    pub chart_type: ChartType,
+    /// Defaults to .gitlab-ci.yml or, on GitHub, to the only workflow.
+    #[builder(default)]
+    pub path: Option<String>,
+}
+
+impl ChartCliArgs {
+    pub fn builder() -> ChartCliArgsBuilder {
+        ChartCliArgsBuilder::default()
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct LintResponse {
     pub valid: bool,
@@ -349,6 +451,30 @@ impl Job {
     pub fn builder() -> JobBuilder {
         JobBuilder::default()
     }
+
+    /// A job is finished when it has reached a final state and its log
+    /// will not grow anymore. Covers both Gitlab job statuses and Github job
+    /// conclusions. A manual job does not run until it is played, so its log
+    /// does not grow either.
+    pub fn is_finished(&self) -> bool {
+        is_final_status(&self.status) || self.status == "manual"
+    }
+}
+
+fn is_final_status(status: &str) -> bool {
+    matches!(
+        status,
+        "success"
+            | "failed"
+            | "failure"
+            | "canceled"
+            | "cancelled"
+            | "skipped"
+            | "neutral"
+            | "timed_out"
+            | "action_required"
+            | "completed"
+    )
 }
 
 impl From<Job> for DisplayBody {
@@ -380,11 +506,67 @@ impl Timestamp for Job {
     }
 }
 
-// Technically no need to encapsulate the common ListRemoteCliArgs but we might
-// need to add pipeline_id to retrieve jobs from a specific pipeline.
+/// Job statuses that jobs can be filtered by. Named after Gitlab's job
+/// statuses, Github's job statuses and conclusions are mapped onto them.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum JobStatus {
+    Created,
+    Pending,
+    Running,
+    Failed,
+    Success,
+    Canceled,
+    Skipped,
+    Manual,
+}
+
+impl JobStatus {
+    /// Whether the status of a job as reported by the remote matches.
+    pub fn matches(&self, status: &str) -> bool {
+        match self {
+            JobStatus::Created => status == "created",
+            JobStatus::Pending => matches!(
+                status,
+                "pending" | "waiting_for_resource" | "queued" | "waiting" | "requested"
+            ),
+            JobStatus::Running => matches!(status, "running" | "in_progress"),
+            JobStatus::Failed => matches!(status, "failed" | "failure" | "timed_out"),
+            JobStatus::Success => status == "success",
+            JobStatus::Canceled => matches!(status, "canceled" | "cancelled"),
+            JobStatus::Skipped => status == "skipped",
+            JobStatus::Manual => matches!(status, "manual" | "action_required"),
+        }
+    }
+}
+
+impl Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobStatus::Created => write!(f, "created"),
+            JobStatus::Pending => write!(f, "pending"),
+            JobStatus::Running => write!(f, "running"),
+            JobStatus::Failed => write!(f, "failed"),
+            JobStatus::Success => write!(f, "success"),
+            JobStatus::Canceled => write!(f, "canceled"),
+            JobStatus::Skipped => write!(f, "skipped"),
+            JobStatus::Manual => write!(f, "manual"),
+        }
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct JobListCliArgs {
     pub list_args: ListRemoteCliArgs,
+    /// List the jobs of a pipeline instead of the project's jobs.
+    #[builder(default)]
+    pub pipeline_id: Option<i64>,
+    #[builder(default)]
+    pub status: Vec<JobStatus>,
+    #[builder(default)]
+    pub stage: Option<String>,
+    /// Regular expression the job name must match.
+    #[builder(default)]
+    pub name: Option<String>,
 }
 
 impl JobListCliArgs {
@@ -393,15 +575,119 @@ impl JobListCliArgs {
     }
 }
 
+#[derive(Builder, Clone)]
+pub struct JobLogCliArgs {
+    pub id: i64,
+    #[builder(default)]
+    pub follow: bool,
+    /// Polling interval when following the log
+    #[builder(default = "Seconds::new(5)")]
+    pub poll_interval: Seconds,
+    #[builder(default)]
+    pub backoff_max_retries: u32,
+    #[builder(default)]
+    pub backoff_retry_after: u64,
+}
+
+impl JobLogCliArgs {
+    pub fn builder() -> JobLogCliArgsBuilder {
+        JobLogCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct JobLogBodyArgs {
+    pub id: i64,
+    #[builder(default)]
+    pub backoff_max_retries: u32,
+    #[builder(default)]
+    pub backoff_retry_after: u64,
+}
+
+impl JobLogBodyArgs {
+    pub fn builder() -> JobLogBodyArgsBuilder {
+        JobLogBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct JobArtifact {
+    name: String,
+    file_type: String,
+    size: u64,
+}
+
+impl JobArtifact {
+    pub fn builder() -> JobArtifactBuilder {
+        JobArtifactBuilder::default()
+    }
+}
+
+impl From<JobArtifact> for DisplayBody {
+    fn from(a: JobArtifact) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Name", a.name),
+                Column::new("Type", a.file_type),
+                Column::new("Size", a.size.to_string()),
+            ],
+        }
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct JobArtifactCliArgs {
+    pub job_id: i64,
+    /// Gitlab: path of a single file inside the artifacts archive. Github:
+    /// name of the artifact to download.
+    #[builder(default)]
+    pub path: Option<String>,
+    #[builder(default = "\".\".to_string()")]
+    pub output_dir: String,
+    #[builder(default)]
+    pub list: bool,
+    #[builder(default)]
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl JobArtifactCliArgs {
+    pub fn builder() -> JobArtifactCliArgsBuilder {
+        JobArtifactCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct JobArtifactBodyArgs {
+    pub job_id: i64,
+    #[builder(default)]
+    pub path: Option<String>,
+}
+
+impl JobArtifactBodyArgs {
+    pub fn builder() -> JobArtifactBodyArgsBuilder {
+        JobArtifactBodyArgsBuilder::default()
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct JobListBodyArgs {
     pub list_args: Option<ListBodyArgs>,
+    #[builder(default)]
+    pub pipeline_id: Option<i64>,
+    /// Jobs matching any of the statuses. Empty means all.
+    #[builder(default)]
+    pub status: Vec<JobStatus>,
 }
 
 impl JobListBodyArgs {
     pub fn builder() -> JobListBodyArgsBuilder {
         JobListBodyArgsBuilder::default()
     }
+
+    /// For remotes that cannot filter jobs by status server-side.
+    pub fn status_matches(&self, job: &Job) -> bool {
+        self.status.is_empty() || self.status.iter().any(|s| s.matches(&job.status))
+    }
 }
 
 pub fn execute(
@@ -412,24 +698,43 @@ pub fn execute(
 ) -> Result<()> {
     match options {
         PipelineOptions::Lint(args) => {
+            if is_github(&domain) {
+                let files = match args.path {
+                    Some(path) => vec![PathBuf::from(path)],
+                    None => workflow::workflow_files(Path::new("."))?,
+                };
+                return lint_workflow_files(&files, std::io::stdout());
+            }
             // TODO - should propagage cache args
             let remote = remote::get_cicd(domain, path, config, None, CacheType::File)?;
-            let file = std::fs::File::open(args.path)?;
+            let file = std::fs::File::open(args.path.as_deref().unwrap_or(GITLAB_CI_FILE))?;
             let body = read_ci_file(file)?;
             lint_ci_file(remote, &body, false, std::io::stdout())
         }
-        PipelineOptions::MergedCi => {
+        PipelineOptions::MergedCi { local: true } => {
+            local_merged_ci(Path::new("."), GITLAB_CI_FILE, std::io::stdout())
+        }
+        PipelineOptions::MergedCi { local: false } => {
             // TODO - should propagage cache args
             let remote = remote::get_cicd(domain, path, config, None, CacheType::File)?;
-            let file = std::fs::File::open(".gitlab-ci.yml")?;
+            let file = std::fs::File::open(GITLAB_CI_FILE)?;
             let body = read_ci_file(file)?;
             lint_ci_file(remote, &body, true, std::io::stdout())
         }
         PipelineOptions::Chart(args) => {
-            let file = std::fs::File::open(".gitlab-ci.yml")?;
-            let body = read_ci_file(file)?;
-            let parser = YamlParser::new(load_yaml(&String::from_utf8_lossy(&body)));
-            let chart = generate_mermaid_stages_diagram(parser, args)?;
+            let chart = if is_github(&domain) {
+                let file = match args.path {
+                    Some(path) => PathBuf::from(path),
+                    None => workflow::default_workflow(Path::new("."))?,
+                };
+                let parser =
+                    WorkflowParser::new(workflow::load_workflow(&fs::read_to_string(file)?)?);
+                generate_mermaid_stages_diagram(parser, args.chart_type)?
+            } else {
+                let file = args.path.as_deref().unwrap_or(GITLAB_CI_FILE);
+                let parser = YamlParser::new(include::resolve_local(Path::new("."), file)?);
+                generate_mermaid_stages_diagram(parser, args.chart_type)?
+            };
             println!("{}", chart);
             Ok(())
         }
@@ -452,6 +757,64 @@ pub fn execute(
                 .build()?;
             list_pipelines(remote, body_args, cli_args, std::io::stdout())
         }
+        PipelineOptions::Run(cli_args) => {
+            let remote = remote::get_cicd(domain, path, config, None, CacheType::None)?;
+            let ref_name = match cli_args.ref_name {
+                Some(ref_name) => ref_name,
+                None => match git::current_branch(Arc::new(BlockingCommand))? {
+                    CmdInfo::Branch(branch) => branch,
+                    _ => return Err(error::gen("Could not determine the current branch")),
+                },
+            };
+            let body_args = PipelineCreateBodyArgs::builder()
+                .ref_name(ref_name)
+                .variables(cli_args.variables)
+                .workflow(cli_args.workflow)
+                .build()?;
+            create_pipeline(remote, body_args, std::io::stdout())
+        }
+        PipelineOptions::Retry { id } => {
+            let remote = remote::get_cicd(domain, path, config, None, CacheType::None)?;
+            retry_pipeline(remote, id, std::io::stdout())
+        }
+        PipelineOptions::Cancel { id } => {
+            let remote = remote::get_cicd(domain, path, config, None, CacheType::None)?;
+            cancel_pipeline(remote, id, std::io::stdout())
+        }
+        PipelineOptions::Watch(cli_args) => {
+            // Pipeline and job statuses change while watching, never cache.
+            let cicd_remote = remote::get_cicd(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            let job_remote = remote::get_cicd_job(domain, path, config, None, CacheType::None)?;
+            let id = match cli_args.id {
+                Some(id) => id,
+                None => {
+                    let branch = match cli_args.branch {
+                        Some(branch) => branch,
+                        None => match git::current_branch(Arc::new(BlockingCommand))? {
+                            CmdInfo::Branch(branch) => branch,
+                            _ => return Err(error::gen("Could not determine the current branch")),
+                        },
+                    };
+                    latest_pipeline_id(cicd_remote.clone(), &branch)?
+                }
+            };
+            let throttler = throttle::PreFixed::new(cli_args.poll_interval.into());
+            watch_pipeline(
+                cicd_remote,
+                job_remote,
+                id,
+                &throttler,
+                time::now_epoch_seconds,
+                std::io::stdout().is_terminal(),
+                std::io::stdout(),
+            )
+        }
         PipelineOptions::Jobs(options) => match options {
             JobOptions::List(cli_args) => {
                 let remote = remote::get_cicd_job(
@@ -462,7 +825,11 @@ pub fn execute(
                     CacheType::File,
                 )?;
                 let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
-                let body_args = JobListBodyArgs::builder().list_args(from_to_args).build()?;
+                let body_args = JobListBodyArgs::builder()
+                    .list_args(from_to_args)
+                    .pipeline_id(cli_args.pipeline_id)
+                    .status(cli_args.status.clone())
+                    .build()?;
                 if cli_args.list_args.num_pages {
                     return num_job_pages(remote, body_args, std::io::stdout());
                 }
@@ -471,6 +838,25 @@ pub fn execute(
                 }
                 list_jobs(remote, body_args, cli_args, std::io::stdout())
             }
+            JobOptions::Log(cli_args) => {
+                // Logs change while the job runs, so never cache them.
+                let remote = remote::get_cicd_job(domain, path, config, None, CacheType::None)?;
+                let throttler = throttle::PreFixed::new(cli_args.poll_interval.into());
+                job_log(remote, cli_args, &throttler, std::io::stdout())
+            }
+            JobOptions::Artifacts(cli_args) => {
+                let remote = remote::get_cicd_job_artifact(
+                    domain,
+                    path,
+                    config,
+                    Some(&cli_args.get_args.cache_args),
+                    CacheType::File,
+                )?;
+                if cli_args.list {
+                    return list_job_artifacts(remote, cli_args, std::io::stdout());
+                }
+                download_job_artifacts(remote, cli_args, std::io::stdout())
+            }
         },
         PipelineOptions::Runners(options) => match options {
             RunnerOptions::List(cli_args) => {
@@ -515,6 +901,165 @@ pub fn execute(
     }
 }
 
+fn create_pipeline<W: Write>(
+    remote: Arc<dyn Cicd>,
+    body_args: PipelineCreateBodyArgs,
+    mut writer: W,
+) -> Result<()> {
+    let ref_name = body_args.ref_name.clone();
+    match remote.create(body_args)? {
+        Some(pipeline) => writeln!(
+            writer,
+            "Pipeline {} created on {}: {}",
+            pipeline.id, pipeline.branch, pipeline.web_url
+        )?,
+        None => writeln!(writer, "Pipeline requested on {}", ref_name)?,
+    }
+    Ok(())
+}
+
+fn retry_pipeline<W: Write>(remote: Arc<dyn Cicd>, id: i64, mut writer: W) -> Result<()> {
+    remote.retry(id)?;
+    writeln!(writer, "Pipeline {} retried", id)?;
+    Ok(())
+}
+
+fn cancel_pipeline<W: Write>(remote: Arc<dyn Cicd>, id: i64, mut writer: W) -> Result<()> {
+    remote.cancel(id)?;
+    writeln!(writer, "Pipeline {} canceled", id)?;
+    Ok(())
+}
+
+fn latest_pipeline_id(remote: Arc<dyn Cicd>, branch: &str) -> Result<i64> {
+    let body_args = PipelineBodyArgs::builder()
+        .from_to_page(Some(ListBodyArgs::builder().page(1).max_pages(1).build()?))
+        .ref_name(Some(branch.to_string()))
+        .build()?;
+    remote
+        .list(body_args)?
+        .into_iter()
+        .max_by(|a, b| a.created_at.cmp(&b.created_at))
+        .map(|pipeline| pipeline.id)
+        .ok_or_else(|| error::gen(format!("No pipelines found for branch {}", branch)))
+}
+
+/// Polls the pipeline and its jobs redrawing their status until the pipeline
+/// finishes. Errors out if the pipeline failed or is blocked on a manual
+/// action, so it can gate other commands.
+fn watch_pipeline<W: Write>(
+    cicd_remote: Arc<dyn Cicd>,
+    job_remote: Arc<dyn CicdJob>,
+    id: i64,
+    throttler: &dyn ThrottleStrategy,
+    now: fn() -> Seconds,
+    clear_screen: bool,
+    mut writer: W,
+) -> Result<()> {
+    let body_args = JobListBodyArgs::builder()
+        .list_args(None)
+        .pipeline_id(Some(id))
+        .build()?;
+    let mut last_drawn = String::new();
+    loop {
+        let pipeline =
+            wait_on_rate_limit(|| cicd_remote.get_pipeline(id), throttler, now, &mut writer)?;
+        let jobs = wait_on_rate_limit(
+            || job_remote.list(body_args.clone()),
+            throttler,
+            now,
+            &mut writer,
+        )?;
+        let status_table = pipeline_status_table(id, &pipeline, jobs);
+        // Only redraw on changes, keeps the output readable when it is not
+        // a terminal.
+        if status_table != last_drawn {
+            if clear_screen {
+                write!(writer, "\x1b[2J\x1b[H")?;
+            }
+            write!(writer, "{}", status_table)?;
+            writer.flush()?;
+            last_drawn = status_table;
+        }
+        if pipeline.is_finished() {
+            if pipeline.is_failed() {
+                return Err(error::gen(format!(
+                    "Pipeline {} finished with status {}",
+                    id, pipeline.status
+                )));
+            }
+            return Ok(());
+        }
+        if pipeline.is_blocked() {
+            return Err(error::gen(format!(
+                "Pipeline {} is waiting for a manual action",
+                id
+            )));
+        }
+        throttler.throttle(None);
+    }
+}
+
+/// Runs the request, waiting for the rate limit window to reset whenever the
+/// rate limit threshold has been reached instead of bailing out.
+fn wait_on_rate_limit<T, W: Write>(
+    request: impl Fn() -> Result<T>,
+    throttler: &dyn ThrottleStrategy,
+    now: fn() -> Seconds,
+    writer: &mut W,
+) -> Result<T> {
+    loop {
+        match request() {
+            Ok(result) => return Ok(result),
+            Err(err) => match err.downcast_ref::<error::GRError>() {
+                Some(error::GRError::RateLimitExceeded(headers)) => {
+                    let wait = if headers.retry_after > Seconds::new(0) {
+                        headers.retry_after
+                    } else if headers.reset > now() {
+                        headers.reset - now()
+                    } else {
+                        Seconds::new(1)
+                    };
+                    writeln!(writer, "Rate limit reached, resuming in {} seconds", wait)?;
+                    throttler.throttle_for(wait.into());
+                }
+                _ => return Err(err),
+            },
+        }
+    }
+}
+
+/// Compact table of the pipeline's stages and the status of their jobs.
+/// Stages are ordered by their first job.
+fn pipeline_status_table(id: i64, pipeline: &Pipeline, mut jobs: Vec<Job>) -> String {
+    jobs.sort_by_key(|job| job.id);
+    let mut stages: Vec<(String, Vec<String>)> = Vec::new();
+    for job in jobs {
+        let job_status = format!("{} ({})", job.name, job.status);
+        match stages.iter_mut().find(|(stage, _)| *stage == job.stage) {
+            Some((_, stage_jobs)) => stage_jobs.push(job_status),
+            None => stages.push((job.stage, vec![job_status])),
+        }
+    }
+    let width = stages
+        .iter()
+        .map(|(stage, _)| stage.len())
+        .max()
+        .unwrap_or_default();
+    let mut table = format!(
+        "Pipeline {} on {}: {}\n{}\n",
+        id, pipeline.branch, pipeline.status, pipeline.web_url
+    );
+    for (stage, stage_jobs) in stages {
+        table.push_str(&format!(
+            "{:width$} | {}\n",
+            stage,
+            stage_jobs.join(", "),
+            width = width
+        ));
+    }
+    table
+}
+
 fn get_runner_details<W: Write>(
     remote: Arc<dyn CicdRunner>,
     cli_args: RunnerMetadataGetCliArgs,
@@ -534,13 +1079,136 @@ fn list_runners<W: Write>(
     common::list_runners(remote, body_args, cli_args, &mut writer)
 }
 
+/// Lists jobs filtering by stage and name client-side. Pipeline and status
+/// filters are handed over to the remote.
 fn list_jobs<W: Write>(
     remote: Arc<dyn CicdJob>,
     body_args: JobListBodyArgs,
     cli_args: JobListCliArgs,
     mut writer: W,
 ) -> Result<()> {
-    common::list_jobs(remote, body_args, cli_args, &mut writer)
+    let name_re = match &cli_args.name {
+        Some(name) => Some(
+            Regex::new(name)
+                .map_err(|err| error::gen(format!("Invalid job name regex {}: {}", name, err)))?,
+        ),
+        None => None,
+    };
+    let jobs = list_remote_objs!(remote, body_args, cli_args.list_args, writer, CicdJob);
+    let jobs = jobs
+        .into_iter()
+        .filter(|job| match &cli_args.stage {
+            Some(stage) => &job.stage == stage,
+            None => true,
+        })
+        .filter(|job| match &name_re {
+            Some(re) => re.is_match(&job.name),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+    if jobs.is_empty() {
+        writer.write_all(b"No resources found.\n")?;
+        return Ok(());
+    }
+    display::print(&mut writer, jobs, cli_args.list_args.get_args)
+}
+
+/// Prints the log of a job. When following, the remote is polled until the
+/// job finishes and only the bytes not printed yet are written out. Every poll
+/// is a single request: the job status is only checked once the log stops
+/// growing, and the log is fetched one last time after the job finished.
+fn job_log<W: Write>(
+    remote: Arc<dyn CicdJob>,
+    cli_args: JobLogCliArgs,
+    throttler: &dyn ThrottleStrategy,
+    mut writer: W,
+) -> Result<()> {
+    let body_args = JobLogBodyArgs::builder()
+        .id(cli_args.id)
+        .backoff_max_retries(cli_args.backoff_max_retries)
+        .backoff_retry_after(cli_args.backoff_retry_after)
+        .build()?;
+    let mut offset = 0;
+    let mut finished = !cli_args.follow;
+    let mut check_status = false;
+    loop {
+        if check_status {
+            finished = remote.get(cli_args.id)?.is_finished();
+            check_status = false;
+        } else {
+            let log = remote.log(body_args.clone())?;
+            // Log can be truncated or reset by the remote, ex. job retried.
+            if log.len() < offset {
+                offset = 0;
+            }
+            let grew = log.len() > offset;
+            if grew {
+                writer.write_all(&log.as_bytes()[offset..])?;
+                writer.flush()?;
+                offset = log.len();
+            }
+            // The status was gathered before this log, so it is complete.
+            if finished {
+                return Ok(());
+            }
+            check_status = !grew;
+        }
+        throttler.throttle(None);
+    }
+}
+
+fn list_job_artifacts<W: Write>(
+    remote: Arc<dyn CicdJobArtifact>,
+    cli_args: JobArtifactCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let artifacts = remote.list(cli_args.job_id)?;
+    if artifacts.is_empty() {
+        writeln!(writer, "No resources found.")?;
+        return Ok(());
+    }
+    display::print(&mut writer, artifacts, cli_args.get_args)
+}
+
+/// Downloads the job artifacts into the output directory. The file is named
+/// after the requested path, or after the job if the whole archive is
+/// requested. Archives always get a `.zip` extension.
+fn download_job_artifacts<W: Write>(
+    remote: Arc<dyn CicdJobArtifact>,
+    cli_args: JobArtifactCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let file_name = match &cli_args.path {
+        Some(path) => Path::new(path)
+            .file_name()
+            .ok_or_else(|| error::gen(format!("Invalid artifact path: {}", path)))?
+            .to_string_lossy()
+            .to_string(),
+        None => format!("artifacts-{}", cli_args.job_id),
+    };
+    let file_name =
+        if remote.downloads_archive(cli_args.path.as_deref()) && !file_name.ends_with(".zip") {
+            format!("{}.zip", file_name)
+        } else {
+            file_name
+        };
+    let output_dir = Path::new(&cli_args.output_dir);
+    fs::create_dir_all(output_dir)?;
+    let file_path = output_dir.join(file_name);
+    let body_args = JobArtifactBodyArgs::builder()
+        .job_id(cli_args.job_id)
+        .path(cli_args.path)
+        .build()?;
+    let mut file = BufWriter::new(File::create(&file_path)?);
+    if let Err(err) = remote.download(body_args, &mut file) {
+        // Do not leave empty or partially downloaded files behind.
+        drop(file);
+        fs::remove_file(&file_path)?;
+        return Err(err);
+    }
+    file.flush()?;
+    writeln!(writer, "Downloaded {}", file_path.display())?;
+    Ok(())
 }
 
 fn list_pipelines<W: Write>(
@@ -558,6 +1226,35 @@ fn read_ci_file<R: Read>(mut reader: R) -> Result<Vec<u8>> {
     Ok(buf)
 }
 
+// GitHub has no remote lint nor a single CI file, workflows are handled locally.
+fn is_github(domain: &str) -> bool {
+    domain.starts_with("github")
+}
+
+fn lint_workflow_files<W: Write>(files: &[PathBuf], mut writer: W) -> Result<()> {
+    let mut valid = true;
+    for file in files {
+        let content = fs::read_to_string(file)
+            .map_err(|err| error::gen(format!("Could not read {}: {}", file.display(), err)))?;
+        for error in workflow::lint_workflow(&content) {
+            valid = false;
+            writeln!(writer, "{}: {}", file.display(), error)?;
+        }
+    }
+    if !valid {
+        return Err(error::gen("Linting failed."));
+    }
+    writeln!(writer, "File is valid.")?;
+    Ok(())
+}
+
+/// Merges the CI file with its local includes without calling the remote.
+fn local_merged_ci<W: Write>(root: &Path, path: &str, mut writer: W) -> Result<()> {
+    let merged = include::resolve_local(root, path)?;
+    writeln!(writer, "{}", include::to_yaml_string(&merged)?)?;
+    Ok(())
+}
+
 fn lint_ci_file<W: Write>(
     remote: Arc<dyn Cicd>,
     body: &[u8],
@@ -589,8 +1286,14 @@ fn lint_ci_file<W: Write>(
 #[cfg(test)]
 mod test {
     use std::io::Cursor;
+    use std::rc::Rc;
+    use std::sync::Mutex;
 
     use super::*;
+    use crate::http::throttle::NoThrottle;
+    use crate::io::RateLimitHeader;
+    use crate::test::utils::MockThrottler;
+    use crate::time::Milliseconds;
     use crate::{api_traits::NumberDeltaErr, error};
 
     #[derive(Clone, Builder)]
@@ -651,6 +1354,27 @@ mod test {
                 .build()
                 .unwrap())
         }
+
+        fn create(&self, _args: PipelineCreateBodyArgs) -> Result<Option<Pipeline>> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(self.pipelines.first().cloned())
+        }
+
+        fn retry(&self, _id: i64) -> Result<()> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(())
+        }
+
+        fn cancel(&self, _id: i64) -> Result<()> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(())
+        }
     }
 
     #[test]
@@ -812,6 +1536,70 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_create_pipeline_displays_new_pipeline() {
+        let pp_remote = PipelineMock::builder()
+            .pipelines(vec![Pipeline::builder()
+                .id(123)
+                .status("created".to_string())
+                .web_url("https://gitlab.com/owner/repo/-/pipelines/123".to_string())
+                .branch("main".to_string())
+                .sha("1234567890abcdef".to_string())
+                .created_at("2020-01-01T00:00:00Z".to_string())
+                .updated_at("2020-01-01T00:00:00Z".to_string())
+                .duration(0)
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        let body_args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .build()
+            .unwrap();
+        create_pipeline(Arc::new(pp_remote), body_args, &mut buf).unwrap();
+        assert_eq!(
+            "Pipeline 123 created on main: https://gitlab.com/owner/repo/-/pipelines/123\n",
+            String::from_utf8(buf).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_create_pipeline_no_pipeline_returned_displays_ref() {
+        let pp_remote = PipelineMock::builder().build().unwrap();
+        let mut buf = Vec::new();
+        let body_args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .workflow(Some("ci.yml".to_string()))
+            .build()
+            .unwrap();
+        create_pipeline(Arc::new(pp_remote), body_args, &mut buf).unwrap();
+        assert_eq!(
+            "Pipeline requested on main\n",
+            String::from_utf8(buf).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_retry_and_cancel_pipeline() {
+        let pp_remote = Arc::new(PipelineMock::builder().build().unwrap());
+        let mut buf = Vec::new();
+        retry_pipeline(pp_remote.clone(), 123, &mut buf).unwrap();
+        cancel_pipeline(pp_remote, 123, &mut buf).unwrap();
+        assert_eq!(
+            "Pipeline 123 retried\nPipeline 123 canceled\n",
+            String::from_utf8(buf).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_retry_pipeline_error() {
+        let pp_remote = PipelineMock::builder().error(true).build().unwrap();
+        let mut buf = Vec::new();
+        assert!(retry_pipeline(Arc::new(pp_remote), 123, &mut buf).is_err());
+        assert_eq!("", String::from_utf8(buf).unwrap())
+    }
+
     #[derive(Builder, Clone)]
     struct RunnerMock {
         #[builder(default = "vec![]")]
@@ -1026,6 +1814,34 @@ mod test {
         assert_eq!(String::from_utf8(writer).unwrap(), "YAML Error\n");
     }
 
+    #[test]
+    fn test_lint_workflow_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("ci.yml");
+        let invalid = dir.path().join("release.yml");
+        fs::write(
+            &valid,
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n",
+        )
+        .unwrap();
+        fs::write(&invalid, "on: push\njobs:\n  build:\n    needs: test\n").unwrap();
+
+        let mut writer = Vec::new();
+        lint_workflow_files(std::slice::from_ref(&valid), &mut writer).unwrap();
+        assert_eq!("File is valid.\n", String::from_utf8(writer).unwrap());
+
+        let mut writer = Vec::new();
+        let result = lint_workflow_files(&[valid, invalid.clone()], &mut writer);
+        assert_eq!("Linting failed.", result.unwrap_err().to_string());
+        assert_eq!(
+            format!(
+                "{0}: Job build is missing runs-on\n{0}: Job build needs test, which does not exist\n",
+                invalid.display()
+            ),
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
     #[test]
     fn test_get_merged_yaml_from_lint_response() {
         let response = LintResponse::builder()
@@ -1061,6 +1877,7 @@ test:
     }
 
     #[derive(Builder)]
+    #[builder(pattern = "owned")]
     struct JobMock {
         #[builder(default)]
         jobs: Vec<Job>,
@@ -1068,6 +1885,12 @@ test:
         error: bool,
         #[builder(default)]
         num_pages: Option<u32>,
+        // Each call to log/get consumes the first element. Simulates the
+        // log growing and the job changing status while following it.
+        #[builder(default)]
+        logs: Mutex<Vec<String>>,
+        #[builder(default)]
+        statuses: Mutex<Vec<String>>,
     }
 
     impl JobMock {
@@ -1085,6 +1908,18 @@ test:
             Ok(jj)
         }
 
+        fn get(&self, id: i64) -> Result<Job> {
+            let status = self.statuses.lock().unwrap().remove(0);
+            Ok(gen_job(id, &status))
+        }
+
+        fn log(&self, _args: JobLogBodyArgs) -> Result<String> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(self.logs.lock().unwrap().remove(0))
+        }
+
         fn num_pages(&self, _args: JobListBodyArgs) -> Result<Option<u32>> {
             if self.error {
                 return Err(error::gen("Error"));
@@ -1149,6 +1984,585 @@ test:
         );
     }
 
+    fn gen_pipeline(id: i64, status: &str, created_at: &str) -> Pipeline {
+        Pipeline::builder()
+            .id(id)
+            .status(status.to_string())
+            .web_url(format!("https://gitlab.com/owner/repo/-/pipelines/{}", id))
+            .branch("main".to_string())
+            .sha("1234567890abcdef".to_string())
+            .created_at(created_at.to_string())
+            .updated_at(created_at.to_string())
+            .duration(0)
+            .build()
+            .unwrap()
+    }
+
+    // Each call to get_pipeline consumes the first status. A rate limited
+    // status simulates the rate limit threshold being reached.
+    struct WatchMock {
+        statuses: Mutex<Vec<&'static str>>,
+    }
+
+    impl WatchMock {
+        fn new(statuses: Vec<&'static str>) -> Self {
+            WatchMock {
+                statuses: Mutex::new(statuses),
+            }
+        }
+    }
+
+    impl Cicd for WatchMock {
+        fn list(&self, _args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
+            unimplemented!()
+        }
+
+        fn get_pipeline(&self, id: i64) -> Result<Pipeline> {
+            let status = self.statuses.lock().unwrap().remove(0);
+            if status == "rate_limited" {
+                return Err(error::GRError::RateLimitExceeded(RateLimitHeader::new(
+                    0,
+                    Seconds::new(1000),
+                    Seconds::new(0),
+                ))
+                .into());
+            }
+            Ok(gen_pipeline(id, status, "2020-01-01T00:00:00Z"))
+        }
+
+        fn num_pages(&self) -> Result<Option<u32>> {
+            unimplemented!()
+        }
+
+        fn num_resources(&self) -> Result<Option<crate::api_traits::NumberDeltaErr>> {
+            unimplemented!()
+        }
+
+        fn lint(&self, _body: YamlBytes) -> Result<LintResponse> {
+            unimplemented!()
+        }
+
+        fn create(&self, _args: PipelineCreateBodyArgs) -> Result<Option<Pipeline>> {
+            unimplemented!()
+        }
+
+        fn retry(&self, _id: i64) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn cancel(&self, _id: i64) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    fn watch_jobs() -> JobMock {
+        JobMock::builder()
+            .jobs(vec![
+                gen_staged_job(2, "clippy", "test"),
+                gen_staged_job(1, "build", "build"),
+                gen_staged_job(3, "cargo-test", "test"),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_watch_pipeline_until_success_redraws_on_changes() {
+        let cicd_remote = Arc::new(WatchMock::new(vec!["running", "running", "success"]));
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        watch_pipeline(
+            cicd_remote,
+            Arc::new(watch_jobs()),
+            10,
+            &throttler,
+            || Seconds::new(900),
+            false,
+            &mut buf,
+        )
+        .unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            "Pipeline 10 on main: running\n\
+             https://gitlab.com/owner/repo/-/pipelines/10\n\
+             build | build (success)\n\
+             test  | clippy (success), cargo-test (success)\n\
+             Pipeline 10 on main: success\n\
+             https://gitlab.com/owner/repo/-/pipelines/10\n\
+             build | build (success)\n\
+             test  | clippy (success), cargo-test (success)\n",
+            output
+        );
+        assert_eq!(2, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_watch_pipeline_failed_is_error() {
+        let cicd_remote = Arc::new(WatchMock::new(vec!["failed"]));
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        let result = watch_pipeline(
+            cicd_remote,
+            Arc::new(watch_jobs()),
+            10,
+            &throttler,
+            || Seconds::new(900),
+            false,
+            &mut buf,
+        );
+        assert_eq!(
+            "Pipeline 10 finished with status failed",
+            result.unwrap_err().to_string()
+        );
+        assert_eq!(0, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_watch_pipeline_blocked_on_manual_action_is_error() {
+        let cicd_remote = Arc::new(WatchMock::new(vec!["running", "manual"]));
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        let result = watch_pipeline(
+            cicd_remote,
+            Arc::new(watch_jobs()),
+            10,
+            &throttler,
+            || Seconds::new(900),
+            false,
+            &mut buf,
+        );
+        assert_eq!(
+            "Pipeline 10 is waiting for a manual action",
+            result.unwrap_err().to_string()
+        );
+        assert!(String::from_utf8(buf)
+            .unwrap()
+            .contains("Pipeline 10 on main: manual\n"));
+        assert_eq!(1, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_watch_pipeline_waits_for_rate_limit_reset() {
+        let cicd_remote = Arc::new(WatchMock::new(vec!["rate_limited", "success"]));
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        watch_pipeline(
+            cicd_remote,
+            Arc::new(watch_jobs()),
+            10,
+            &throttler,
+            || Seconds::new(900),
+            false,
+            &mut buf,
+        )
+        .unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.starts_with("Rate limit reached, resuming in 100 seconds\n"));
+        assert_eq!(
+            Milliseconds::new(100_000),
+            *throttler.milliseconds_throttled()
+        );
+    }
+
+    #[test]
+    fn test_watch_pipeline_clears_screen_on_redraw() {
+        let cicd_remote = Arc::new(WatchMock::new(vec!["success"]));
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        watch_pipeline(
+            cicd_remote,
+            Arc::new(watch_jobs()),
+            10,
+            &throttler,
+            || Seconds::new(900),
+            true,
+            &mut buf,
+        )
+        .unwrap();
+        assert!(String::from_utf8(buf).unwrap().starts_with("\x1b[2J\x1b[H"));
+    }
+
+    #[test]
+    fn test_latest_pipeline_id_picks_newest() {
+        let remote = PipelineMock::builder()
+            .pipelines(vec![
+                gen_pipeline(2, "success", "2024-01-02T00:00:00Z"),
+                gen_pipeline(3, "running", "2024-01-03T00:00:00Z"),
+                gen_pipeline(1, "failed", "2024-01-01T00:00:00Z"),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(3, latest_pipeline_id(Arc::new(remote), "main").unwrap());
+    }
+
+    #[test]
+    fn test_latest_pipeline_id_no_pipelines_is_error() {
+        let remote = PipelineMock::builder().build().unwrap();
+        assert!(latest_pipeline_id(Arc::new(remote), "main").is_err());
+    }
+
+    #[test]
+    fn test_pipeline_is_failed() {
+        assert!(gen_pipeline(1, "failed", "").is_failed());
+        assert!(gen_pipeline(1, "cancelled", "").is_failed());
+        assert!(!gen_pipeline(1, "success", "").is_failed());
+        assert!(!gen_pipeline(1, "manual", "").is_failed());
+        assert!(!gen_pipeline(1, "manual", "").is_finished());
+        assert!(gen_pipeline(1, "manual", "").is_blocked());
+        assert!(!gen_pipeline(1, "in_progress", "").is_failed());
+        assert!(!gen_pipeline(1, "in_progress", "").is_finished());
+    }
+
+    fn gen_staged_job(id: i64, name: &str, stage: &str) -> Job {
+        let mut job = gen_job(id, "success");
+        job.name = name.to_string();
+        job.stage = stage.to_string();
+        job
+    }
+
+    #[test]
+    fn test_list_jobs_filter_by_stage_and_name() {
+        let jobs = vec![
+            gen_staged_job(1, "build", "build"),
+            gen_staged_job(2, "clippy", "test"),
+            gen_staged_job(3, "cargo-test", "test"),
+        ];
+        let remote = JobMock::builder().jobs(jobs).build().unwrap();
+        let body_args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        let cli_args = JobListCliArgs::builder()
+            .list_args(
+                ListRemoteCliArgs::builder()
+                    .get_args(
+                        GetRemoteCliArgs::builder()
+                            .no_headers(true)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .stage(Some("test".to_string()))
+            .name(Some("^cargo".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_jobs(Arc::new(remote), body_args, cli_args, &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert_eq!(1, output.lines().count());
+        assert!(output.starts_with("3|cargo-test|"));
+    }
+
+    #[test]
+    fn test_list_jobs_filter_no_matches() {
+        let jobs = vec![gen_staged_job(1, "build", "build")];
+        let remote = JobMock::builder().jobs(jobs).build().unwrap();
+        let body_args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        let cli_args = JobListCliArgs::builder()
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .stage(Some("deploy".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_jobs(Arc::new(remote), body_args, cli_args, &mut buf).unwrap();
+        assert_eq!("No resources found.\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn test_list_jobs_invalid_name_regex_is_error() {
+        let remote = JobMock::builder().build().unwrap();
+        let body_args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        let cli_args = JobListCliArgs::builder()
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .name(Some("(build".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        assert!(list_jobs(Arc::new(remote), body_args, cli_args, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_job_status_matches_gitlab_and_github_statuses() {
+        assert!(JobStatus::Failed.matches("failed"));
+        assert!(JobStatus::Failed.matches("failure"));
+        assert!(JobStatus::Running.matches("in_progress"));
+        assert!(JobStatus::Canceled.matches("cancelled"));
+        assert!(JobStatus::Pending.matches("queued"));
+        assert!(!JobStatus::Success.matches("failed"));
+    }
+
+    #[test]
+    fn test_job_list_body_args_status_matches_any() {
+        let body_args = JobListBodyArgs::builder()
+            .list_args(None)
+            .status(vec![JobStatus::Failed, JobStatus::Manual])
+            .build()
+            .unwrap();
+        assert!(body_args.status_matches(&gen_job(1, "manual")));
+        assert!(!body_args.status_matches(&gen_job(1, "success")));
+        let body_args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        assert!(body_args.status_matches(&gen_job(1, "success")));
+    }
+
+    fn gen_job(id: i64, status: &str) -> Job {
+        Job::builder()
+            .id(id)
+            .name("job1".to_string())
+            .branch("main".to_string())
+            .author_name("user1".to_string())
+            .commit_sha("1234567890abcdef".to_string())
+            .pipeline_id(1)
+            .url("https://gitlab.com/owner/repo/-/jobs/1".to_string())
+            .runner_tags(vec![])
+            .stage("build".to_string())
+            .status(status.to_string())
+            .created_at("2020-01-01T00:00:00Z".to_string())
+            .started_at("2020-01-01T00:01:00Z".to_string())
+            .finished_at("".to_string())
+            .duration("0".to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_job_log_no_follow_prints_log_once() {
+        let remote = JobMock::builder()
+            .logs(Mutex::new(vec!["Running with gitlab-runner\n".to_string()]))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        let cli_args = JobLogCliArgs::builder().id(1).build().unwrap();
+        job_log(Arc::new(remote), cli_args, &NoThrottle, &mut buf).unwrap();
+        assert_eq!(
+            "Running with gitlab-runner\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_job_log_follow_prints_only_new_bytes_until_finished() {
+        let remote = JobMock::builder()
+            .statuses(Mutex::new(vec![
+                "running".to_string(),
+                "success".to_string(),
+            ]))
+            .logs(Mutex::new(vec![
+                "step 1\n".to_string(),
+                "step 1\n".to_string(),
+                "step 1\nstep 2\n".to_string(),
+                "step 1\nstep 2\nJob succeeded\n".to_string(),
+                "step 1\nstep 2\nJob succeeded\n".to_string(),
+                "step 1\nstep 2\nJob succeeded\n".to_string(),
+            ]))
+            .build()
+            .unwrap();
+        let remote = Arc::new(remote);
+        let mut buf = Vec::new();
+        let cli_args = JobLogCliArgs::builder().id(1).follow(true).build().unwrap();
+        job_log(remote.clone(), cli_args, &NoThrottle, &mut buf).unwrap();
+        assert_eq!(
+            "step 1\nstep 2\nJob succeeded\n",
+            String::from_utf8(buf).unwrap()
+        );
+        // The status is only requested when the log did not grow.
+        assert!(remote.logs.lock().unwrap().is_empty());
+        assert!(remote.statuses.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_job_log_follow_log_reset_prints_from_start() {
+        let remote = JobMock::builder()
+            .statuses(Mutex::new(vec!["failed".to_string()]))
+            .logs(Mutex::new(vec![
+                "attempt 1 step 1\n".to_string(),
+                "attempt 2\n".to_string(),
+                "attempt 2\n".to_string(),
+                "attempt 2\n".to_string(),
+            ]))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        let cli_args = JobLogCliArgs::builder().id(1).follow(true).build().unwrap();
+        job_log(Arc::new(remote), cli_args, &NoThrottle, &mut buf).unwrap();
+        assert_eq!(
+            "attempt 1 step 1\nattempt 2\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_job_log_error() {
+        let remote = JobMock::builder().error(true).build().unwrap();
+        let mut buf = Vec::new();
+        let cli_args = JobLogCliArgs::builder().id(1).build().unwrap();
+        assert!(job_log(Arc::new(remote), cli_args, &NoThrottle, &mut buf).is_err());
+    }
+
+    struct ArtifactMock {
+        artifacts: Vec<JobArtifact>,
+        content: Option<Vec<u8>>,
+        // Github downloads every artifact as a zip archive.
+        github: bool,
+    }
+
+    impl CicdJobArtifact for ArtifactMock {
+        fn list(&self, _job_id: i64) -> Result<Vec<JobArtifact>> {
+            Ok(self.artifacts.clone())
+        }
+
+        fn download(&self, _args: JobArtifactBodyArgs, writer: &mut dyn Write) -> Result<()> {
+            match &self.content {
+                Some(content) => {
+                    writer.write_all(content)?;
+                    Ok(())
+                }
+                None => Err(error::gen("404 Not Found")),
+            }
+        }
+
+        fn downloads_archive(&self, path: Option<&str>) -> bool {
+            self.github || path.is_none()
+        }
+    }
+
+    #[test]
+    fn test_list_job_artifacts() {
+        let remote = Arc::new(ArtifactMock {
+            artifacts: vec![JobArtifact::builder()
+                .name("artifacts.zip".to_string())
+                .file_type("archive".to_string())
+                .size(1000)
+                .build()
+                .unwrap()],
+            content: None,
+            github: false,
+        });
+        let cli_args = JobArtifactCliArgs::builder()
+            .job_id(7)
+            .list(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_job_artifacts(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "Name|Type|Size\nartifacts.zip|archive|1000\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_job_artifacts_empty() {
+        let remote = Arc::new(ArtifactMock {
+            artifacts: vec![],
+            content: None,
+            github: false,
+        });
+        let cli_args = JobArtifactCliArgs::builder()
+            .job_id(7)
+            .list(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_job_artifacts(remote, cli_args, &mut buf).unwrap();
+        assert_eq!("No resources found.\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn test_download_job_artifacts_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_dir = dir.path().join("out");
+        let remote = Arc::new(ArtifactMock {
+            artifacts: vec![],
+            content: Some(vec![0x50, 0x4b, 0x03, 0x04, 0xff, 0x00]),
+            github: false,
+        });
+        let cli_args = JobArtifactCliArgs::builder()
+            .job_id(7)
+            .output_dir(output_dir.to_string_lossy().to_string())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        download_job_artifacts(remote, cli_args, &mut buf).unwrap();
+        let file_path = output_dir.join("artifacts-7.zip");
+        assert_eq!(
+            vec![0x50, 0x4b, 0x03, 0x04, 0xff, 0x00],
+            fs::read(&file_path).unwrap()
+        );
+        assert_eq!(
+            format!("Downloaded {}\n", file_path.display()),
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_download_job_single_artifact_named_after_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = Arc::new(ArtifactMock {
+            artifacts: vec![],
+            content: Some(b"<testsuites/>".to_vec()),
+            github: false,
+        });
+        let cli_args = JobArtifactCliArgs::builder()
+            .job_id(7)
+            .path(Some("target/junit.xml".to_string()))
+            .output_dir(dir.path().to_string_lossy().to_string())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        download_job_artifacts(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "<testsuites/>",
+            fs::read_to_string(dir.path().join("junit.xml")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_download_github_artifact_named_after_path_is_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = Arc::new(ArtifactMock {
+            artifacts: vec![],
+            content: Some(vec![0x50, 0x4b, 0x03, 0x04]),
+            github: true,
+        });
+        let cli_args = JobArtifactCliArgs::builder()
+            .job_id(7)
+            .path(Some("coverage".to_string()))
+            .output_dir(dir.path().to_string_lossy().to_string())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        download_job_artifacts(remote, cli_args, &mut buf).unwrap();
+        assert!(dir.path().join("coverage.zip").exists());
+    }
+
+    #[test]
+    fn test_download_job_artifacts_error_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = Arc::new(ArtifactMock {
+            artifacts: vec![],
+            content: None,
+            github: false,
+        });
+        let cli_args = JobArtifactCliArgs::builder()
+            .job_id(7)
+            .output_dir(dir.path().to_string_lossy().to_string())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        assert!(download_job_artifacts(remote, cli_args, &mut buf).is_err());
+        assert!(!dir.path().join("artifacts-7.zip").exists());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_job_is_finished() {
+        assert!(gen_job(1, "success").is_finished());
+        assert!(gen_job(1, "failure").is_finished());
+        assert!(gen_job(1, "canceled").is_finished());
+        assert!(!gen_job(1, "running").is_finished());
+        assert!(!gen_job(1, "pending").is_finished());
+        assert!(!gen_job(1, "in_progress").is_finished());
+    }
+
     #[test]
     fn test_create_new_runner() {
         let remote = RunnerMock::builder().build().unwrap();
@@ -0,0 +1,649 @@
+//! Offline resolution of a .gitlab-ci.yml file. Local includes (glob patterns
+//! included), YAML merge keys, `extends` and `!reference` tags are expanded the
+//! same way GitLab does when it merges the configuration, so charts and the
+//! merged yaml can be computed without calling the lint API. Remote, project,
+//! template and component includes cannot be resolved offline and are skipped.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser, Tag},
+    scanner::{Marker, TScalarStyle},
+    yaml::Hash,
+    Yaml, YamlEmitter, YamlLoader,
+};
+
+use crate::{error, Result};
+
+const REFERENCE_TAG: &str = "!reference";
+// Same nesting limit GitLab applies to `!reference` tags.
+const MAX_REFERENCE_DEPTH: usize = 10;
+
+/// Loads the CI file at `path`, relative to the repository `root`, and returns
+/// the merged configuration.
+pub fn resolve_local(root: &Path, path: &str) -> Result<Yaml> {
+    let mut doc = load_with_includes(root, Path::new(path), &mut vec![])?;
+    resolve_extends(&mut doc)?;
+    resolve_references(&mut doc)?;
+    Ok(Yaml::Hash(doc))
+}
+
+pub fn to_yaml_string(yaml: &Yaml) -> Result<String> {
+    let mut out = String::new();
+    YamlEmitter::new(&mut out)
+        .dump(yaml)
+        .map_err(|err| error::gen(format!("Could not serialize merged yaml: {:?}", err)))?;
+    Ok(out.trim_start_matches("---").trim_start().to_string())
+}
+
+// The YAML loader drops tags on sequences, so `!reference [job, key]` would be
+// indistinguishable from a plain array. Wrap tagged sequences into a single
+// key mapping `{"!reference": [job, key]}` so they can be resolved later.
+#[derive(Default)]
+struct ReferenceTagLoader {
+    loader: YamlLoader,
+    // One entry per open sequence or mapping. True for `!reference` sequences.
+    nodes: Vec<bool>,
+}
+
+impl MarkedEventReceiver for ReferenceTagLoader {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        match ev {
+            Event::SequenceStart(
+                aid,
+                Some(Tag {
+                    ref handle,
+                    ref suffix,
+                }),
+            ) if handle == "!" && suffix == "reference" => {
+                self.nodes.push(true);
+                self.loader.on_event(Event::MappingStart(aid, None), mark);
+                self.loader.on_event(
+                    Event::Scalar(REFERENCE_TAG.to_string(), TScalarStyle::Plain, 0, None),
+                    mark,
+                );
+                self.loader.on_event(Event::SequenceStart(0, None), mark);
+            }
+            Event::SequenceStart(..) | Event::MappingStart(..) => {
+                self.nodes.push(false);
+                self.loader.on_event(ev, mark);
+            }
+            Event::SequenceEnd => {
+                self.loader.on_event(ev, mark);
+                if self.nodes.pop() == Some(true) {
+                    self.loader.on_event(Event::MappingEnd, mark);
+                }
+            }
+            Event::MappingEnd => {
+                self.nodes.pop();
+                self.loader.on_event(ev, mark);
+            }
+            _ => self.loader.on_event(ev, mark),
+        }
+    }
+}
+
+fn load(content: &str, file: &Path) -> Result<Hash> {
+    let mut receiver = ReferenceTagLoader::default();
+    Parser::new_from_str(content)
+        .load(&mut receiver, false)
+        .map_err(|err| error::gen(format!("Could not parse {}: {}", file.display(), err)))?;
+    match receiver.loader.documents().first() {
+        Some(Yaml::Hash(hash)) => Ok(expand_merge_keys(Yaml::Hash(hash.clone()))
+            .into_hash()
+            .unwrap()),
+        Some(Yaml::Null) | Some(Yaml::BadValue) | None => Ok(Hash::new()),
+        Some(_) => Err(error::gen(format!(
+            "{} does not contain a yaml mapping",
+            file.display()
+        ))),
+    }
+}
+
+/// Expands `<<: *anchor` merge keys. Explicit keys take precedence over merged
+/// ones and, when merging a list of anchors, the first one wins.
+fn expand_merge_keys(yaml: Yaml) -> Yaml {
+    match yaml {
+        Yaml::Hash(hash) => {
+            let mut merged = Hash::new();
+            let mut own = Hash::new();
+            for (key, value) in hash {
+                let value = expand_merge_keys(value);
+                if key.as_str() != Some("<<") {
+                    own.insert(key, value);
+                    continue;
+                }
+                let sources = match value {
+                    Yaml::Array(sources) => sources,
+                    source => vec![source],
+                };
+                for source in sources {
+                    if let Yaml::Hash(source) = source {
+                        for (key, value) in source {
+                            merged.entry(key).or_insert(value);
+                        }
+                    }
+                }
+            }
+            for (key, value) in own {
+                merged.insert(key, value);
+            }
+            Yaml::Hash(merged)
+        }
+        Yaml::Array(items) => Yaml::Array(items.into_iter().map(expand_merge_keys).collect()),
+        yaml => yaml,
+    }
+}
+
+/// Deep merges `other` into `base`. Mappings are merged recursively, any other
+/// value in `other` replaces the one in `base`.
+fn deep_merge(base: &mut Hash, other: Hash) {
+    for (key, value) in other {
+        if let Yaml::Hash(value) = value {
+            if let Some(Yaml::Hash(base_value)) = base.get_mut(&key) {
+                deep_merge(base_value, value);
+            } else {
+                base.insert(key, Yaml::Hash(value));
+            }
+        } else {
+            base.insert(key, value);
+        }
+    }
+}
+
+fn load_with_includes(root: &Path, file: &Path, visiting: &mut Vec<PathBuf>) -> Result<Hash> {
+    if visiting.iter().any(|visited| visited == file) {
+        return Err(error::gen(format!(
+            "Include cycle detected: {} is included by itself",
+            file.display()
+        )));
+    }
+    let content = fs::read_to_string(root.join(file))
+        .map_err(|err| error::gen(format!("Could not read {}: {}", file.display(), err)))?;
+    let mut doc = load(&content, file)?;
+    let mut merged = Hash::new();
+    if let Some(include) = doc.remove(&Yaml::String("include".to_string())) {
+        visiting.push(file.to_path_buf());
+        for included_file in local_includes(root, &include)? {
+            let included = load_with_includes(root, &included_file, visiting)?;
+            deep_merge(&mut merged, included);
+        }
+        visiting.pop();
+    }
+    // The including file has precedence over the included ones.
+    deep_merge(&mut merged, doc);
+    Ok(merged)
+}
+
+fn local_includes(root: &Path, include: &Yaml) -> Result<Vec<PathBuf>> {
+    let entries = match include {
+        Yaml::Array(entries) => entries.iter().collect(),
+        entry => vec![entry],
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let local = match entry {
+            Yaml::String(path) if !path.starts_with("http://") && !path.starts_with("https://") => {
+                path.as_str()
+            }
+            Yaml::Hash(_) => match entry["local"].as_str() {
+                Some(path) => path,
+                None => continue,
+            },
+            _ => continue,
+        };
+        let local = local.trim_start_matches('/');
+        if local.contains('*') {
+            files.extend(glob_files(root, local)?);
+        } else {
+            files.push(PathBuf::from(local));
+        }
+    }
+    Ok(files)
+}
+
+/// Returns the files under `root` matching the glob `pattern`, sorted by path.
+/// `*` matches within a directory and `**` across directories.
+fn glob_files(root: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let regex = Regex::new(&glob_to_regex(pattern))?;
+    // Only walk from the deepest directory that has no wildcards.
+    let prefix = &pattern[..pattern.find('*').unwrap_or(pattern.len())];
+    let base = match prefix.rfind('/') {
+        Some(pos) => &prefix[..pos],
+        None => "",
+    };
+    let mut files = Vec::new();
+    walk(root, Path::new(base), &mut files)?;
+    let mut files: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|file| regex.is_match(file))
+        .map(PathBuf::from)
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn glob_to_regex(pattern: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+// Collects files recursively as paths relative to `root` using `/` as separator.
+fn walk(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<()> {
+    let entries = match fs::read_dir(root.join(dir)) {
+        Ok(entries) => entries,
+        // Patterns pointing to non-existing directories match nothing.
+        Err(_) => return Ok(()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                walk(root, &path, files)?;
+            }
+        } else {
+            let components: Vec<String> = path
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            files.push(components.join("/"));
+        }
+    }
+    Ok(())
+}
+
+fn resolve_extends(doc: &mut Hash) -> Result<()> {
+    let mut resolved = Vec::new();
+    for (name, job) in doc.iter() {
+        if let (Some(name), true) = (name.as_str(), job.as_hash().is_some()) {
+            if !job["extends"].is_badvalue() {
+                resolved.push((name.to_string(), extended_job(doc, name, &mut vec![])?));
+            }
+        }
+    }
+    for (name, job) in resolved {
+        doc.insert(Yaml::String(name), Yaml::Hash(job));
+    }
+    Ok(())
+}
+
+fn extended_job(doc: &Hash, name: &str, chain: &mut Vec<String>) -> Result<Hash> {
+    if chain.iter().any(|job| job == name) {
+        return Err(error::gen(format!(
+            "Circular extends detected: {} -> {}",
+            chain.join(" -> "),
+            name
+        )));
+    }
+    let mut job = doc[&Yaml::String(name.to_string())]
+        .as_hash()
+        .cloned()
+        .unwrap_or_default();
+    let bases = match job.remove(&Yaml::String("extends".to_string())) {
+        Some(Yaml::String(base)) => vec![base],
+        Some(Yaml::Array(bases)) => bases.into_iter().filter_map(|b| b.into_string()).collect(),
+        _ => vec![],
+    };
+    chain.push(name.to_string());
+    let mut merged = Hash::new();
+    for base in bases {
+        if !doc.contains_key(&Yaml::String(base.clone())) {
+            return Err(error::gen(format!(
+                "Job {} extends {}, which does not exist",
+                name, base
+            )));
+        }
+        deep_merge(&mut merged, extended_job(doc, &base, chain)?);
+    }
+    chain.pop();
+    deep_merge(&mut merged, job);
+    Ok(merged)
+}
+
+fn resolve_references(doc: &mut Hash) -> Result<()> {
+    let source = doc.clone();
+    for (_, value) in doc.iter_mut() {
+        replace_references(value, &source, 0)?;
+    }
+    Ok(())
+}
+
+fn reference_path(hash: &Hash) -> Option<Vec<String>> {
+    if hash.len() != 1 {
+        return None;
+    }
+    hash.get(&Yaml::String(REFERENCE_TAG.to_string()))?
+        .as_vec()?
+        .iter()
+        .map(|key| key.as_str().map(|key| key.to_string()))
+        .collect()
+}
+
+fn replace_references(value: &mut Yaml, doc: &Hash, depth: usize) -> Result<()> {
+    match value {
+        Yaml::Hash(hash) => {
+            if let Some(path) = reference_path(hash) {
+                *value = lookup_reference(doc, &path, depth)?;
+                return Ok(());
+            }
+            for (_, value) in hash.iter_mut() {
+                replace_references(value, doc, depth)?;
+            }
+        }
+        Yaml::Array(items) => {
+            let mut expanded = Vec::with_capacity(items.len());
+            for mut item in items.drain(..) {
+                let is_reference = item.as_hash().and_then(reference_path).is_some();
+                replace_references(&mut item, doc, depth)?;
+                // A referenced sequence inside a sequence, such as a script
+                // line, is flattened into the enclosing one.
+                match item {
+                    Yaml::Array(nested) if is_reference => expanded.extend(nested),
+                    item => expanded.push(item),
+                }
+            }
+            *items = expanded;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn lookup_reference(doc: &Hash, path: &[String], depth: usize) -> Result<Yaml> {
+    if depth >= MAX_REFERENCE_DEPTH {
+        return Err(error::gen(format!(
+            "!reference [{}] is nested too deeply",
+            path.join(", ")
+        )));
+    }
+    let mut current = Some(&Yaml::Hash(doc.clone()));
+    for key in path {
+        current = current
+            .and_then(|value| value.as_hash())
+            .and_then(|hash| hash.get(&Yaml::String(key.to_string())));
+    }
+    let mut value = current.cloned().ok_or_else(|| {
+        error::gen(format!(
+            "!reference [{}] could not be found",
+            path.join(", ")
+        ))
+    })?;
+    replace_references(&mut value, doc, depth + 1)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::cmds::cicd::mermaid::{generate_mermaid_stages_diagram, ChartType, YamlParser};
+
+    fn write_files(files: &[(&str, &str)]) -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn script(yaml: &Yaml, job: &str) -> Vec<String> {
+        yaml[job]["script"]
+            .as_vec()
+            .unwrap()
+            .iter()
+            .map(|line| line.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_local_includes() {
+        let dir = write_files(&[
+            (
+                ".gitlab-ci.yml",
+                "include:\n  - local: /ci/build.yml\n  - ci/test.yml\n  - remote: https://example.com/ci.yml\n  - project: group/project\n    file: ci.yml\nstages: [build, test]\n",
+            ),
+            ("ci/build.yml", "build:\n  stage: build\n  script: [make]\n"),
+            (
+                "ci/test.yml",
+                "include: ci/lint.yml\ntest:\n  stage: test\n  script: [make test]\n",
+            ),
+            ("ci/lint.yml", "lint:\n  stage: test\n  script: [make lint]\n"),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!(vec!["make"], script(&yaml, "build"));
+        assert_eq!(vec!["make test"], script(&yaml, "test"));
+        assert_eq!(vec!["make lint"], script(&yaml, "lint"));
+        assert!(yaml["include"].is_badvalue());
+    }
+
+    #[test]
+    fn test_including_file_overrides_included_keys() {
+        let dir = write_files(&[
+            (
+                ".gitlab-ci.yml",
+                "include: ci/build.yml\nbuild:\n  variables:\n    PROFILE: release\n",
+            ),
+            (
+                "ci/build.yml",
+                "build:\n  stage: build\n  variables:\n    PROFILE: debug\n    TARGET: x86\n  script: [make]\n",
+            ),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!("build", yaml["build"]["stage"].as_str().unwrap());
+        assert_eq!(
+            "release",
+            yaml["build"]["variables"]["PROFILE"].as_str().unwrap()
+        );
+        assert_eq!(
+            "x86",
+            yaml["build"]["variables"]["TARGET"].as_str().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_glob_includes() {
+        let dir = write_files(&[
+            (".gitlab-ci.yml", "include: 'ci/*.yml'\n"),
+            ("ci/a.yml", "job_a:\n  script: [a]\n"),
+            ("ci/b.yml", "job_b:\n  script: [b]\n"),
+            ("ci/nested/c.yml", "job_c:\n  script: [c]\n"),
+            ("ci/readme.md", "not yaml: ["),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!(vec!["a"], script(&yaml, "job_a"));
+        assert_eq!(vec!["b"], script(&yaml, "job_b"));
+        assert!(yaml["job_c"].is_badvalue());
+    }
+
+    #[test]
+    fn test_glob_double_star_matches_nested_directories() {
+        let dir = write_files(&[
+            (".gitlab-ci.yml", "include:\n  - local: 'ci/**/*.yml'\n"),
+            ("ci/a.yml", "job_a:\n  script: [a]\n"),
+            ("ci/nested/deep/c.yml", "job_c:\n  script: [c]\n"),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!(vec!["a"], script(&yaml, "job_a"));
+        assert_eq!(vec!["c"], script(&yaml, "job_c"));
+    }
+
+    #[test]
+    fn test_include_cycle_is_an_error() {
+        let dir = write_files(&[
+            (".gitlab-ci.yml", "include: ci/a.yml\n"),
+            ("ci/a.yml", "include: ci/b.yml\n"),
+            ("ci/b.yml", "include: ci/a.yml\n"),
+        ]);
+        let err = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap_err();
+        assert!(err.to_string().contains("Include cycle detected"));
+    }
+
+    #[test]
+    fn test_missing_local_include_is_an_error() {
+        let dir = write_files(&[(".gitlab-ci.yml", "include: ci/missing.yml\n")]);
+        let err = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap_err();
+        assert!(err.to_string().contains("ci/missing.yml"));
+    }
+
+    #[test]
+    fn test_resolve_extends() {
+        let dir = write_files(&[(
+            ".gitlab-ci.yml",
+            ".base:\n  image: rust\n  variables:\n    A: base\n    B: base\n.test_base:\n  extends: .base\n  stage: test\n  variables:\n    B: test\nunit:\n  extends: [.test_base]\n  variables:\n    C: unit\n  script: [cargo test]\n",
+        )]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        let unit = &yaml["unit"];
+        assert_eq!("rust", unit["image"].as_str().unwrap());
+        assert_eq!("test", unit["stage"].as_str().unwrap());
+        assert_eq!("base", unit["variables"]["A"].as_str().unwrap());
+        assert_eq!("test", unit["variables"]["B"].as_str().unwrap());
+        assert_eq!("unit", unit["variables"]["C"].as_str().unwrap());
+        assert!(unit["extends"].is_badvalue());
+    }
+
+    #[test]
+    fn test_extends_across_included_files() {
+        let dir = write_files(&[
+            (
+                ".gitlab-ci.yml",
+                "include: ci/templates.yml\nbuild:\n  extends: .build\n",
+            ),
+            (
+                "ci/templates.yml",
+                ".build:\n  stage: build\n  script: [make]\n",
+            ),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!("build", yaml["build"]["stage"].as_str().unwrap());
+        assert_eq!(vec!["make"], script(&yaml, "build"));
+    }
+
+    #[test]
+    fn test_circular_extends_is_an_error() {
+        let dir = write_files(&[(".gitlab-ci.yml", "a:\n  extends: b\nb:\n  extends: a\n")]);
+        let err = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap_err();
+        assert!(err.to_string().contains("Circular extends detected"));
+    }
+
+    #[test]
+    fn test_extends_unknown_job_is_an_error() {
+        let dir = write_files(&[(".gitlab-ci.yml", "a:\n  extends: .missing\n")]);
+        let err = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap_err();
+        assert_eq!(
+            "Job a extends .missing, which does not exist",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_resolve_reference_tags() {
+        let dir = write_files(&[(
+            ".gitlab-ci.yml",
+            ".setup:\n  script:\n    - echo setup\n    - echo more\n  variables:\n    A: a\ntest:\n  variables: !reference [.setup, variables]\n  script:\n    - !reference [.setup, script]\n    - echo test\n",
+        )]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!(
+            vec!["echo setup", "echo more", "echo test"],
+            script(&yaml, "test")
+        );
+        assert_eq!("a", yaml["test"]["variables"]["A"].as_str().unwrap());
+    }
+
+    #[test]
+    fn test_nested_reference_tags() {
+        let dir = write_files(&[(
+            ".gitlab-ci.yml",
+            ".a:\n  script: [echo a]\n.b:\n  script:\n    - !reference [.a, script]\n    - echo b\nc:\n  script:\n    - !reference [.b, script]\n",
+        )]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!(vec!["echo a", "echo b"], script(&yaml, "c"));
+    }
+
+    #[test]
+    fn test_missing_reference_is_an_error() {
+        let dir = write_files(&[(
+            ".gitlab-ci.yml",
+            "test:\n  script: !reference [.setup, script]\n",
+        )]);
+        let err = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap_err();
+        assert_eq!(
+            "!reference [.setup, script] could not be found",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_yaml_anchor_merge_keys() {
+        let dir = write_files(&[(
+            ".gitlab-ci.yml",
+            ".defaults: &defaults\n  stage: test\n  image: rust\ntest:\n  <<: *defaults\n  image: alpine\n  script: [make]\n",
+        )]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        assert_eq!("test", yaml["test"]["stage"].as_str().unwrap());
+        assert_eq!("alpine", yaml["test"]["image"].as_str().unwrap());
+        assert!(yaml["test"]["<<"].is_badvalue());
+    }
+
+    #[test]
+    fn test_merged_yaml_to_string() {
+        let dir = write_files(&[
+            (".gitlab-ci.yml", "include: ci/build.yml\nstages: [build]\n"),
+            ("ci/build.yml", "build:\n  stage: build\n  script: [make]\n"),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        let merged = to_yaml_string(&yaml).unwrap();
+        assert_eq!(
+            "build:\n  stage: build\n  script:\n    - make\nstages:\n  - build",
+            merged
+        );
+    }
+
+    #[test]
+    fn test_chart_includes_jobs_from_local_includes() {
+        let dir = write_files(&[
+            (
+                ".gitlab-ci.yml",
+                "include: ci/test.yml\nstages: [build, test]\nbuild:\n  stage: build\n  script: [make]\n",
+            ),
+            ("ci/templates.yml", ".test:\n  stage: test\n"),
+            (
+                "ci/test.yml",
+                "include: ci/templates.yml\nunit:\n  extends: .test\n  script: [make test]\n",
+            ),
+        ]);
+        let yaml = resolve_local(dir.path(), ".gitlab-ci.yml").unwrap();
+        let chart = generate_mermaid_stages_diagram(YamlParser::new(yaml), ChartType::Jobs)
+            .unwrap()
+            .to_string();
+        assert!(chart.contains("build"));
+        assert!(chart.contains("unit"));
+    }
+}
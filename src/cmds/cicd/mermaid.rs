@@ -61,6 +61,12 @@ impl StageMap {
 pub struct Job {
     pub name: String,
     pub rules: Vec<HashMap<String, CicdEntity>>,
+    /// Jobs this job depends on. None if the job has no `needs` keyword, in
+    /// which case it waits for the previous stage.
+    pub needs: Option<Vec<Need>>,
+    /// Name of the job in the .gitlab-ci.yml if this job is one of the
+    /// instances of a `parallel: matrix` job.
+    pub parallel_of: Option<String>,
 }
 
 impl Job {
@@ -68,17 +74,76 @@ impl Job {
         Self {
             name: name.to_string(),
             rules,
+            needs: None,
+            parallel_of: None,
         }
     }
+
+    pub fn with_needs(mut self, needs: Option<Vec<Need>>) -> Self {
+        self.needs = needs;
+        self
+    }
+
+    fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.parallel_of.as_deref() == Some(name)
+    }
+}
+
+/// A `needs` dependency of a job.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Need {
+    /// A job in the same pipeline.
+    Job { name: String, optional: bool },
+    /// A job in another project or in the parent pipeline. `source` is the
+    /// project path or the pipeline id.
+    External { name: String, source: String },
+}
+
+impl Need {
+    fn from_entity(entity: &CicdEntity) -> Option<Need> {
+        if let Some(name) = entity.as_str() {
+            return Some(Need::Job {
+                name: job_node_name(name),
+                optional: false,
+            });
+        }
+        let name = entity["job"].as_str()?;
+        if let Some(source) = entity["project"]
+            .as_str()
+            .or_else(|| entity["pipeline"].as_str())
+        {
+            return Some(Need::External {
+                name: name.to_string(),
+                source: source.to_string(),
+            });
+        }
+        Some(Need::Job {
+            name: job_node_name(name),
+            optional: entity["optional"] == CicdEntity::Bool(true),
+        })
+    }
+}
+
+fn parse_needs(needs: &CicdEntity) -> Option<Vec<Need>> {
+    needs
+        .as_vec()
+        .map(|needs| needs.iter().filter_map(Need::from_entity).collect())
 }
 
-/// Defines a CicdEntity entity that can be a sequence, a mapping, a string or null.
+// Job names with white spaces are joined with hyphens to be valid mermaid nodes.
+fn job_node_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<&str>>().join("-")
+}
+
+/// Defines a CicdEntity entity that can be a sequence, a mapping, a string, a
+/// number, a boolean or null.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CicdEntity {
     Vec(Vec<CicdEntity>),
     Hash(HashMap<String, CicdEntity>),
     String(String),
     Integer(i64),
+    Bool(bool),
     Null,
 }
 
@@ -238,7 +303,7 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
                     })
                     .unwrap_or_default();
                 // if job_name has white spaces join them with a hyphen
-                let job_name = job_name.split_whitespace().collect::<Vec<&str>>().join("-");
+                let job_name = job_node_name(job_name);
                 // if rules is empty, check only rules
                 let only = job_details["only"].as_vec();
                 if only.is_some() {
@@ -263,7 +328,8 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
                 if job_name.starts_with('.') {
                     continue;
                 }
-                let job = Job::new(&job_name, rules.clone());
+                let needs = parse_needs(&job_details["needs"]);
+                let job = Job::new(&job_name, rules.clone()).with_needs(needs.clone());
                 let mut parallel_jobs = vec![];
                 // check if it's a parallel job
                 if let Some(parallel) = job_details["parallel"].as_hash() {
@@ -276,8 +342,11 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
                     }
                     for val_matrix in all_values {
                         for val in val_matrix {
-                            parallel_jobs
-                                .push(Job::new(&format!("{}-{}", job_name, val), rules.clone()))
+                            let mut parallel_job =
+                                Job::new(&format!("{}-{}", job_name, val), rules.clone())
+                                    .with_needs(needs.clone());
+                            parallel_job.parallel_of = Some(job_name.clone());
+                            parallel_jobs.push(parallel_job);
                         }
                     }
                 }
@@ -293,6 +362,133 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
     }
 }
 
+/// Parses a GitHub Actions workflow. Workflows have no stages, so jobs are
+/// grouped by their depth in the `needs` graph: jobs without `needs` go into
+/// the first stage and every other job goes into the stage right after the
+/// deepest job it needs.
+pub struct WorkflowParser<T> {
+    parser: T,
+}
+
+impl<T> WorkflowParser<T> {
+    pub fn new(parser: T) -> Self {
+        Self { parser }
+    }
+}
+
+impl<T: ToCicdEntity> WorkflowParser<T> {
+    /// Job ids with the ids of the jobs they need, sorted by job id.
+    fn jobs(&self) -> Vec<(String, Vec<String>)> {
+        let entity = self.parser.get(&Some(EntityName::Job));
+        let mut jobs: Vec<(String, Vec<String>)> = entity["jobs"]
+            .as_hash()
+            .map(|jobs| {
+                jobs.iter()
+                    .map(|(id, job)| {
+                        let needs = match &job["needs"] {
+                            CicdEntity::String(need) => vec![need.to_string()],
+                            CicdEntity::Vec(needs) => needs
+                                .iter()
+                                .filter_map(|need| need.as_str().map(|s| s.to_string()))
+                                .collect(),
+                            _ => vec![],
+                        };
+                        (id.to_string(), needs)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        jobs.sort();
+        jobs
+    }
+
+    /// Stage index of each job, in the same order as `jobs`.
+    fn job_levels(&self, jobs: &[(String, Vec<String>)]) -> Result<Vec<usize>> {
+        fn level(
+            idx: usize,
+            jobs: &[(String, Vec<String>)],
+            levels: &mut [Option<usize>],
+            path: &mut Vec<usize>,
+        ) -> Result<usize> {
+            if let Some(level) = levels[idx] {
+                return Ok(level);
+            }
+            if let Some(pos) = path.iter().position(|&i| i == idx) {
+                let mut cycle: Vec<&str> =
+                    path[pos..].iter().map(|&i| jobs[i].0.as_str()).collect();
+                cycle.push(jobs[idx].0.as_str());
+                return Err(GRError::MermaidParsingError(format!(
+                    "Cycle detected in needs: {}",
+                    cycle.join(" -> ")
+                ))
+                .into());
+            }
+            path.push(idx);
+            let mut job_level = 0;
+            for need in jobs[idx].1.iter() {
+                let need_idx = match jobs.iter().position(|(id, _)| id == need) {
+                    Some(need_idx) => need_idx,
+                    None => {
+                        return Err(GRError::MermaidParsingError(format!(
+                            "Job {} needs {}, which does not exist",
+                            jobs[idx].0, need
+                        ))
+                        .into())
+                    }
+                };
+                job_level = job_level.max(level(need_idx, jobs, levels, path)? + 1);
+            }
+            path.pop();
+            levels[idx] = Some(job_level);
+            Ok(job_level)
+        }
+        let mut levels = vec![None; jobs.len()];
+        for idx in 0..jobs.len() {
+            level(idx, jobs, &mut levels, &mut vec![])?;
+        }
+        Ok(levels.into_iter().map(|level| level.unwrap()).collect())
+    }
+}
+
+impl<T: ToCicdEntity> CicdParser for WorkflowParser<T> {
+    fn get_stages(&self) -> Result<StageMap> {
+        let jobs = self.jobs();
+        if jobs.is_empty() {
+            return Err(GRError::MermaidParsingError("No jobs found".to_string()).into());
+        }
+        let num_stages = self.job_levels(&jobs)?.into_iter().max().unwrap() + 1;
+        let mut stages = StageMap::new();
+        for i in 1..=num_stages {
+            let stage_name = format!("stage_{}", i);
+            stages.insert(stage_name.clone(), Stage::new(&stage_name));
+        }
+        Ok(stages)
+    }
+
+    fn get_jobs(&self, stages: &mut StageMap) {
+        let jobs = self.jobs();
+        let levels = match self.job_levels(&jobs) {
+            Ok(levels) => levels,
+            Err(_) => return,
+        };
+        for ((id, needs), level) in jobs.into_iter().zip(levels) {
+            let needs = needs
+                .into_iter()
+                .map(|name| Need::Job {
+                    name,
+                    optional: false,
+                })
+                .collect();
+            // Jobs without needs start right away, they do not wait for a
+            // previous stage.
+            let job = Job::new(&id, vec![]).with_needs(Some(needs));
+            if let Some(stage) = stages.get_mut(&format!("stage_{}", level + 1)) {
+                stage.jobs.push(job);
+            }
+        }
+    }
+}
+
 fn combine_matrix_values(matrix: &CicdEntity) -> Vec<String> {
     let map = matrix.as_hash().unwrap();
     let keys = map.keys().collect::<Vec<&String>>();
@@ -353,11 +549,12 @@ impl Display for Mermaid {
     }
 }
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum ChartType {
     StagesWithJobs,
     Jobs,
     Stages,
+    Dag,
 }
 
 /// Generate a Mermaid state diagram with each stage encapsulating all its jobs
@@ -373,7 +570,7 @@ pub fn generate_mermaid_stages_diagram(
             mermaid.push("stateDiagram-v2".to_string());
             mermaid.push("    direction LR".to_string());
         }
-        ChartType::Jobs | ChartType::Stages => {
+        ChartType::Jobs | ChartType::Stages | ChartType::Dag => {
             mermaid.push("graph LR".to_string());
         }
     }
@@ -382,6 +579,10 @@ pub fn generate_mermaid_stages_diagram(
 
     parser.get_jobs(&mut stages);
 
+    if chart_type == ChartType::Dag {
+        return generate_mermaid_dag(&stages, mermaid);
+    }
+
     for (i, stage) in stages.stage_names.iter().enumerate() {
         let stage_obj = stages.stages.get(stage).unwrap();
         let jobs = &stage_obj.jobs;
@@ -443,6 +644,7 @@ pub fn generate_mermaid_stages_diagram(
                                 jobs_first_stage_compatible = true;
                                 mermaid.push(format!("    {} --> {}", job.name, next_job.name));
                             }
+                            ChartType::Dag => unreachable!(),
                         }
                     }
                 }
@@ -456,6 +658,136 @@ pub fn generate_mermaid_stages_diagram(
     Ok(mermaid)
 }
 
+/// Generate a Mermaid graph with the job to job dependencies given by `needs`.
+/// Jobs without `needs` depend on all the jobs of the previous stage. Optional
+/// needs are drawn as dotted links and needs on jobs from other projects or
+/// from the parent pipeline are drawn as external nodes.
+fn generate_mermaid_dag(stages: &StageMap, mut mermaid: Mermaid) -> Result<Mermaid> {
+    let jobs: Vec<&Job> = stages
+        .stage_names
+        .iter()
+        .flat_map(|stage| stages.stages[stage].jobs.iter())
+        .collect();
+    // Dependencies as indexes in `jobs`, used to detect cycles.
+    let mut dependencies: Vec<Vec<usize>> = vec![vec![]; jobs.len()];
+    let mut linked = vec![false; jobs.len()];
+    let mut externals: Vec<(String, String)> = vec![];
+    let mut previous_stage_jobs: Vec<usize> = vec![];
+    let mut index = 0;
+    for stage in stages.stage_names.iter() {
+        let stage_jobs: Vec<usize> = (index..index + stages.stages[stage].jobs.len()).collect();
+        index += stage_jobs.len();
+        for &job_idx in stage_jobs.iter() {
+            let job = jobs[job_idx];
+            let needs = match &job.needs {
+                Some(needs) => needs,
+                None => {
+                    for &dep in previous_stage_jobs.iter() {
+                        mermaid.push(format!("    {} --> {}", jobs[dep].name, job.name));
+                        dependencies[job_idx].push(dep);
+                        linked[dep] = true;
+                        linked[job_idx] = true;
+                    }
+                    continue;
+                }
+            };
+            for need in needs {
+                match need {
+                    Need::Job { name, optional } => {
+                        let deps: Vec<usize> = (0..jobs.len())
+                            .filter(|&i| jobs[i].is_named(name))
+                            .collect();
+                        if deps.is_empty() {
+                            if *optional {
+                                continue;
+                            }
+                            return Err(GRError::MermaidParsingError(format!(
+                                "Job {} needs {}, which does not exist",
+                                job.name, name
+                            ))
+                            .into());
+                        }
+                        let link = if *optional { "-.->" } else { "-->" };
+                        for dep in deps {
+                            mermaid.push(format!("    {} {} {}", jobs[dep].name, link, job.name));
+                            dependencies[job_idx].push(dep);
+                            linked[dep] = true;
+                            linked[job_idx] = true;
+                        }
+                    }
+                    Need::External { name, source } => {
+                        let external = (source.to_string(), name.to_string());
+                        let pos = match externals.iter().position(|e| *e == external) {
+                            Some(pos) => pos,
+                            None => {
+                                externals.push(external);
+                                externals.len() - 1
+                            }
+                        };
+                        mermaid.push(format!(
+                            "    external{}([\"{}: {}\"]) -.-> {}",
+                            pos, source, name, job.name
+                        ));
+                        linked[job_idx] = true;
+                    }
+                }
+            }
+        }
+        if !stage_jobs.is_empty() {
+            previous_stage_jobs = stage_jobs;
+        }
+    }
+    if let Some(cycle) = find_cycle(&dependencies) {
+        let cycle: Vec<&str> = cycle.iter().map(|&i| jobs[i].name.as_str()).collect();
+        return Err(GRError::MermaidParsingError(format!(
+            "Cycle detected in needs: {}",
+            cycle.join(" -> ")
+        ))
+        .into());
+    }
+    for (job, linked) in jobs.iter().zip(linked) {
+        if !linked {
+            mermaid.push(format!("    {}", job.name));
+        }
+    }
+    Ok(mermaid)
+}
+
+/// Returns the nodes forming a cycle in the dependency graph, if any, with the
+/// first node repeated at the end.
+fn find_cycle(dependencies: &[Vec<usize>]) -> Option<Vec<usize>> {
+    // 0 not visited, 1 in the current path, 2 done
+    fn visit(node: usize, deps: &[Vec<usize>], state: &mut [u8], path: &mut Vec<usize>) -> bool {
+        state[node] = 1;
+        path.push(node);
+        for &dep in deps[node].iter() {
+            if state[dep] == 1 {
+                let start = path.iter().position(|&n| n == dep).unwrap();
+                path.drain(..start);
+                path.push(dep);
+                return true;
+            }
+            if state[dep] == 0 && visit(dep, deps, state, path) {
+                return true;
+            }
+        }
+        path.pop();
+        state[node] = 2;
+        false
+    }
+    let mut state = vec![0; dependencies.len()];
+    for node in 0..dependencies.len() {
+        let mut path = vec![];
+        if state[node] == 0 && visit(node, dependencies, &mut state, &mut path) {
+            // The path follows dependencies, reverse it so it reads in
+            // execution order.
+            path.reverse();
+            return Some(path);
+        }
+    }
+    None
+}
+
 fn rules_compatible(
     rules1: &[HashMap<String, CicdEntity>],
     rules2: &[HashMap<String, CicdEntity>],
@@ -1350,4 +1682,207 @@ mod tests {
 
         Ok(())
     }
+
+    fn dag_diagram(yaml: &str) -> Result<String> {
+        let parser = YamlParser::new(crate::cmds::cicd::yaml::load_yaml(yaml));
+        Ok(generate_mermaid_stages_diagram(parser, ChartType::Dag)?.to_string())
+    }
+
+    #[test]
+    fn test_dag_pipeline_with_needs() -> Result<()> {
+        let diagram = dag_diagram(
+            "
+stages: [build, test, deploy]
+compile:
+  stage: build
+lint:
+  stage: build
+  needs: []
+unit-test:
+  stage: test
+  needs: [compile]
+docs:
+  stage: test
+  needs:
+    - job: lint
+      optional: true
+    - job: missing
+      optional: true
+production:
+  stage: deploy
+",
+        )?;
+        assert!(diagram.starts_with("graph LR"));
+        assert!(diagram.contains("    compile --> unit-test"));
+        assert!(diagram.contains("    lint -.-> docs"));
+        assert!(!diagram.contains("lint --> unit-test"));
+        assert!(!diagram.contains("missing"));
+        // no needs, depends on the previous stage
+        assert!(diagram.contains("    unit-test --> production"));
+        assert!(diagram.contains("    docs --> production"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dag_cross_pipeline_needs_are_external_nodes() -> Result<()> {
+        let diagram = dag_diagram(
+            "
+stages: [test]
+test:
+  stage: test
+  needs:
+    - project: group/library
+      job: build
+      ref: main
+      artifacts: true
+    - pipeline: $PARENT_PIPELINE_ID
+      job: generate
+",
+        )?;
+        assert!(diagram.contains("    external0([\"group/library: build\"]) -.-> test"));
+        assert!(diagram.contains("    external1([\"$PARENT_PIPELINE_ID: generate\"]) -.-> test"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dag_needs_on_parallel_job_links_all_instances() -> Result<()> {
+        let diagram = dag_diagram(
+            "
+stages: [build, test]
+build:
+  stage: build
+  parallel:
+    matrix:
+      - ARCH: [x86, arm]
+test:
+  stage: test
+  needs: [build]
+",
+        )?;
+        assert!(diagram.contains("    build-x86 --> test"));
+        assert!(diagram.contains("    build-arm --> test"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dag_job_without_links_is_a_node() -> Result<()> {
+        let diagram = dag_diagram("stages: [build]\ncompile:\n  stage: build\n")?;
+        assert_eq!("graph LR\n    compile\n", diagram);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dag_missing_need_is_an_error() {
+        let err =
+            dag_diagram("stages: [test]\ntest:\n  stage: test\n  needs: [build]\n").unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::MermaidParsingError(msg)) => {
+                assert_eq!("Job test needs build, which does not exist", msg)
+            }
+            _ => panic!("Expected MermaidParsingError"),
+        }
+    }
+
+    #[test]
+    fn test_dag_cycle_is_an_error() {
+        let err = dag_diagram(
+            "stages: [test]\na:\n  stage: test\n  needs: [b]\nb:\n  stage: test\n  needs: [a]\n",
+        )
+        .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::MermaidParsingError(msg)) => {
+                assert!(
+                    msg == "Cycle detected in needs: a -> b -> a"
+                        || msg == "Cycle detected in needs: b -> a -> b"
+                )
+            }
+            _ => panic!("Expected MermaidParsingError"),
+        }
+    }
+
+    fn workflow_diagram(yaml: &str, chart_type: ChartType) -> Result<String> {
+        let parser = WorkflowParser::new(crate::cmds::cicd::yaml::load_yaml(yaml));
+        Ok(generate_mermaid_stages_diagram(parser, chart_type)?.to_string())
+    }
+
+    const WORKFLOW: &str = "
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+  lint:
+    runs-on: ubuntu-latest
+  test:
+    runs-on: ubuntu-latest
+    needs: build
+  release:
+    runs-on: ubuntu-latest
+    needs: [test, lint]
+";
+
+    #[test]
+    fn test_workflow_jobs_grouped_in_stages_by_needs() {
+        let parser = WorkflowParser::new(crate::cmds::cicd::yaml::load_yaml(WORKFLOW));
+        let mut stages = parser.get_stages().unwrap();
+        parser.get_jobs(&mut stages);
+        assert_eq!(vec!["stage_1", "stage_2", "stage_3"], stages.stage_names);
+        let names = |stage: &str| -> Vec<String> {
+            stages.stages[stage]
+                .jobs
+                .iter()
+                .map(|job| job.name.clone())
+                .collect()
+        };
+        assert_eq!(vec!["build", "lint"], names("stage_1"));
+        assert_eq!(vec!["test"], names("stage_2"));
+        assert_eq!(vec!["release"], names("stage_3"));
+    }
+
+    #[test]
+    fn test_workflow_dag_diagram() -> Result<()> {
+        let diagram = workflow_diagram(WORKFLOW, ChartType::Dag)?;
+        assert_eq!(
+            "graph LR\n    build --> test\n    test --> release\n    lint --> release\n",
+            diagram
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_workflow_stages_diagram() -> Result<()> {
+        let diagram = workflow_diagram(WORKFLOW, ChartType::Stages)?;
+        assert!(diagram.contains("stage_1 --> stage_2"));
+        assert!(diagram.contains("stage_2 --> stage_3"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_workflow_unknown_need_is_an_error() {
+        let err = workflow_diagram(
+            "on: push\njobs:\n  test:\n    needs: build\n",
+            ChartType::Dag,
+        )
+        .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::MermaidParsingError(msg)) => {
+                assert_eq!("Job test needs build, which does not exist", msg)
+            }
+            _ => panic!("Expected MermaidParsingError"),
+        }
+    }
+
+    #[test]
+    fn test_workflow_needs_cycle_is_an_error() {
+        let err = workflow_diagram(
+            "on: push\njobs:\n  a:\n    needs: b\n  b:\n    needs: a\n",
+            ChartType::Jobs,
+        )
+        .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::MermaidParsingError(msg)) => {
+                assert_eq!("Cycle detected in needs: a -> b -> a", msg)
+            }
+            _ => panic!("Expected MermaidParsingError"),
+        }
+    }
 }
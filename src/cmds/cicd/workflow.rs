@@ -0,0 +1,373 @@
+//! GitHub Actions workflows. GitHub does not offer an API to validate
+//! workflows, so linting is done locally and covers the structure of the
+//! workflow only: triggers, job ids, `runs-on` and `needs`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+    Yaml, YamlLoader,
+};
+
+use crate::{error, Result};
+
+pub const WORKFLOWS_DIR: &str = ".github/workflows";
+
+// https://docs.github.com/en/actions/writing-workflows/choosing-when-your-workflow-runs/events-that-trigger-workflows
+const EVENTS: &[&str] = &[
+    "branch_protection_rule",
+    "check_run",
+    "check_suite",
+    "create",
+    "delete",
+    "deployment",
+    "deployment_status",
+    "discussion",
+    "discussion_comment",
+    "fork",
+    "gollum",
+    "issue_comment",
+    "issues",
+    "label",
+    "merge_group",
+    "milestone",
+    "page_build",
+    "project",
+    "project_card",
+    "project_column",
+    "public",
+    "pull_request",
+    "pull_request_review",
+    "pull_request_review_comment",
+    "pull_request_target",
+    "push",
+    "registry_package",
+    "release",
+    "repository_dispatch",
+    "schedule",
+    "status",
+    "watch",
+    "workflow_call",
+    "workflow_dispatch",
+    "workflow_run",
+];
+
+/// Workflow files in the `.github/workflows` directory under `root`, sorted by
+/// path.
+pub fn workflow_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let dir = root.join(WORKFLOWS_DIR);
+    let entries = fs::read_dir(&dir)
+        .map_err(|err| error::gen(format!("Could not read {}: {}", dir.display(), err)))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if let Some("yml" | "yaml") = path.extension().and_then(|ext| ext.to_str()) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The workflow to use when none is given. Fails if there is more than one.
+pub fn default_workflow(root: &Path) -> Result<PathBuf> {
+    let mut files = workflow_files(root)?;
+    match files.len() {
+        0 => Err(error::gen(format!(
+            "No workflows found in {}",
+            WORKFLOWS_DIR
+        ))),
+        1 => Ok(files.remove(0)),
+        _ => {
+            let names: Vec<String> = files
+                .iter()
+                .map(|file| file.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            Err(error::gen(format!(
+                "Multiple workflows found, choose one of: {}",
+                names.join(", ")
+            )))
+        }
+    }
+}
+
+pub fn load_workflow(content: &str) -> Result<Yaml> {
+    YamlLoader::load_from_str(content)
+        .map_err(|err| error::gen(format!("Invalid yaml: {}", err)))?
+        .pop()
+        .ok_or_else(|| error::gen("Empty workflow"))
+}
+
+/// Structural lint of a workflow. Returns the list of errors found, empty if
+/// the workflow is valid.
+pub fn lint_workflow(content: &str) -> Vec<String> {
+    let duplicates = match duplicate_job_ids(content) {
+        Ok(duplicates) => duplicates,
+        Err(err) => return vec![err.to_string()],
+    };
+    let mut errors: Vec<String> = duplicates
+        .iter()
+        .map(|id| format!("Duplicate job id: {}", id))
+        .collect();
+    let workflow = match load_workflow(content) {
+        Ok(workflow) => workflow,
+        // Duplicated keys make the yaml loader fail, which is already reported.
+        Err(_) if !errors.is_empty() => return errors,
+        Err(err) => return vec![err.to_string()],
+    };
+    if workflow.as_hash().is_none() {
+        return vec!["Workflow must be a mapping".to_string()];
+    }
+    lint_triggers(&workflow["on"], &mut errors);
+    let jobs = match workflow["jobs"].as_hash() {
+        Some(jobs) if !jobs.is_empty() => jobs,
+        _ => {
+            errors.push("Missing jobs".to_string());
+            return errors;
+        }
+    };
+    for (id, job) in jobs {
+        let id = id.as_str().unwrap_or_default();
+        if job.as_hash().is_none() {
+            errors.push(format!("Job {} must be a mapping", id));
+            continue;
+        }
+        // Jobs calling a reusable workflow run on the runners of that workflow.
+        if job["runs-on"].is_badvalue() && job["uses"].is_badvalue() {
+            errors.push(format!("Job {} is missing runs-on", id));
+        }
+        let needs = match &job["needs"] {
+            Yaml::String(need) => vec![need.as_str()],
+            Yaml::Array(needs) => needs.iter().filter_map(|need| need.as_str()).collect(),
+            _ => vec![],
+        };
+        for need in needs {
+            if !jobs.contains_key(&Yaml::String(need.to_string())) {
+                errors.push(format!("Job {} needs {}, which does not exist", id, need));
+            }
+        }
+    }
+    errors
+}
+
+fn lint_triggers(on: &Yaml, errors: &mut Vec<String>) {
+    let events: Vec<&Yaml> = match on {
+        Yaml::BadValue => {
+            errors.push("Missing on triggers".to_string());
+            return;
+        }
+        Yaml::String(_) => vec![on],
+        Yaml::Array(events) => events.iter().collect(),
+        Yaml::Hash(events) => events.keys().collect(),
+        _ => {
+            errors.push("Invalid on triggers: expected an event, a list or a mapping".to_string());
+            return;
+        }
+    };
+    if events.is_empty() {
+        errors.push("Missing on triggers".to_string());
+    }
+    for event in events {
+        match event.as_str() {
+            Some(name) if EVENTS.contains(&name) => {}
+            Some(name) => errors.push(format!("Invalid on trigger: {}", name)),
+            None => errors.push(format!("Invalid on trigger: {:?}", event)),
+        }
+    }
+    if let Yaml::Hash(_) = on {
+        let schedule = &on["schedule"];
+        if !schedule.is_badvalue()
+            && !schedule
+                .as_vec()
+                .is_some_and(|crons| crons.iter().all(|cron| cron["cron"].as_str().is_some()))
+        {
+            errors.push("Invalid schedule trigger: expected a list of cron entries".to_string());
+        }
+    }
+}
+
+// The yaml loader fails on duplicated keys without telling which, so walk the
+// parser events and gather the ids repeated under the top level `jobs` key.
+#[derive(Default)]
+struct JobIdsReceiver {
+    // Open mappings (true) and sequences (false).
+    nodes: Vec<bool>,
+    // Whether the next scalar in each open mapping is a key.
+    expect_key: Vec<bool>,
+    top_level_key: Option<String>,
+    ids: Vec<String>,
+    duplicates: Vec<String>,
+}
+
+impl JobIdsReceiver {
+    fn in_jobs(&self) -> bool {
+        self.nodes == [true, true] && self.top_level_key.as_deref() == Some("jobs")
+    }
+
+    // A key or a value has been consumed in the current mapping.
+    fn consumed(&mut self) {
+        if let (Some(true), Some(expect_key)) = (self.nodes.last(), self.expect_key.last_mut()) {
+            *expect_key = !*expect_key;
+        }
+    }
+}
+
+impl MarkedEventReceiver for JobIdsReceiver {
+    fn on_event(&mut self, ev: Event, _mark: Marker) {
+        match ev {
+            Event::MappingStart(..) => {
+                self.nodes.push(true);
+                self.expect_key.push(true);
+            }
+            Event::SequenceStart(..) => self.nodes.push(false),
+            Event::MappingEnd => {
+                self.nodes.pop();
+                self.expect_key.pop();
+                self.consumed();
+            }
+            Event::SequenceEnd => {
+                self.nodes.pop();
+                self.consumed();
+            }
+            Event::Scalar(value, ..) => {
+                if self.expect_key.last() == Some(&true) {
+                    if self.nodes.len() == 1 {
+                        self.top_level_key = Some(value.clone());
+                    } else if self.in_jobs() {
+                        if self.ids.contains(&value) {
+                            self.duplicates.push(value.clone());
+                        }
+                        self.ids.push(value);
+                    }
+                }
+                self.consumed();
+            }
+            Event::Alias(_) => self.consumed(),
+            _ => {}
+        }
+    }
+}
+
+fn duplicate_job_ids(content: &str) -> Result<Vec<String>> {
+    let mut receiver = JobIdsReceiver::default();
+    Parser::new_from_str(content)
+        .load(&mut receiver, false)
+        .map_err(|err| error::gen(format!("Invalid yaml: {}", err)))?;
+    Ok(receiver.duplicates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_workflow() {
+        let workflow = "
+on:
+  push:
+    branches: [main]
+  pull_request:
+  schedule:
+    - cron: '0 0 * * *'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo build
+  test:
+    runs-on: ubuntu-latest
+    needs: build
+  deploy:
+    needs: [build, test]
+    uses: ./.github/workflows/deploy.yml
+";
+        assert!(lint_workflow(workflow).is_empty());
+    }
+
+    #[test]
+    fn test_trigger_forms() {
+        assert!(lint_workflow("on: push\njobs:\n  a:\n    runs-on: x\n").is_empty());
+        assert!(
+            lint_workflow("on: [push, workflow_dispatch]\njobs:\n  a:\n    runs-on: x\n")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_unknown_needs() {
+        let errors =
+            lint_workflow("on: push\njobs:\n  test:\n    runs-on: x\n    needs: [build]\n");
+        assert_eq!(vec!["Job test needs build, which does not exist"], errors);
+    }
+
+    #[test]
+    fn test_duplicate_job_ids() {
+        let errors = lint_workflow(
+            "on: push\njobs:\n  build:\n    runs-on: x\n    steps:\n      - name: build\n  build:\n    runs-on: y\n",
+        );
+        assert_eq!(vec!["Duplicate job id: build"], errors);
+    }
+
+    #[test]
+    fn test_same_key_in_different_jobs_is_not_a_duplicate() {
+        let errors = lint_workflow(
+            "on: push\njobs:\n  a:\n    runs-on: x\n    env: {build: 1}\n  b:\n    runs-on: x\n    env:\n      build: 1\n",
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_missing_runs_on() {
+        let errors = lint_workflow("on: push\njobs:\n  build:\n    steps: []\n");
+        assert_eq!(vec!["Job build is missing runs-on"], errors);
+    }
+
+    #[test]
+    fn test_invalid_triggers() {
+        let errors = lint_workflow("on: [push, commit]\njobs:\n  a:\n    runs-on: x\n");
+        assert_eq!(vec!["Invalid on trigger: commit"], errors);
+        let errors = lint_workflow("on: 3\njobs:\n  a:\n    runs-on: x\n");
+        assert_eq!(
+            vec!["Invalid on triggers: expected an event, a list or a mapping"],
+            errors
+        );
+        let errors = lint_workflow("jobs:\n  a:\n    runs-on: x\n");
+        assert_eq!(vec!["Missing on triggers"], errors);
+        let errors = lint_workflow("on:\n  schedule: '0 0 * * *'\njobs:\n  a:\n    runs-on: x\n");
+        assert_eq!(
+            vec!["Invalid schedule trigger: expected a list of cron entries"],
+            errors
+        );
+    }
+
+    #[test]
+    fn test_missing_jobs() {
+        assert_eq!(vec!["Missing jobs"], lint_workflow("on: push\n"));
+    }
+
+    #[test]
+    fn test_invalid_yaml() {
+        let errors = lint_workflow("on: [push\njobs:\n");
+        assert_eq!(1, errors.len());
+        assert!(errors[0].starts_with("Invalid yaml"));
+    }
+
+    #[test]
+    fn test_workflow_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let workflows = dir.path().join(WORKFLOWS_DIR);
+        fs::create_dir_all(&workflows).unwrap();
+        for file in ["release.yaml", "ci.yml", "README.md"] {
+            fs::write(workflows.join(file), "").unwrap();
+        }
+        let files = workflow_files(dir.path()).unwrap();
+        assert_eq!(
+            vec![workflows.join("ci.yml"), workflows.join("release.yaml")],
+            files
+        );
+    }
+}
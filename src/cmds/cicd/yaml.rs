@@ -34,6 +34,7 @@ impl ToCicdEntity for Yaml {
             }
             Yaml::String(ref s) => CicdEntity::String(s.to_string()),
             Yaml::Integer(ref i) => CicdEntity::Integer(*i),
+            Yaml::Boolean(ref b) => CicdEntity::Bool(*b),
             _ => CicdEntity::Null,
         }
     }
@@ -108,7 +109,7 @@ mod tests {
     fn test_yaml_boolean_to_cicd_entity() {
         let yaml = create_yaml("true");
         let result = yaml.get(&None);
-        assert!(matches!(result, CicdEntity::Null));
+        assert!(matches!(result, CicdEntity::Bool(true)));
     }
 
     #[test]
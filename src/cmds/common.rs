@@ -9,12 +9,16 @@ use std::io::Write;
 use std::sync::Arc;
 
 use crate::api_traits::{
-    Cicd, CicdJob, CicdRunner, CodeGist, CommentMergeRequest, Deploy, DeployAsset, ProjectMember,
-    RemoteProject, RemoteTag, TrendingProjectURL,
+    Cicd, CicdJob, CicdRunner, CodeGist, CommentIssue, CommentMergeRequest, Deploy, DeployAsset,
+    MergeRequestCommit, ProjectMember, RemoteIssue, RemoteProject, RemoteTag, TrendingProjectURL,
 };
 
-use super::cicd::{JobListBodyArgs, JobListCliArgs, RunnerListBodyArgs, RunnerListCliArgs};
+use super::cicd::{JobListBodyArgs, RunnerListBodyArgs, RunnerListCliArgs};
 use super::gist::{GistListBodyArgs, GistListCliArgs};
+use super::issue::{
+    CommentIssueListBodyArgs, CommentIssueListCliArgs, IssueListBodyArgs, IssueListCliArgs,
+};
+use super::merge_request::commit::{MergeRequestCommitListBodyArgs, MergeRequestCommitListCliArgs};
 use super::merge_request::{
     CommentMergeRequestListBodyArgs, CommentMergeRequestListCliArgs, MergeRequestListBodyArgs,
 };
@@ -155,9 +159,33 @@ query_pages!(
     CommentMergeRequestListBodyArgs
 );
 
+query_pages!(
+    num_merge_request_commit_pages,
+    MergeRequestCommit,
+    MergeRequestCommitListBodyArgs
+);
+query_num_resources!(
+    num_merge_request_commit_resources,
+    MergeRequestCommit,
+    MergeRequestCommitListBodyArgs
+);
+
 query_pages!(num_user_gists, CodeGist);
 query_num_resources!(num_user_gist_resources, CodeGist);
 
+query_pages!(num_issue_pages, RemoteIssue, IssueListBodyArgs);
+query_num_resources!(num_issue_resources, RemoteIssue, IssueListBodyArgs);
+query_pages!(
+    num_comment_issue_pages,
+    CommentIssue,
+    CommentIssueListBodyArgs
+);
+query_num_resources!(
+    num_comment_issue_resources,
+    CommentIssue,
+    CommentIssueListBodyArgs
+);
+
 macro_rules! list_resource {
     ($func_name:ident, $trait_name:ident, $body_args:ident, $cli_args:ident, $embeds_list_args: literal) => {
         pub fn $func_name<W: Write>(
@@ -202,6 +230,14 @@ macro_rules! list_remote_objs {
     }};
 }
 
+list_resource!(
+    list_merge_request_commits,
+    MergeRequestCommit,
+    MergeRequestCommitListBodyArgs,
+    MergeRequestCommitListCliArgs,
+    true
+);
+
 list_resource!(
     list_merge_requests,
     MergeRequest,
@@ -219,8 +255,6 @@ list_resource!(
     true
 );
 
-list_resource!(list_jobs, CicdJob, JobListBodyArgs, JobListCliArgs, true);
-
 list_resource!(list_releases, Deploy, ReleaseBodyArgs, ListRemoteCliArgs);
 list_resource!(
     list_release_assets,
@@ -270,6 +304,22 @@ list_resource!(
     true
 );
 
+list_resource!(
+    list_issues,
+    RemoteIssue,
+    IssueListBodyArgs,
+    IssueListCliArgs,
+    true
+);
+
+list_resource!(
+    list_issue_comments,
+    CommentIssue,
+    CommentIssueListBodyArgs,
+    CommentIssueListCliArgs,
+    true
+);
+
 list_resource!(list_trending, TrendingProjectURL, String, TrendingCliArgs);
 
 pub fn get_user(
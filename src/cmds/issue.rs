@@ -0,0 +1,601 @@
+use std::fmt::{self, Display, Formatter};
+use std::io::{BufRead, Cursor, Write};
+use std::sync::Arc;
+
+use crate::api_traits::{CommentIssue, RemoteIssue, Timestamp};
+use crate::cli::issue::IssueOptions;
+use crate::config::ConfigProperties;
+use crate::display::{Column, DisplayBody};
+use crate::remote::{self, CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
+use crate::{display, Result};
+
+use super::common;
+use super::merge_request::get_reader_file_cli;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IssueState {
+    Opened,
+    Closed,
+}
+
+impl Display for IssueState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueState::Opened => write!(f, "opened"),
+            IssueState::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, Default)]
+pub struct Issue {
+    pub id: i64,
+    #[builder(default)]
+    pub title: String,
+    #[builder(default)]
+    pub state: String,
+    #[builder(default)]
+    pub author: String,
+    #[builder(default)]
+    pub web_url: String,
+    #[builder(default)]
+    pub description: String,
+    #[builder(default)]
+    pub created_at: String,
+    #[builder(default)]
+    pub updated_at: String,
+}
+
+impl Issue {
+    pub fn builder() -> IssueBuilder {
+        IssueBuilder::default()
+    }
+}
+
+impl From<Issue> for DisplayBody {
+    fn from(issue: Issue) -> Self {
+        DisplayBody::new(vec![
+            Column::new("ID", issue.id.to_string()),
+            Column::new("Title", issue.title),
+            Column::new("State", issue.state),
+            Column::builder()
+                .name("Description".to_string())
+                .value(issue.description)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::new("Author", issue.author),
+            Column::new("URL", issue.web_url),
+            Column::new("Created at", issue.created_at),
+            Column::new("Updated at", issue.updated_at),
+        ])
+    }
+}
+
+impl Timestamp for Issue {
+    fn created_at(&self) -> String {
+        self.created_at.clone()
+    }
+}
+
+#[derive(Builder)]
+pub struct IssueBodyArgs {
+    pub title: String,
+    #[builder(default)]
+    pub description: String,
+}
+
+impl IssueBodyArgs {
+    pub fn builder() -> IssueBodyArgsBuilder {
+        IssueBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct IssueCliArgs {
+    pub title: String,
+    #[builder(default)]
+    pub description: Option<String>,
+    #[builder(default)]
+    pub description_from_file: Option<String>,
+}
+
+impl IssueCliArgs {
+    pub fn builder() -> IssueCliArgsBuilder {
+        IssueCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct IssueListBodyArgs {
+    pub state: IssueState,
+    pub list_args: Option<ListBodyArgs>,
+}
+
+impl IssueListBodyArgs {
+    pub fn builder() -> IssueListBodyArgsBuilder {
+        IssueListBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct IssueListCliArgs {
+    pub state: IssueState,
+    pub list_args: ListRemoteCliArgs,
+}
+
+impl IssueListCliArgs {
+    pub fn builder() -> IssueListCliArgsBuilder {
+        IssueListCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct IssueGetCliArgs {
+    pub id: i64,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl IssueGetCliArgs {
+    pub fn builder() -> IssueGetCliArgsBuilder {
+        IssueGetCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct CommentIssueCliArgs {
+    pub id: i64,
+    pub comment: Option<String>,
+    pub comment_from_file: Option<String>,
+}
+
+impl CommentIssueCliArgs {
+    pub fn builder() -> CommentIssueCliArgsBuilder {
+        CommentIssueCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct CommentIssueListCliArgs {
+    pub id: i64,
+    pub list_args: ListRemoteCliArgs,
+}
+
+impl CommentIssueListCliArgs {
+    pub fn builder() -> CommentIssueListCliArgsBuilder {
+        CommentIssueListCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct CommentIssueListBodyArgs {
+    pub id: i64,
+    pub list_args: Option<ListBodyArgs>,
+}
+
+impl CommentIssueListBodyArgs {
+    pub fn builder() -> CommentIssueListBodyArgsBuilder {
+        CommentIssueListBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct CommentIssueBodyArgs {
+    pub id: i64,
+    pub comment: String,
+}
+
+impl CommentIssueBodyArgs {
+    pub fn builder() -> CommentIssueBodyArgsBuilder {
+        CommentIssueBodyArgsBuilder::default()
+    }
+}
+
+pub fn execute(
+    options: IssueOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        IssueOptions::Create(cli_args) => {
+            let remote = remote::get_issue(domain, path, config, None, CacheType::None)?;
+            if let Some(description_file) = &cli_args.description_from_file {
+                let reader = get_reader_file_cli(description_file)?;
+                create(remote, cli_args, Some(reader), std::io::stdout())
+            } else {
+                create(remote, cli_args, None::<Cursor<&str>>, std::io::stdout())
+            }
+        }
+        IssueOptions::List(cli_args) => {
+            let remote = remote::get_issue(
+                domain,
+                path,
+                config,
+                Some(&cli_args.list_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
+            let body_args = IssueListBodyArgs::builder()
+                .state(cli_args.state)
+                .list_args(from_to_args)
+                .build()?;
+            if cli_args.list_args.num_pages {
+                return common::num_issue_pages(remote, body_args, std::io::stdout());
+            }
+            if cli_args.list_args.num_resources {
+                return common::num_issue_resources(remote, body_args, std::io::stdout());
+            }
+            list(remote, body_args, cli_args, std::io::stdout())
+        }
+        IssueOptions::Get(cli_args) => {
+            let remote = remote::get_issue(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            get_issue_details(remote, cli_args, std::io::stdout())
+        }
+        IssueOptions::Close { id } => {
+            let remote = remote::get_issue(domain, path, config, None, CacheType::None)?;
+            close(remote, id, std::io::stdout())
+        }
+        IssueOptions::CreateComment(cli_args) => {
+            let remote = remote::get_comment_issue(domain, path, config, None, CacheType::None)?;
+            if let Some(comment_file) = &cli_args.comment_from_file {
+                let reader = get_reader_file_cli(comment_file)?;
+                create_comment(remote, cli_args, Some(reader))
+            } else {
+                create_comment(remote, cli_args, None::<Cursor<&str>>)
+            }
+        }
+        IssueOptions::ListComment(cli_args) => {
+            let remote = remote::get_comment_issue(
+                domain,
+                path,
+                config,
+                Some(&cli_args.list_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
+            let body_args = CommentIssueListBodyArgs::builder()
+                .id(cli_args.id)
+                .list_args(from_to_args)
+                .build()?;
+            if cli_args.list_args.num_pages {
+                return common::num_comment_issue_pages(remote, body_args, std::io::stdout());
+            }
+            if cli_args.list_args.num_resources {
+                return common::num_comment_issue_resources(remote, body_args, std::io::stdout());
+            }
+            list_comments(remote, body_args, cli_args, std::io::stdout())
+        }
+    }
+}
+
+fn create<R: BufRead, W: Write>(
+    remote: Arc<dyn RemoteIssue>,
+    args: IssueCliArgs,
+    reader: Option<R>,
+    mut writer: W,
+) -> Result<()> {
+    let description = if let Some(description) = args.description {
+        description
+    } else if let Some(mut reader) = reader {
+        let mut description = String::new();
+        reader.read_to_string(&mut description)?;
+        description
+    } else {
+        String::new()
+    };
+    let issue = remote.create(
+        IssueBodyArgs::builder()
+            .title(args.title)
+            .description(description)
+            .build()?,
+    )?;
+    writer.write_all(format!("Issue created: {}\n", issue.web_url).as_bytes())?;
+    Ok(())
+}
+
+fn list<W: Write>(
+    remote: Arc<dyn RemoteIssue>,
+    body_args: IssueListBodyArgs,
+    cli_args: IssueListCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    common::list_issues(remote, body_args, cli_args, &mut writer)
+}
+
+fn get_issue_details<W: Write>(
+    remote: Arc<dyn RemoteIssue>,
+    args: IssueGetCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let response = remote.get(args.id)?;
+    display::print(&mut writer, vec![response], args.get_args)?;
+    Ok(())
+}
+
+fn close<W: Write>(remote: Arc<dyn RemoteIssue>, id: i64, mut writer: W) -> Result<()> {
+    let issue = remote.close(id)?;
+    writer.write_all(format!("Issue closed: {}\n", issue.web_url).as_bytes())?;
+    Ok(())
+}
+
+fn create_comment<R: BufRead>(
+    remote: Arc<dyn CommentIssue>,
+    args: CommentIssueCliArgs,
+    reader: Option<R>,
+) -> Result<()> {
+    let comment = if let Some(comment) = args.comment {
+        comment
+    } else {
+        let mut comment = String::new();
+        // The unwrap is Ok here. This is enforced at the CLI interface. The
+        // user is required to provide a file or a comment.
+        reader.unwrap().read_to_string(&mut comment)?;
+        comment
+    };
+    remote.create(
+        CommentIssueBodyArgs::builder()
+            .id(args.id)
+            .comment(comment)
+            .build()
+            .unwrap(),
+    )
+}
+
+fn list_comments<W: Write>(
+    remote: Arc<dyn CommentIssue>,
+    body_args: CommentIssueListBodyArgs,
+    cli_args: CommentIssueListCliArgs,
+    writer: W,
+) -> Result<()> {
+    common::list_issue_comments(remote, body_args, cli_args, writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{api_traits::NumberDeltaErr, cmds::merge_request::Comment};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct IssueRemoteMock {
+        issues: Vec<Issue>,
+        comments: Vec<Comment>,
+        create_args: Mutex<Option<(String, String)>>,
+        comment_argument: Mutex<String>,
+    }
+
+    impl IssueRemoteMock {
+        fn new(issues: Vec<Issue>) -> Self {
+            IssueRemoteMock {
+                issues,
+                ..Default::default()
+            }
+        }
+    }
+
+    impl RemoteIssue for IssueRemoteMock {
+        fn create(&self, args: IssueBodyArgs) -> Result<Issue> {
+            *self.create_args.lock().unwrap() = Some((args.title, args.description));
+            Ok(self.issues[0].clone())
+        }
+
+        fn list(&self, _args: IssueListBodyArgs) -> Result<Vec<Issue>> {
+            Ok(self.issues.clone())
+        }
+
+        fn get(&self, _id: i64) -> Result<Issue> {
+            Ok(self.issues[0].clone())
+        }
+
+        fn close(&self, _id: i64) -> Result<Issue> {
+            Ok(self.issues[0].clone())
+        }
+
+        fn num_pages(&self, _args: IssueListBodyArgs) -> Result<Option<u32>> {
+            Ok(Some(3))
+        }
+
+        fn num_resources(&self, _args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            todo!()
+        }
+    }
+
+    impl CommentIssue for IssueRemoteMock {
+        fn create(&self, args: CommentIssueBodyArgs) -> Result<()> {
+            *self.comment_argument.lock().unwrap() = args.comment;
+            Ok(())
+        }
+
+        fn list(&self, _args: CommentIssueListBodyArgs) -> Result<Vec<Comment>> {
+            Ok(self.comments.clone())
+        }
+
+        fn num_pages(&self, _args: CommentIssueListBodyArgs) -> Result<Option<u32>> {
+            todo!()
+        }
+
+        fn num_resources(&self, _args: CommentIssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            todo!()
+        }
+    }
+
+    fn gen_issue() -> Issue {
+        Issue::builder()
+            .id(1)
+            .title("Crash on startup".to_string())
+            .state("opened".to_string())
+            .author("jdoe".to_string())
+            .web_url("https://gitlab.com/owner/repo/-/issues/1".to_string())
+            .description("It crashes".to_string())
+            .created_at("2024-03-01T00:00:00Z".to_string())
+            .updated_at("2024-03-02T00:00:00Z".to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_list_issues() {
+        let remote = Arc::new(IssueRemoteMock::new(vec![gen_issue()]));
+        let body_args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let cli_args = IssueListCliArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list(remote, body_args, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Title|State|Author|URL|Created at|Updated at\n\
+             1|Crash on startup|opened|jdoe|https://gitlab.com/owner/repo/-/issues/1|2024-03-01T00:00:00Z|2024-03-02T00:00:00Z\n",
+            String::from_utf8(buf).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_list_issues_empty_warns_user() {
+        let remote = Arc::new(IssueRemoteMock::default());
+        let body_args = IssueListBodyArgs::builder()
+            .state(IssueState::Closed)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let cli_args = IssueListCliArgs::builder()
+            .state(IssueState::Closed)
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list(remote, body_args, cli_args, &mut buf).unwrap();
+        assert_eq!("No resources found.\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn test_num_issue_pages() {
+        let remote = Arc::new(IssueRemoteMock::default());
+        let body_args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        common::num_issue_pages(remote, body_args, &mut buf).unwrap();
+        assert_eq!("3\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn test_get_issue_details_with_optional_fields() {
+        let remote = Arc::new(IssueRemoteMock::new(vec![gen_issue()]));
+        let cli_args = IssueGetCliArgs::builder()
+            .id(1)
+            .get_args(
+                GetRemoteCliArgs::builder()
+                    .display_optional(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        get_issue_details(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Title|State|Description|Author|URL|Created at|Updated at\n\
+             1|Crash on startup|opened|It crashes|jdoe|https://gitlab.com/owner/repo/-/issues/1|2024-03-01T00:00:00Z|2024-03-02T00:00:00Z\n",
+            String::from_utf8(buf).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_create_issue_with_description_from_reader() {
+        let remote = Arc::new(IssueRemoteMock::new(vec![gen_issue()]));
+        let cli_args = IssueCliArgs::builder()
+            .title("Crash on startup".to_string())
+            .description_from_file(Some("description.txt".to_string()))
+            .build()
+            .unwrap();
+        let reader = Cursor::new("Steps to reproduce");
+        let mut buf = Vec::new();
+        create(remote.clone(), cli_args, Some(reader), &mut buf).unwrap();
+        assert_eq!(
+            Some((
+                "Crash on startup".to_string(),
+                "Steps to reproduce".to_string()
+            )),
+            *remote.create_args.lock().unwrap()
+        );
+        assert_eq!(
+            "Issue created: https://gitlab.com/owner/repo/-/issues/1\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_close_issue() {
+        let remote = Arc::new(IssueRemoteMock::new(vec![gen_issue()]));
+        let mut buf = Vec::new();
+        close(remote, 1, &mut buf).unwrap();
+        assert_eq!(
+            "Issue closed: https://gitlab.com/owner/repo/-/issues/1\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_create_comment_on_issue() {
+        let remote = Arc::new(IssueRemoteMock::default());
+        let cli_args = CommentIssueCliArgs::builder()
+            .id(1)
+            .comment(Some("Cannot reproduce".to_string()))
+            .comment_from_file(None)
+            .build()
+            .unwrap();
+        create_comment(remote.clone(), cli_args, None::<Cursor<&str>>).unwrap();
+        assert_eq!(
+            "Cannot reproduce",
+            remote.comment_argument.lock().unwrap().as_str()
+        );
+    }
+
+    #[test]
+    fn test_list_issue_comments() {
+        let comment = Comment::builder()
+            .id(1)
+            .body("Cannot reproduce".to_string())
+            .author("user1".to_string())
+            .created_at("2024-03-01".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(IssueRemoteMock {
+            comments: vec![comment],
+            ..Default::default()
+        });
+        let body_args = CommentIssueListBodyArgs::builder()
+            .id(1)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let cli_args = CommentIssueListCliArgs::builder()
+            .id(1)
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_comments(remote, body_args, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Body|Author|Created at\n1|Cannot reproduce|user1|2024-03-01\n",
+            String::from_utf8(buf).unwrap(),
+        );
+    }
+}
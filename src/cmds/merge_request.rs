@@ -1,4 +1,6 @@
-use crate::api_traits::{CommentMergeRequest, MergeRequest, RemoteProject, Timestamp};
+use crate::api_traits::{
+    CommentMergeRequest, MergeRequest, MergeRequestThread, RemoteProject, Timestamp, UserInfo,
+};
 use crate::cli::merge_request::MergeRequestOptions;
 use crate::config::ConfigProperties;
 use crate::display::{Column, DisplayBody};
@@ -7,16 +9,29 @@ use crate::git::Repo;
 use crate::io::{CmdInfo, ShellResponse, TaskRunner};
 use crate::remote::{CacheCliArgs, CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
 use crate::shell::BlockingCommand;
-use crate::{dialog, display, exec, git, remote, Cmd, Result};
+use crate::{dialog, display, exec, git, list_remote_objs, remote, Cmd, Result};
+use serde::Deserialize;
 use std::fmt::{self, Display, Formatter};
 use std::{
-    fs::File,
-    io::{BufRead, BufReader, Cursor, Write},
+    fs::{self, File},
+    io::{BufRead, BufReader, Cursor, Read, Write},
+    path::Path,
     sync::Arc,
 };
 
+pub mod commit;
+pub mod rebase;
+pub mod review;
+pub mod reviewer;
+pub mod stack;
+pub mod status;
+pub mod template;
+pub mod thread;
+
 use super::common::{self, get_user};
-use super::project::{Member, Project};
+use super::project::{Member, MrMemberType, Project};
+use super::user::UserCliArgs;
+use reviewer::ReviewerPolicy;
 
 /// GPT_PROMPT is a template for the GPT prompt to generate a merge request
 /// description given a list of commit messages.
@@ -51,9 +66,12 @@ pub struct MergeRequestResponse {
     pub author: String,
     pub updated_at: String,
     pub source_branch: String,
+    pub target_branch: String,
     pub sha: String,
     pub created_at: String,
     pub title: String,
+    // One of opened, closed or merged.
+    pub state: String,
     // For Github to filter pull requests from issues.
     pub pull_request: String,
     // Optional fields to display for get and list operations
@@ -61,6 +79,10 @@ pub struct MergeRequestResponse {
     pub merged_at: String,
     pub pipeline_id: Option<i64>,
     pub pipeline_url: Option<String>,
+    pub labels: Vec<String>,
+    pub milestone: String,
+    // Only retrieved on request, as it takes one API call per merge request.
+    pub unresolved_threads: Option<usize>,
 }
 
 impl MergeRequestResponse {
@@ -71,41 +93,109 @@ impl MergeRequestResponse {
 
 impl From<MergeRequestResponse> for DisplayBody {
     fn from(mr: MergeRequestResponse) -> DisplayBody {
+        let mut columns = vec![
+            Column::new("ID", mr.id.to_string()),
+            Column::new("Title", mr.title),
+            Column::new("Source Branch", mr.source_branch),
+            Column::builder()
+                .name("SHA".to_string())
+                .value(mr.sha)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::builder()
+                .name("Description".to_string())
+                .value(mr.description)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::new("Author", mr.author),
+            Column::new("URL", mr.web_url),
+            Column::new("Updated at", mr.updated_at),
+            Column::builder()
+                .name("Merged at".to_string())
+                .value(mr.merged_at)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::builder()
+                .name("Pipeline ID".to_string())
+                .value(mr.pipeline_id.map_or("".to_string(), |id| id.to_string()))
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::builder()
+                .name("Pipeline URL".to_string())
+                .value(mr.pipeline_url.unwrap_or("".to_string()))
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::builder()
+                .name("Labels".to_string())
+                .value(mr.labels.join(","))
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::builder()
+                .name("Milestone".to_string())
+                .value(mr.milestone)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::builder()
+                .name("State".to_string())
+                .value(mr.state)
+                .optional(true)
+                .build()
+                .unwrap(),
+        ];
+        if let Some(unresolved_threads) = mr.unresolved_threads {
+            columns.push(Column::new(
+                "Unresolved threads",
+                unresolved_threads.to_string(),
+            ));
+        }
+        DisplayBody { columns }
+    }
+}
+
+impl Timestamp for MergeRequestResponse {
+    fn created_at(&self) -> String {
+        self.created_at.clone()
+    }
+}
+
+/// A file changed by a merge request.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct MergeRequestDiff {
+    pub old_path: String,
+    pub new_path: String,
+    /// One of added, modified, removed or renamed.
+    pub status: String,
+    pub additions: usize,
+    pub deletions: usize,
+    /// Unified diff hunks. Empty for binary files.
+    pub diff: String,
+}
+
+impl MergeRequestDiff {
+    pub fn builder() -> MergeRequestDiffBuilder {
+        MergeRequestDiffBuilder::default()
+    }
+}
+
+impl From<MergeRequestDiff> for DisplayBody {
+    fn from(diff: MergeRequestDiff) -> DisplayBody {
         DisplayBody {
             columns: vec![
-                Column::new("ID", mr.id.to_string()),
-                Column::new("Title", mr.title),
-                Column::new("Source Branch", mr.source_branch),
-                Column::builder()
-                    .name("SHA".to_string())
-                    .value(mr.sha)
-                    .optional(true)
-                    .build()
-                    .unwrap(),
-                Column::builder()
-                    .name("Description".to_string())
-                    .value(mr.description)
-                    .optional(true)
-                    .build()
-                    .unwrap(),
-                Column::new("Author", mr.author),
-                Column::new("URL", mr.web_url),
-                Column::new("Updated at", mr.updated_at),
+                Column::new("Path", diff.new_path),
+                Column::new("Status", diff.status),
+                Column::new("Additions", diff.additions.to_string()),
+                Column::new("Deletions", diff.deletions.to_string()),
                 Column::builder()
-                    .name("Merged at".to_string())
-                    .value(mr.merged_at)
-                    .optional(true)
-                    .build()
-                    .unwrap(),
-                Column::builder()
-                    .name("Pipeline ID".to_string())
-                    .value(mr.pipeline_id.map_or("".to_string(), |id| id.to_string()))
-                    .optional(true)
-                    .build()
-                    .unwrap(),
-                Column::builder()
-                    .name("Pipeline URL".to_string())
-                    .value(mr.pipeline_url.unwrap_or("".to_string()))
+                    .name("Old path".to_string())
+                    .value(diff.old_path)
                     .optional(true)
                     .build()
                     .unwrap(),
@@ -114,10 +204,21 @@ impl From<MergeRequestResponse> for DisplayBody {
     }
 }
 
-impl Timestamp for MergeRequestResponse {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
-    }
+/// Number of added and removed lines in unified diff hunks. File headers are
+/// only expected before the first hunk, afterwards a line starting with `+++`
+/// or `---` is a changed line, ex. a removed markdown rule.
+pub fn count_diff_lines(diff: &str) -> (usize, usize) {
+    diff.lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .fold((0, 0), |(additions, deletions), line| {
+            if line.starts_with('+') {
+                (additions + 1, deletions)
+            } else if line.starts_with('-') {
+                (additions, deletions + 1)
+            } else {
+                (additions, deletions)
+            }
+        })
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -125,6 +226,7 @@ pub enum MergeRequestState {
     Opened,
     Closed,
     Merged,
+    All,
 }
 
 impl TryFrom<&str> for MergeRequestState {
@@ -135,6 +237,7 @@ impl TryFrom<&str> for MergeRequestState {
             "opened" => Ok(MergeRequestState::Opened),
             "closed" => Ok(MergeRequestState::Closed),
             "merged" => Ok(MergeRequestState::Merged),
+            "all" => Ok(MergeRequestState::All),
             _ => Err(format!("Invalid merge request state: {}", s)),
         }
     }
@@ -146,6 +249,7 @@ impl Display for MergeRequestState {
             MergeRequestState::Opened => write!(f, "opened"),
             MergeRequestState::Closed => write!(f, "closed"),
             MergeRequestState::Merged => write!(f, "merged"),
+            MergeRequestState::All => write!(f, "all"),
         }
     }
 }
@@ -165,13 +269,17 @@ pub struct MergeRequestBodyArgs {
     #[builder(default)]
     pub assignee: Member,
     #[builder(default)]
-    pub reviewer: Member,
+    pub reviewers: Vec<Member>,
     #[builder(default = "String::from(\"true\")")]
     pub remove_source_branch: String,
     #[builder(default)]
     pub draft: bool,
     #[builder(default)]
     pub amend: bool,
+    #[builder(default)]
+    pub labels: Vec<String>,
+    #[builder(default)]
+    pub milestone: Option<String>,
 }
 
 impl MergeRequestBodyArgs {
@@ -180,6 +288,88 @@ impl MergeRequestBodyArgs {
     }
 }
 
+/// Partial update of an existing merge request. Only the fields that are set
+/// are sent to the remote.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct MergeRequestUpdateBodyArgs {
+    pub id: i64,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub target_branch: Option<String>,
+    pub assignee: Option<Member>,
+    pub reviewers: Option<Vec<Member>>,
+    /// Some(true) converts to draft, Some(false) marks it as ready for review.
+    pub draft: Option<bool>,
+    /// Replaces the labels of the merge request.
+    pub labels: Option<Vec<String>>,
+    /// Milestone title.
+    pub milestone: Option<String>,
+}
+
+impl MergeRequestUpdateBodyArgs {
+    pub fn builder() -> MergeRequestUpdateBodyArgsBuilder {
+        MergeRequestUpdateBodyArgsBuilder::default()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.target_branch.is_none()
+            && self.assignee.is_none()
+            && self.reviewers.is_none()
+            && self.draft.is_none()
+            && self.labels.is_none()
+            && self.milestone.is_none()
+    }
+}
+
+/// How the changes of a merge request are integrated in the target branch.
+/// Gitlab sets the merge method at the project level, so only squashing can be
+/// requested per merge request.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeMethod {
+    Merge,
+    Rebase,
+    Squash,
+}
+
+impl Display for MergeMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeMethod::Merge => write!(f, "merge"),
+            MergeMethod::Rebase => write!(f, "rebase"),
+            MergeMethod::Squash => write!(f, "squash"),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct MergeRequestMergeBodyArgs {
+    pub id: i64,
+    /// None leaves the merge method to the project settings.
+    pub merge_method: Option<MergeMethod>,
+    /// Commit message of the squashed commit. The first line is the subject.
+    pub squash_message: Option<String>,
+    pub delete_branch: bool,
+    /// Only merge if the head of the source branch matches this SHA.
+    pub sha: Option<String>,
+    /// Merge once the pipeline succeeds instead of right away.
+    pub auto_merge: bool,
+}
+
+impl MergeRequestMergeBodyArgs {
+    pub fn builder() -> MergeRequestMergeBodyArgsBuilder {
+        MergeRequestMergeBodyArgsBuilder::default()
+    }
+
+    pub fn squash(&self) -> bool {
+        self.merge_method == Some(MergeMethod::Squash)
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct MergeRequestListBodyArgs {
     pub state: MergeRequestState,
@@ -190,6 +380,11 @@ pub struct MergeRequestListBodyArgs {
     pub author: Option<Member>,
     #[builder(default)]
     pub reviewer: Option<Member>,
+    #[builder(default)]
+    pub labels: Vec<String>,
+    /// Merge requests opened from the given branch.
+    #[builder(default)]
+    pub source_branch: Option<String>,
 }
 
 impl MergeRequestListBodyArgs {
@@ -198,6 +393,25 @@ impl MergeRequestListBodyArgs {
     }
 }
 
+/// Merge options given in the cli. Options not set fall back to the merge
+/// requests configuration section.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct MergeRequestMergeCliArgs {
+    pub id: i64,
+    pub merge_method: Option<MergeMethod>,
+    pub squash_message: Option<String>,
+    pub delete_branch: Option<bool>,
+    pub sha: Option<String>,
+    pub auto_merge: bool,
+}
+
+impl MergeRequestMergeCliArgs {
+    pub fn builder() -> MergeRequestMergeCliArgsBuilder {
+        MergeRequestMergeCliArgsBuilder::default()
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct MergeRequestCliArgs {
     pub title: Option<String>,
@@ -209,7 +423,7 @@ pub struct MergeRequestCliArgs {
     #[builder(default)]
     pub assignee: Option<String>,
     #[builder(default)]
-    pub reviewer: Option<String>,
+    pub reviewers: Vec<String>,
     #[builder(default)]
     pub rand_reviewer: bool,
     pub target_branch: Option<String>,
@@ -234,6 +448,13 @@ pub struct MergeRequestCliArgs {
     pub patch: bool,
     #[builder(default)]
     pub gpt_prompt: bool,
+    #[builder(default)]
+    pub labels: Vec<String>,
+    #[builder(default)]
+    pub milestone: Option<String>,
+    /// Name of the merge request template to fill in the description with.
+    #[builder(default)]
+    pub template: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -270,6 +491,11 @@ pub struct MergeRequestListCliArgs {
     pub author: Option<MergeRequestUser>,
     #[builder(default)]
     pub reviewer: Option<MergeRequestUser>,
+    #[builder(default)]
+    pub labels: Vec<String>,
+    /// Retrieve the number of unresolved threads of each merge request.
+    #[builder(default)]
+    pub unresolved_threads: bool,
 }
 
 impl MergeRequestListCliArgs {
@@ -280,6 +506,8 @@ impl MergeRequestListCliArgs {
             assignee: None,
             author: None,
             reviewer: None,
+            labels: vec![],
+            unresolved_threads: false,
         }
     }
     pub fn builder() -> MergeRequestListCliArgsBuilder {
@@ -287,6 +515,24 @@ impl MergeRequestListCliArgs {
     }
 }
 
+#[derive(Builder)]
+pub struct MergeRequestCheckoutCliArgs {
+    pub id: i64,
+    /// Directory of a new git worktree to check out the merge request into.
+    #[builder(default)]
+    pub worktree: Option<String>,
+    /// Check out the head of the merge request without creating a local
+    /// branch.
+    #[builder(default)]
+    pub detach: bool,
+}
+
+impl MergeRequestCheckoutCliArgs {
+    pub fn builder() -> MergeRequestCheckoutCliArgsBuilder {
+        MergeRequestCheckoutCliArgsBuilder::default()
+    }
+}
+
 #[derive(Builder)]
 pub struct MergeRequestGetCliArgs {
     pub id: i64,
@@ -299,6 +545,53 @@ impl MergeRequestGetCliArgs {
     }
 }
 
+#[derive(Builder)]
+pub struct MergeRequestDiffCliArgs {
+    pub id: i64,
+    #[builder(default)]
+    pub stat: bool,
+    #[builder(default)]
+    pub name_only: bool,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl MergeRequestDiffCliArgs {
+    pub fn builder() -> MergeRequestDiffCliArgsBuilder {
+        MergeRequestDiffCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct MergeRequestUpdateCliArgs {
+    pub id: i64,
+    #[builder(default)]
+    pub title: Option<String>,
+    #[builder(default)]
+    pub description: Option<String>,
+    #[builder(default)]
+    pub description_from_file: Option<String>,
+    #[builder(default)]
+    pub target_branch: Option<String>,
+    #[builder(default)]
+    pub assignee: Option<String>,
+    #[builder(default)]
+    pub reviewers: Vec<String>,
+    #[builder(default)]
+    pub draft: Option<bool>,
+    #[builder(default)]
+    pub labels: Vec<String>,
+    #[builder(default)]
+    pub milestone: Option<String>,
+    #[builder(default)]
+    pub interactive: bool,
+}
+
+impl MergeRequestUpdateCliArgs {
+    pub fn builder() -> MergeRequestUpdateCliArgsBuilder {
+        MergeRequestUpdateCliArgsBuilder::default()
+    }
+}
+
 #[derive(Builder)]
 pub struct CommentMergeRequestCliArgs {
     pub id: i64,
@@ -348,7 +641,47 @@ impl CommentMergeRequestBodyArgs {
     }
 }
 
-#[derive(Builder, Clone)]
+#[derive(Builder)]
+pub struct CommentMergeRequestEditCliArgs {
+    pub id: i64,
+    pub comment_id: i64,
+    pub comment: Option<String>,
+    pub comment_from_file: Option<String>,
+}
+
+impl CommentMergeRequestEditCliArgs {
+    pub fn builder() -> CommentMergeRequestEditCliArgsBuilder {
+        CommentMergeRequestEditCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct CommentMergeRequestUpdateBodyArgs {
+    pub id: i64,
+    pub comment_id: i64,
+    pub comment: String,
+}
+
+impl CommentMergeRequestUpdateBodyArgs {
+    pub fn builder() -> CommentMergeRequestUpdateBodyArgsBuilder {
+        CommentMergeRequestUpdateBodyArgsBuilder::default()
+    }
+}
+
+/// Emoji reactions supported by both Github and Gitlab.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reaction {
+    ThumbsUp,
+    ThumbsDown,
+    Laugh,
+    Confused,
+    Heart,
+    Hooray,
+    Rocket,
+    Eyes,
+}
+
+#[derive(Builder, Clone, Debug, Default)]
 pub struct Comment {
     pub id: i64,
     pub body: String,
@@ -395,8 +728,8 @@ pub fn execute(
                 CacheType::File,
             )?;
             let project_remote = remote::get_project(
-                domain,
-                path,
+                domain.clone(),
+                path.clone(),
                 config.clone(),
                 Some(&cli_args.cache_args),
                 CacheType::File,
@@ -436,22 +769,59 @@ pub fn execute(
             if cli_args.patch {
                 return patch(mr_body, &cli_args);
             }
-            open(mr_remote, config, mr_body, &cli_args)
+            let reviewer_remote = mr_remote.clone();
+            let reviewer_config = config.clone();
+            open(mr_remote, config, mr_body, &cli_args, |target_branch| {
+                select_reviewers(
+                    reviewer_remote,
+                    reviewer_config,
+                    &cli_args,
+                    target_branch,
+                    domain,
+                    path,
+                )
+            })
         }
         MergeRequestOptions::List(cli_args) => list_merge_requests(domain, path, config, cli_args),
-        MergeRequestOptions::Merge { id } => {
+        MergeRequestOptions::Merge(cli_args) => {
+            let body_args = merge_body_args(&cli_args, &config);
             let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
-            merge(remote, id)
+            merge(remote, body_args, std::io::stdout())
         }
-        MergeRequestOptions::Checkout { id } => {
+        MergeRequestOptions::Checkout(cli_args) => {
             // TODO: It should propagate the cache cli args.
             let remote = remote::get_mr(domain, path, config, None, CacheType::File)?;
-            checkout(remote, id)
+            checkout(remote, &BlockingCommand, cli_args)
         }
         MergeRequestOptions::Close { id } => {
             let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
             close(remote, id)
         }
+        MergeRequestOptions::Reopen { id } => {
+            let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
+            reopen(remote, id, std::io::stdout())
+        }
+        MergeRequestOptions::EditComment(cli_args) => {
+            let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
+            if let Some(comment_file) = &cli_args.comment_from_file {
+                let reader = get_reader_file_cli(comment_file)?;
+                edit_comment(remote, cli_args, Some(reader))
+            } else {
+                edit_comment(remote, cli_args, None::<Cursor<&str>>)
+            }
+        }
+        MergeRequestOptions::DeleteComment { id, comment_id } => {
+            let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
+            remote.delete(id, comment_id)
+        }
+        MergeRequestOptions::ReactComment {
+            id,
+            comment_id,
+            reaction,
+        } => {
+            let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
+            remote.react(id, comment_id, reaction)
+        }
         MergeRequestOptions::CreateComment(cli_args) => {
             let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
             if let Some(comment_file) = &cli_args.comment_from_file {
@@ -461,6 +831,131 @@ pub fn execute(
                 create_comment(remote, cli_args, None::<Cursor<&str>>)
             }
         }
+        MergeRequestOptions::Review(cli_args) => {
+            let remote = remote::get_review_mr(domain, path, config, None, CacheType::None)?;
+            let comments = match &cli_args.comments_from_file {
+                Some(comments_file) => {
+                    review::parse_review_comments(get_reader_file_cli(comments_file)?)?
+                }
+                None => vec![],
+            };
+            review::review(remote, cli_args, comments, std::io::stdout())
+        }
+        MergeRequestOptions::ListThread(cli_args) => {
+            let remote = remote::get_thread_mr(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            thread::list(remote, cli_args, std::io::stdout())
+        }
+        MergeRequestOptions::ReplyThread(cli_args) => {
+            let remote = remote::get_thread_mr(domain, path, config, None, CacheType::None)?;
+            if let Some(comment_file) = &cli_args.comment_from_file {
+                let reader = get_reader_file_cli(comment_file)?;
+                thread::reply(remote, cli_args, Some(reader))
+            } else {
+                thread::reply(remote, cli_args, None::<Cursor<&str>>)
+            }
+        }
+        MergeRequestOptions::ResolveThread {
+            id,
+            thread_id,
+            resolved,
+        } => {
+            let remote = remote::get_thread_mr(domain, path, config, None, CacheType::None)?;
+            thread::resolve(remote, id, thread_id, resolved, std::io::stdout())
+        }
+        MergeRequestOptions::Stack(cli_args) => {
+            let remote = remote::get_mr(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            let base = match &cli_args.base {
+                Some(base) => base.clone(),
+                None => {
+                    let project_remote =
+                        remote::get_project(domain, path, config, None, CacheType::None)?;
+                    match project_remote.get_project_data(None, None)? {
+                        CmdInfo::Project(project) => project.default_branch().to_string(),
+                        _ => {
+                            return Err(GRError::ApplicationError(
+                                "Could not get the default branch of the project".to_string(),
+                            )
+                            .into())
+                        }
+                    }
+                }
+            };
+            stack::stack(
+                remote,
+                Arc::new(BlockingCommand),
+                &base,
+                cli_args,
+                std::io::stdout(),
+            )
+        }
+        MergeRequestOptions::Rebase(cli_args) => {
+            let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
+            rebase::rebase(
+                remote,
+                Arc::new(BlockingCommand),
+                cli_args,
+                std::io::stdout(),
+            )
+        }
+        MergeRequestOptions::Status(cli_args) => {
+            let remote = remote::get_readiness_mr(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            let thread_remote = remote::get_thread_mr(domain, path, config, None, CacheType::None)?;
+            status::status(
+                remote,
+                thread_remote,
+                cli_args.id,
+                cli_args.get_args,
+                std::io::stdout(),
+            )
+        }
+        MergeRequestOptions::ListCommits(cli_args) => {
+            let remote = remote::get_commit_mr(
+                domain,
+                path,
+                config,
+                Some(&cli_args.list_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
+            let body_args = commit::MergeRequestCommitListBodyArgs::builder()
+                .id(cli_args.id)
+                .list_args(from_to_args)
+                .ci_status(cli_args.list_args.get_args.display_optional)
+                .build()?;
+            if cli_args.list_args.num_pages {
+                return common::num_merge_request_commit_pages(
+                    remote,
+                    body_args,
+                    std::io::stdout(),
+                );
+            }
+            if cli_args.list_args.num_resources {
+                return common::num_merge_request_commit_resources(
+                    remote,
+                    body_args,
+                    std::io::stdout(),
+                );
+            }
+            common::list_merge_request_commits(remote, body_args, cli_args, std::io::stdout())
+        }
         MergeRequestOptions::ListComment(cli_args) => {
             let remote = remote::get_comment_mr(
                 domain,
@@ -500,13 +995,247 @@ pub fn execute(
             )?;
             get_merge_request_details(remote, cli_args, std::io::stdout())
         }
+        MergeRequestOptions::Diff(cli_args) => {
+            let remote = remote::get_mr(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            diff(remote, cli_args, std::io::stdout())
+        }
         MergeRequestOptions::Approve { id } => {
             let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
             approve(remote, id, std::io::stdout())
         }
+        MergeRequestOptions::Update(cli_args) => {
+            let remote = remote::get_mr(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            let description = match &cli_args.description_from_file {
+                Some(description_file) => {
+                    let mut reader = get_reader_file_cli(description_file)?;
+                    let mut description = String::new();
+                    reader.read_to_string(&mut description)?;
+                    Some(description)
+                }
+                None => cli_args.description.clone(),
+            };
+            let mut usernames = cli_args.reviewers.clone();
+            usernames.extend(cli_args.assignee.clone());
+            let members = if usernames.is_empty() {
+                vec![]
+            } else {
+                let user_remote =
+                    remote::get_user(domain, path, config.clone(), None, CacheType::File)?;
+                resolve_members(user_remote, &config, &usernames)?
+            };
+            let mut body_args = update_body_args(&cli_args, description, &members);
+            if cli_args.interactive {
+                let current = remote.get(cli_args.id)?;
+                let (title, description) = dialog::prompt_user_title_description(
+                    body_args.title.as_deref().unwrap_or(&current.title),
+                    body_args
+                        .description
+                        .as_deref()
+                        .unwrap_or(&current.description),
+                );
+                body_args.title = Some(title);
+                body_args.description = Some(description);
+            }
+            update(remote, body_args, std::io::stdout())
+        }
     }
 }
 
+/// Reviewers of a new merge request. Reviewers given in the cli take
+/// precedence, otherwise they are picked by the configured reviewer policy.
+/// The round-robin policy also returns the rotation to persist once the merge
+/// request has been opened.
+fn select_reviewers(
+    mr_remote: Arc<dyn MergeRequest>,
+    config: Arc<dyn ConfigProperties>,
+    cli_args: &MergeRequestCliArgs,
+    target_branch: &str,
+    domain: String,
+    path: String,
+) -> Result<(Vec<Member>, Option<reviewer::Rotation>)> {
+    if !cli_args.reviewers.is_empty() {
+        let user_remote = remote::get_user(
+            domain,
+            path,
+            config.clone(),
+            Some(&cli_args.cache_args),
+            CacheType::File,
+        )?;
+        return Ok((
+            resolve_members(user_remote, &config, &cli_args.reviewers)?,
+            None,
+        ));
+    }
+    let policy = if cli_args.rand_reviewer {
+        Some(ReviewerPolicy::Random)
+    } else {
+        config.merge_request_reviewer_policy()
+    };
+    let members = config.merge_request_members();
+    let num_reviewers = config.merge_request_num_reviewers();
+    let reviewers = match policy {
+        Some(ReviewerPolicy::Random) => reviewer::random(&members, num_reviewers),
+        Some(ReviewerPolicy::RoundRobin) => {
+            let cache_location = config.cache_location().ok_or_else(|| {
+                GRError::PreconditionNotMet(
+                    "The round-robin reviewer policy requires a cache_location to persist the rotation"
+                        .to_string(),
+                )
+            })?;
+            let state_file = Path::new(cache_location).join(format!(
+                "reviewers-{}-{}",
+                domain,
+                path.replace('/', "_")
+            ));
+            let (reviewers, rotation) = reviewer::round_robin(&members, num_reviewers, &state_file);
+            return Ok((reviewers, Some(rotation)));
+        }
+        Some(ReviewerPolicy::LeastLoaded) => {
+            let user_remote = remote::get_user(
+                domain,
+                path,
+                config.clone(),
+                Some(&cli_args.cache_args),
+                CacheType::File,
+            )?;
+            // Authors cannot review their own merge requests.
+            let author = user_remote.get_auth_user()?;
+            reviewer::least_loaded(&members, num_reviewers, &author.username, |member| {
+                reviewer::review_load(&mr_remote, member)
+            })?
+        }
+        Some(ReviewerPolicy::Codeowners) => {
+            let toplevel = git::toplevel(&BlockingCommand)?;
+            let content = match reviewer::CODEOWNERS_FILES
+                .iter()
+                .find_map(|file| fs::read_to_string(Path::new(&toplevel).join(file)).ok())
+            {
+                Some(content) => content,
+                None => return Ok((vec![], None)),
+            };
+            let paths = git::outgoing_files(&BlockingCommand, "origin", target_branch)?;
+            let user_remote = remote::get_user(
+                domain,
+                path,
+                config.clone(),
+                Some(&cli_args.cache_args),
+                CacheType::File,
+            )?;
+            // Authors cannot review their own merge requests.
+            let author = user_remote.get_auth_user()?;
+            let owners: Vec<String> = reviewer::codeowners(&content, &paths)?
+                .into_iter()
+                .filter(|owner| owner != &author.username)
+                .collect();
+            resolve_members(user_remote, &config, &owners)?
+        }
+        None => vec![],
+    };
+    Ok((reviewers, None))
+}
+
+/// Resolve usernames to project members. Members defined in the merge requests
+/// configuration section are used first, the remote is queried otherwise.
+fn resolve_members(
+    remote: Arc<dyn UserInfo>,
+    config: &Arc<dyn ConfigProperties>,
+    usernames: &[String],
+) -> Result<Vec<Member>> {
+    let configured_members = config.merge_request_members();
+    usernames
+        .iter()
+        .map(|username| {
+            let member = match configured_members
+                .iter()
+                .find(|member| &member.username == username)
+            {
+                Some(member) => member.clone(),
+                None => remote.get(
+                    &UserCliArgs::builder()
+                        .username(username.to_string())
+                        .get_args(GetRemoteCliArgs::builder().build().unwrap())
+                        .build()
+                        .unwrap(),
+                )?,
+            };
+            Ok(Member {
+                mr_member_type: MrMemberType::Filled,
+                ..member
+            })
+        })
+        .collect()
+}
+
+fn update_body_args(
+    cli_args: &MergeRequestUpdateCliArgs,
+    description: Option<String>,
+    members: &[Member],
+) -> MergeRequestUpdateBodyArgs {
+    let find_member = |username: &String| {
+        members
+            .iter()
+            .find(|member| &member.username == username)
+            .cloned()
+    };
+    let reviewers = if cli_args.reviewers.is_empty() {
+        None
+    } else {
+        Some(cli_args.reviewers.iter().filter_map(find_member).collect())
+    };
+    let labels = if cli_args.labels.is_empty() {
+        None
+    } else {
+        Some(cli_args.labels.clone())
+    };
+    MergeRequestUpdateBodyArgs::builder()
+        .id(cli_args.id)
+        .title(cli_args.title.clone())
+        .description(description)
+        .target_branch(cli_args.target_branch.clone())
+        .assignee(cli_args.assignee.as_ref().and_then(find_member))
+        .reviewers(reviewers)
+        .draft(cli_args.draft)
+        .labels(labels)
+        .milestone(cli_args.milestone.clone())
+        .build()
+        .unwrap()
+}
+
+fn update<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    args: MergeRequestUpdateBodyArgs,
+    mut writer: W,
+) -> Result<()> {
+    if args.is_empty() {
+        return Err(GRError::PreconditionNotMet(
+            "Nothing to update. Provide at least one field to change or use --interactive"
+                .to_string(),
+        )
+        .into());
+    }
+    let merge_request_response = remote.update(args)?;
+    writer.write_all(
+        format!(
+            "Merge request updated: {}\n",
+            merge_request_response.web_url
+        )
+        .as_bytes(),
+    )?;
+    Ok(())
+}
+
 pub fn get_reader_file_cli(file_path: &str) -> Result<Box<dyn BufRead + Send + Sync>> {
     if file_path == "-" {
         Ok(Box::new(BufReader::new(std::io::stdin())))
@@ -569,9 +1298,9 @@ pub fn list_merge_requests(
     )?;
 
     let remote = remote::get_mr(
-        domain,
-        path,
-        config,
+        domain.clone(),
+        path.clone(),
+        config.clone(),
         Some(&cli_args.list_args.get_args.cache_args),
         CacheType::File,
     )?;
@@ -583,6 +1312,7 @@ pub fn list_merge_requests(
         .assignee(assignee)
         .author(author)
         .reviewer(reviewer)
+        .labels(cli_args.labels.clone())
         .build()?;
     if cli_args.list_args.num_pages {
         return common::num_merge_request_pages(remote, body_args, std::io::stdout());
@@ -590,6 +1320,22 @@ pub fn list_merge_requests(
     if cli_args.list_args.num_resources {
         return common::num_merge_request_resources(remote, body_args, std::io::stdout());
     }
+    if cli_args.unresolved_threads {
+        let thread_remote = remote::get_thread_mr(
+            domain,
+            path,
+            config,
+            Some(&cli_args.list_args.get_args.cache_args),
+            CacheType::File,
+        )?;
+        return list_with_unresolved_threads(
+            remote,
+            thread_remote,
+            body_args,
+            cli_args,
+            std::io::stdout(),
+        );
+    }
     list(remote, body_args, cli_args, std::io::stdout())
 }
 
@@ -599,6 +1345,7 @@ fn user_prompt_confirmation(
     description: String,
     target_branch: &String,
     cli_args: &MergeRequestCliArgs,
+    reviewers: Vec<Member>,
 ) -> Result<MergeRequestBodyArgs> {
     let mut title = mr_body.repo.title().to_string();
     if cli_args.draft {
@@ -615,25 +1362,10 @@ fn user_prompt_confirmation(
         None
     };
 
-    let reviewer = if cli_args.reviewer.is_some() {
-        Some(
-            Member::builder()
-                .username(cli_args.reviewer.clone().unwrap())
-                .build()
-                .unwrap(),
-        )
-    } else if cli_args.rand_reviewer {
-        let members = config.merge_request_members();
-        let num_members = members.len();
-        if num_members == 0 {
-            None
-        } else {
-            let rand_index = rand::random::<usize>();
-            let rand_user = members[rand_index % num_members].clone();
-            Some(rand_user)
-        }
+    let labels = if cli_args.labels.is_empty() {
+        config.merge_request_labels()
     } else {
-        None
+        cli_args.labels.clone()
     };
 
     let user_input = if cli_args.auto {
@@ -643,7 +1375,7 @@ fn user_prompt_confirmation(
             .title(title)
             .description(description)
             .assignee(preferred_assignee_members[0].clone())
-            .reviewer(reviewer.unwrap_or_default())
+            .reviewers(reviewers)
             .build()
             .unwrap()
     } else {
@@ -651,7 +1383,7 @@ fn user_prompt_confirmation(
             &title,
             &description,
             assignee.as_ref(),
-            reviewer.as_ref(),
+            &reviewers,
             &config,
         )?
     };
@@ -662,35 +1394,49 @@ fn user_prompt_confirmation(
         .source_branch(mr_body.repo.current_branch().to_string())
         .target_branch(target_branch.to_string())
         .assignee(user_input.assignee)
-        .reviewer(user_input.reviewer)
+        .reviewers(user_input.reviewers)
         // TODO make this configurable
         .remove_source_branch("true".to_string())
         .draft(cli_args.draft)
         .amend(cli_args.amend)
+        .labels(labels)
+        .milestone(cli_args.milestone.clone())
         .build()?)
 }
 
-/// Open a merge request.
+/// Open a merge request. Reviewers are selected given the target branch once
+/// the merge request is known to be valid, as selecting them can query the
+/// remote.
 fn open(
     remote: Arc<dyn MergeRequest>,
     config: Arc<dyn ConfigProperties>,
     mr_body: MergeRequestBody,
     cli_args: &MergeRequestCliArgs,
+    select_reviewers: impl FnOnce(&str) -> Result<(Vec<Member>, Option<reviewer::Rotation>)>,
 ) -> Result<()> {
     let source_branch = &mr_body.repo.current_branch();
     let target_branch = cli_args.target_branch.clone();
     let target_branch = target_branch.unwrap_or(mr_body.project.default_branch().to_string());
 
+    // make sure we are in a feature branch or bail
+    in_feature_branch(source_branch, &target_branch)?;
+
+    let (reviewers, rotation) = select_reviewers(&target_branch)?;
+
     let description = build_description(
-        mr_body.repo.last_commit_message(),
+        &template_description(&mr_body, cli_args, &target_branch)?,
         config.merge_request_description_signature(),
     );
 
-    // make sure we are in a feature branch or bail
-    in_feature_branch(source_branch, &target_branch)?;
-
     // confirm title, description and assignee
-    let args = user_prompt_confirmation(&mr_body, config, description, &target_branch, cli_args)?;
+    let args = user_prompt_confirmation(
+        &mr_body,
+        config,
+        description,
+        &target_branch,
+        cli_args,
+        reviewers,
+    )?;
 
     if cli_args.rebase.is_some() {
         git::rebase(&BlockingCommand, cli_args.rebase.as_ref().unwrap())?;
@@ -722,6 +1468,9 @@ fn open(
         git::push(&BlockingCommand, "origin", &mr_body.repo, cli_args.force)?;
         let merge_request_response = remote.open(args)?;
         println!("Merge request opened: {}", merge_request_response.web_url);
+        if let Some(rotation) = rotation {
+            rotation.save()?;
+        }
         if cli_args.open_browser {
             open::that(merge_request_response.web_url)?;
         }
@@ -863,6 +1612,47 @@ fn cmds<R: BufRead + Send + Sync + 'static>(
     cmds
 }
 
+/// Description of the merge request. If the repository has merge request
+/// templates, the chosen one gets filled in with the commit message and the
+/// outgoing commits.
+fn template_description(
+    mr_body: &MergeRequestBody,
+    cli_args: &MergeRequestCliArgs,
+    target_branch: &str,
+) -> Result<String> {
+    let description = mr_body.repo.last_commit_message().to_string();
+    let root = git::toplevel(&BlockingCommand)?;
+    let templates = template::discover(Path::new(&root))?;
+    let template = template::select(
+        templates,
+        cli_args.template.as_deref(),
+        !cli_args.auto,
+        |names| dialog::fuzzy_select("template:", names),
+    )?;
+    let template = match template {
+        Some(template) => template,
+        None => return Ok(description),
+    };
+    let commits = if template::needs_commits(&template) {
+        git::outgoing_commits(
+            &BlockingCommand,
+            "origin",
+            target_branch,
+            &SummaryOptions::Short,
+        )?
+    } else {
+        "".to_string()
+    };
+    let values = template::TemplateValues::builder()
+        .title(mr_body.repo.title().to_string())
+        .description(description)
+        .commits(commits)
+        .source_branch(mr_body.repo.current_branch().to_string())
+        .target_branch(target_branch.to_string())
+        .build()?;
+    Ok(template::render(&template, &values))
+}
+
 // append description signature from the configuration
 fn build_description(description: &str, signature: &str) -> String {
     if description.is_empty() && signature.is_empty() {
@@ -951,17 +1741,129 @@ fn list<W: Write>(
     common::list_merge_requests(remote, body_args, cli_args, &mut writer)
 }
 
-fn merge(remote: Arc<dyn MergeRequest>, merge_request_id: i64) -> Result<()> {
-    let merge_request = remote.merge(merge_request_id)?;
-    println!("Merge request merged: {}", merge_request.web_url);
+fn list_with_unresolved_threads<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    thread_remote: Arc<dyn MergeRequestThread>,
+    body_args: MergeRequestListBodyArgs,
+    cli_args: MergeRequestListCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let mut merge_requests =
+        list_remote_objs!(remote, body_args, cli_args.list_args, writer, MergeRequest);
+    for merge_request in merge_requests.iter_mut() {
+        let threads = thread_remote.list(merge_request.id)?;
+        merge_request.unresolved_threads = Some(thread::count_unresolved(&threads));
+    }
+    display::print(&mut writer, merge_requests, cli_args.list_args.get_args)?;
+    Ok(())
+}
+
+fn merge_body_args(
+    cli_args: &MergeRequestMergeCliArgs,
+    config: &Arc<dyn ConfigProperties>,
+) -> MergeRequestMergeBodyArgs {
+    // A squash commit message implies squashing.
+    let merge_method = if cli_args.squash_message.is_some() {
+        Some(MergeMethod::Squash)
+    } else {
+        cli_args
+            .merge_method
+            .or_else(|| config.merge_request_merge_method())
+    };
+    MergeRequestMergeBodyArgs::builder()
+        .id(cli_args.id)
+        .merge_method(merge_method)
+        .squash_message(cli_args.squash_message.clone())
+        .delete_branch(
+            cli_args
+                .delete_branch
+                .unwrap_or_else(|| config.merge_request_delete_branch()),
+        )
+        .sha(cli_args.sha.clone())
+        .auto_merge(cli_args.auto_merge)
+        .build()
+        .unwrap()
+}
+
+fn merge<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    args: MergeRequestMergeBodyArgs,
+    mut writer: W,
+) -> Result<()> {
+    let auto_merge = args.auto_merge;
+    let merge_request = remote.merge(args)?;
+    if auto_merge {
+        writeln!(
+            writer,
+            "Merge request will be merged when the pipeline succeeds: {}",
+            merge_request.web_url
+        )?;
+    } else {
+        writeln!(writer, "Merge request merged: {}", merge_request.web_url)?;
+    }
+    Ok(())
+}
+
+/// Local branch holding the changes of a merge request. It is namespaced, so
+/// it does not collide with a local branch of the same name or with a branch
+/// coming from a fork.
+fn checkout_branch(id: i64, source_branch: &str) -> String {
+    format!("mr/{}/{}", id, source_branch)
+}
+
+fn checkout(
+    remote: Arc<dyn MergeRequest>,
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    cli_args: MergeRequestCheckoutCliArgs,
+) -> Result<()> {
+    // Fetch the merge request ref published by the remote, which works for
+    // merge requests coming from forks. Assume origin for now.
+    let head_ref = remote.head_ref(cli_args.id);
+    git::fetch_ref(runner, "origin", &head_ref)?;
+    let head = git::rev_parse(runner, "FETCH_HEAD")?;
+    if cli_args.detach {
+        match &cli_args.worktree {
+            Some(worktree) => git::worktree_add(runner, worktree, &head, true)?,
+            None => git::checkout_detached(runner, &head)?,
+        };
+        return Ok(());
+    }
+    let merge_request = remote.get(cli_args.id)?;
+    let branch = checkout_branch(cli_args.id, &merge_request.source_branch);
+    let checked_out_at = git::branch_worktree(runner, &branch)?;
+    if git::branch_exists(runner, &branch)? {
+        // Only move the branch forward if it does not drop any local commits,
+        // ex. fixups made while reviewing or a rebased merge request.
+        let local_commits = git::count_commits(runner, &head, &branch)?;
+        if local_commits > 0 {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Branch {} has {} commit(s) not in the merge request head. \
+                 Push or delete the branch before checking it out again",
+                branch, local_commits
+            ))
+            .into());
+        }
+    }
+    match &checked_out_at {
+        // Git refuses to move a branch checked out in a worktree.
+        Some(path) => git::fast_forward(runner, path, &head)?,
+        None => git::update_branch(runner, &branch, &head)?,
+    };
+    match (&cli_args.worktree, &checked_out_at) {
+        (Some(worktree), Some(path)) if same_path(worktree, path) => {}
+        (Some(worktree), _) => {
+            git::worktree_add(runner, worktree, &branch, false)?;
+        }
+        (None, _) => {
+            git::switch(runner, &branch)?;
+        }
+    };
     Ok(())
 }
 
-fn checkout(remote: Arc<dyn MergeRequest>, id: i64) -> Result<()> {
-    let merge_request = remote.get(id)?;
-    // assume origin for now
-    git::fetch(Arc::new(BlockingCommand), "origin".to_string())?;
-    git::checkout(&BlockingCommand, &merge_request.source_branch)
+fn same_path(a: &str, b: &str) -> bool {
+    let canonical = |path: &str| std::fs::canonicalize(path).unwrap_or_else(|_| path.into());
+    canonical(a) == canonical(b)
 }
 
 fn close(remote: Arc<dyn MergeRequest>, id: i64) -> Result<()> {
@@ -970,26 +1872,36 @@ fn close(remote: Arc<dyn MergeRequest>, id: i64) -> Result<()> {
     Ok(())
 }
 
+fn reopen<W: Write>(remote: Arc<dyn MergeRequest>, id: i64, mut writer: W) -> Result<()> {
+    let merge_request = remote.reopen(id)?;
+    writer.write_all(format!("Merge request reopened: {}\n", merge_request.web_url).as_bytes())?;
+    Ok(())
+}
+
 fn approve<W: Write>(remote: Arc<dyn MergeRequest>, id: i64, mut writer: W) -> Result<()> {
     let merge_request = remote.approve(id)?;
     writer.write_all(format!("Merge request approved: {}\n", merge_request.web_url).as_bytes())?;
     Ok(())
 }
 
+/// Comment given in the command line or read from a file.
+fn comment_body<R: BufRead>(comment: Option<String>, reader: Option<R>) -> Result<String> {
+    if let Some(comment) = comment {
+        return Ok(comment);
+    }
+    let mut comment = String::new();
+    // The unwrap is Ok here. This is enforced at the CLI interface. The
+    // user is required to provide a file or a comment.
+    reader.unwrap().read_to_string(&mut comment)?;
+    Ok(comment)
+}
+
 fn create_comment<R: BufRead>(
     remote: Arc<dyn CommentMergeRequest>,
     args: CommentMergeRequestCliArgs,
     reader: Option<R>,
 ) -> Result<()> {
-    let comment = if let Some(comment) = args.comment {
-        comment
-    } else {
-        let mut comment = String::new();
-        // The unwrap is Ok here. This is enforced at the CLI interface. The
-        // user is required to provide a file or a comment.
-        reader.unwrap().read_to_string(&mut comment)?;
-        comment
-    };
+    let comment = comment_body(args.comment, reader)?;
     remote.create(
         CommentMergeRequestBodyArgs::builder()
             .id(args.id)
@@ -999,6 +1911,22 @@ fn create_comment<R: BufRead>(
     )
 }
 
+fn edit_comment<R: BufRead>(
+    remote: Arc<dyn CommentMergeRequest>,
+    args: CommentMergeRequestEditCliArgs,
+    reader: Option<R>,
+) -> Result<()> {
+    let comment = comment_body(args.comment, reader)?;
+    remote.update(
+        CommentMergeRequestUpdateBodyArgs::builder()
+            .id(args.id)
+            .comment_id(args.comment_id)
+            .comment(comment)
+            .build()
+            .unwrap(),
+    )
+}
+
 pub fn get_merge_request_details<W: Write>(
     remote: Arc<dyn MergeRequest>,
     args: MergeRequestGetCliArgs,
@@ -1009,6 +1937,108 @@ pub fn get_merge_request_details<W: Write>(
     Ok(())
 }
 
+fn diff<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    cli_args: MergeRequestDiffCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let diffs = remote.diff(cli_args.id)?;
+    if cli_args.stat || cli_args.name_only {
+        // File lists can be formatted, the unified diff cannot.
+        if let display::Format::JSON | display::Format::TOML | display::Format::CSV =
+            cli_args.get_args.format
+        {
+            return display::print(&mut writer, diffs, cli_args.get_args);
+        }
+        if cli_args.name_only {
+            for diff in diffs {
+                writeln!(writer, "{}", diff.new_path)?;
+            }
+            return Ok(());
+        }
+        return write_diff_stat(&diffs, &mut writer);
+    }
+    for diff in diffs {
+        write_unified_diff(&diff, &mut writer)?;
+    }
+    Ok(())
+}
+
+fn write_unified_diff<W: Write>(diff: &MergeRequestDiff, writer: &mut W) -> Result<()> {
+    writeln!(writer, "diff --git a/{} b/{}", diff.old_path, diff.new_path)?;
+    let old_path = match diff.status.as_str() {
+        "added" => "/dev/null".to_string(),
+        _ => format!("a/{}", diff.old_path),
+    };
+    let new_path = match diff.status.as_str() {
+        "removed" => "/dev/null".to_string(),
+        _ => format!("b/{}", diff.new_path),
+    };
+    if diff.diff.is_empty() {
+        if diff.status != "renamed" {
+            writeln!(writer, "Binary files {} and {} differ", old_path, new_path)?;
+        }
+        return Ok(());
+    }
+    writeln!(writer, "--- {}", old_path)?;
+    writeln!(writer, "+++ {}", new_path)?;
+    write!(writer, "{}", diff.diff)?;
+    if !diff.diff.ends_with('\n') {
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Same layout as `git diff --stat`.
+fn write_diff_stat<W: Write>(diffs: &[MergeRequestDiff], writer: &mut W) -> Result<()> {
+    const MAX_BAR_WIDTH: usize = 50;
+    let path_width = diffs
+        .iter()
+        .map(|diff| diff.new_path.len())
+        .max()
+        .unwrap_or_default();
+    let max_changes = diffs
+        .iter()
+        .map(|diff| diff.additions + diff.deletions)
+        .max()
+        .unwrap_or_default();
+    let changes_width = max_changes.to_string().len();
+    let (mut additions, mut deletions) = (0, 0);
+    for diff in diffs {
+        let changes = diff.additions + diff.deletions;
+        // Scale down the bar so the largest change fits.
+        let (plus, minus) = if max_changes > MAX_BAR_WIDTH {
+            (
+                diff.additions * MAX_BAR_WIDTH / max_changes,
+                diff.deletions * MAX_BAR_WIDTH / max_changes,
+            )
+        } else {
+            (diff.additions, diff.deletions)
+        };
+        writeln!(
+            writer,
+            " {:<path_width$} | {:>changes_width$} {}{}",
+            diff.new_path,
+            changes,
+            "+".repeat(plus),
+            "-".repeat(minus),
+        )?;
+        additions += diff.additions;
+        deletions += diff.deletions;
+    }
+    writeln!(
+        writer,
+        " {} file{} changed, {} insertion{}(+), {} deletion{}(-)",
+        diffs.len(),
+        if diffs.len() == 1 { "" } else { "s" },
+        additions,
+        if additions == 1 { "" } else { "s" },
+        deletions,
+        if deletions == 1 { "" } else { "s" },
+    )?;
+    Ok(())
+}
+
 fn list_comments<W: Write>(
     remote: Arc<dyn CommentMergeRequest>,
     body_args: CommentMergeRequestListBodyArgs,
@@ -1026,8 +2056,11 @@ mod tests {
     };
 
     use crate::{
-        api_traits::CommentMergeRequest, cli::browse::BrowseOptions,
-        cmds::project::ProjectListBodyArgs, error,
+        api_traits::CommentMergeRequest,
+        cli::browse::BrowseOptions,
+        cmds::project::ProjectListBodyArgs,
+        error,
+        test::utils::{config, MockRunner},
     };
 
     use super::*;
@@ -1279,6 +2312,72 @@ mod tests {
         assert_eq!("", String::from_utf8(buf).unwrap());
     }
 
+    struct ThreadRemoteMock;
+
+    impl MergeRequestThread for ThreadRemoteMock {
+        fn list(&self, id: i64) -> Result<Vec<thread::Thread>> {
+            // Merge request 1 has two unresolved threads, the rest none.
+            let unresolved = if id == 1 { 2 } else { 0 };
+            Ok(vec![
+                thread::Thread::builder()
+                    .resolvable(true)
+                    .build()
+                    .unwrap();
+                unresolved
+            ])
+        }
+        fn reply(&self, _id: i64, _thread_id: &str, _body: String) -> Result<()> {
+            unimplemented!()
+        }
+        fn resolve(&self, _id: i64, _thread_id: &str, _resolved: bool) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_list_merge_requests_with_unresolved_threads() {
+        let merge_request = |id: i64| {
+            MergeRequestResponse::builder()
+                .id(id)
+                .title("New feature".to_string())
+                .author("author".to_string())
+                .build()
+                .unwrap()
+        };
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![merge_request(1), merge_request(2)])
+                .build()
+                .unwrap(),
+        );
+        let body_args = MergeRequestListBodyArgs::builder()
+            .list_args(None)
+            .state(MergeRequestState::Opened)
+            .build()
+            .unwrap();
+        let cli_args = MergeRequestListCliArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .unresolved_threads(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_with_unresolved_threads(
+            remote,
+            Arc::new(ThreadRemoteMock),
+            body_args,
+            cli_args,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            "ID|Title|Source Branch|Author|URL|Updated at|Unresolved threads\n\
+             1|New feature||author|||2\n\
+             2|New feature||author|||0\n",
+            String::from_utf8(buf).unwrap(),
+        )
+    }
+
     #[test]
     fn test_list_merge_requests_no_headers() {
         let remote = Arc::new(
@@ -1324,6 +2423,8 @@ mod tests {
     struct MergeRequestRemoteMock {
         #[builder(default = "Vec::new()")]
         merge_requests: Vec<MergeRequestResponse>,
+        #[builder(default)]
+        diffs: Vec<MergeRequestDiff>,
     }
 
     impl MergeRequestRemoteMock {
@@ -1339,8 +2440,8 @@ mod tests {
         fn list(&self, _args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
             Ok(self.merge_requests.clone())
         }
-        fn merge(&self, _id: i64) -> Result<MergeRequestResponse> {
-            Ok(MergeRequestResponse::builder().build().unwrap())
+        fn merge(&self, _args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+            Ok(self.merge_requests[0].clone())
         }
         fn get(&self, _id: i64) -> Result<MergeRequestResponse> {
             Ok(self.merge_requests[0].clone())
@@ -1348,12 +2449,27 @@ mod tests {
         fn close(&self, _id: i64) -> Result<MergeRequestResponse> {
             Ok(MergeRequestResponse::builder().build().unwrap())
         }
+        fn reopen(&self, _id: i64) -> Result<MergeRequestResponse> {
+            Ok(self.merge_requests[0].clone())
+        }
+        fn update(&self, _args: MergeRequestUpdateBodyArgs) -> Result<MergeRequestResponse> {
+            Ok(self.merge_requests[0].clone())
+        }
         fn num_pages(&self, _args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
             Ok(None)
         }
         fn approve(&self, _id: i64) -> Result<MergeRequestResponse> {
             Ok(self.merge_requests[0].clone())
         }
+        fn diff(&self, _id: i64) -> Result<Vec<MergeRequestDiff>> {
+            Ok(self.diffs.clone())
+        }
+        fn head_ref(&self, id: i64) -> String {
+            format!("refs/pull/{}/head", id)
+        }
+        fn rebase(&self, _id: i64) -> Result<()> {
+            Ok(())
+        }
 
         fn num_resources(
             &self,
@@ -1433,6 +2549,22 @@ mod tests {
             Ok(())
         }
 
+        fn update(&self, args: CommentMergeRequestUpdateBodyArgs) -> Result<()> {
+            let mut called = self.comment_called.lock().unwrap();
+            *called = true;
+            let mut argument = self.comment_argument.lock().unwrap();
+            *argument = format!("{}: {}", args.comment_id, args.comment);
+            Ok(())
+        }
+
+        fn delete(&self, _id: i64, _comment_id: i64) -> Result<()> {
+            todo!()
+        }
+
+        fn react(&self, _id: i64, _comment_id: i64, _reaction: Reaction) -> Result<()> {
+            todo!()
+        }
+
         fn list(&self, _args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
             Ok(self.list_comments.clone())
         }
@@ -1631,7 +2763,7 @@ mod tests {
             .unwrap();
         let reader = Cursor::new("comment");
         assert!(create_comment(remote.clone(), cli_args, Some(reader)).is_ok());
-        assert!(remote.comment_called.lock().unwrap().clone());
+        assert!(*remote.comment_called.lock().unwrap());
         assert_eq!(
             "All features complete, ship it",
             remote.comment_argument.lock().unwrap().clone(),
@@ -1656,6 +2788,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_edit_comment_on_a_merge_request_with_comment_from_file_ok() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let cli_args = CommentMergeRequestEditCliArgs::builder()
+            .id(1)
+            .comment_id(1234)
+            .comment(None)
+            .comment_from_file(Some("comment_file.txt".to_string()))
+            .build()
+            .unwrap();
+        let reader = Cursor::new("Fixed typo");
+        assert!(edit_comment(remote.clone(), cli_args, Some(reader)).is_ok());
+        assert!(remote.comment_called.lock().unwrap().clone());
+        assert_eq!(
+            "1234: Fixed typo",
+            remote.comment_argument.lock().unwrap().clone(),
+        );
+    }
+
     struct ErrorReader {}
 
     impl Read for ErrorReader {
@@ -1712,6 +2863,9 @@ mod tests {
             .pipeline_url(Some(
                 "https://gitlab.com/owner/repo/-/pipelines/1".to_string(),
             ))
+            .labels(vec!["bug".to_string(), "ui".to_string()])
+            .milestone("v1.0".to_string())
+            .state("merged".to_string())
             .build()
             .unwrap();
         let remote = Arc::new(
@@ -1723,8 +2877,8 @@ mod tests {
         let mut writer = Vec::new();
         get_merge_request_details(remote, cli_args, &mut writer).unwrap();
         assert_eq!(
-            "ID|Title|Source Branch|SHA|Description|Author|URL|Updated at|Merged at|Pipeline ID|Pipeline URL\n\
-             1|New feature|||Implement get merge request||https://gitlab.com/owner/repo/-/merge_requests/1||2024-03-03T00:00:00Z|1|https://gitlab.com/owner/repo/-/pipelines/1\n",
+            "ID|Title|Source Branch|SHA|Description|Author|URL|Updated at|Merged at|Pipeline ID|Pipeline URL|Labels|Milestone|State\n\
+             1|New feature|||Implement get merge request||https://gitlab.com/owner/repo/-/merge_requests/1||2024-03-03T00:00:00Z|1|https://gitlab.com/owner/repo/-/pipelines/1|bug,ui|v1.0|merged\n",
             String::from_utf8(writer).unwrap(),
         )
     }
@@ -1750,6 +2904,340 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reopen_merge_request_ok() {
+        let reopen_response = MergeRequestResponse::builder()
+            .id(1)
+            .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
+            .state("opened".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![reopen_response])
+                .build()
+                .unwrap(),
+        );
+        let mut writer = Vec::new();
+        reopen(remote, 1, &mut writer).unwrap();
+        assert_eq!(
+            "Merge request reopened: https://gitlab.com/owner/repo/-/merge_requests/1\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_merge_merge_request_ok() {
+        let merge_response = MergeRequestResponse::builder()
+            .id(1)
+            .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![merge_response])
+                .build()
+                .unwrap(),
+        );
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(1)
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        merge(remote, args, &mut writer).unwrap();
+        assert_eq!(
+            "Merge request will be merged when the pipeline succeeds: https://gitlab.com/owner/repo/-/merge_requests/1\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_open_in_target_branch_does_not_select_reviewers() {
+        let remote = Arc::new(MergeRequestRemoteMock::builder().build().unwrap());
+        let config: Arc<dyn ConfigProperties> = Arc::new(MergeDefaultsConfig);
+        let mut repo = Repo::new();
+        repo.with_current_branch("main");
+        let mr_body = MergeRequestBody::builder()
+            .repo(repo)
+            .project(Project::new(1, "main"))
+            .build()
+            .unwrap();
+        let cli_args = MergeRequestCliArgs::builder()
+            .title(None)
+            .body_from_commit(None)
+            .description(None)
+            .description_from_file(None)
+            .target_branch(None)
+            .auto(false)
+            .cache_args(CacheCliArgs::default())
+            .open_browser(false)
+            .accept_summary(false)
+            .commit(None)
+            .draft(false)
+            .force(false)
+            .amend(false)
+            .dry_run(false)
+            .build()
+            .unwrap();
+        let mut selected = false;
+        let err = open(remote, config, mr_body, &cli_args, |_| {
+            selected = true;
+            Ok((vec![], None))
+        })
+        .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => (),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+        assert!(!selected);
+    }
+
+    struct MergeDefaultsConfig;
+
+    impl ConfigProperties for MergeDefaultsConfig {
+        fn api_token(&self) -> &str {
+            "1234"
+        }
+        fn cache_location(&self) -> Option<&str> {
+            None
+        }
+        fn merge_request_merge_method(&self) -> Option<MergeMethod> {
+            Some(MergeMethod::Rebase)
+        }
+        fn merge_request_delete_branch(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_merge_body_args_fall_back_to_config() {
+        let config: Arc<dyn ConfigProperties> = Arc::new(MergeDefaultsConfig);
+        let cli_args = MergeRequestMergeCliArgs::builder().id(1).build().unwrap();
+        let args = merge_body_args(&cli_args, &config);
+        assert_eq!(Some(MergeMethod::Rebase), args.merge_method);
+        assert!(args.delete_branch);
+
+        let cli_args = MergeRequestMergeCliArgs::builder()
+            .id(1)
+            .squash_message(Some("New feature".to_string()))
+            .delete_branch(Some(false))
+            .build()
+            .unwrap();
+        let args = merge_body_args(&cli_args, &config);
+        assert!(args.squash());
+        assert!(!args.delete_branch);
+    }
+
+    fn diff_remote() -> Arc<MergeRequestRemoteMock> {
+        let diffs = vec![
+            MergeRequestDiff::builder()
+                .old_path("src/main.rs".to_string())
+                .new_path("src/main.rs".to_string())
+                .status("modified".to_string())
+                .additions(2)
+                .deletions(1)
+                .diff("@@ -1,2 +1,3 @@\n fn main() {\n-    println!(\"hi\");\n+    let name = \"gr\";\n+    println!(\"{}\", name);\n".to_string())
+                .build()
+                .unwrap(),
+            MergeRequestDiff::builder()
+                .old_path("README.md".to_string())
+                .new_path("README.md".to_string())
+                .status("added".to_string())
+                .additions(1)
+                .diff("@@ -0,0 +1 @@\n+# gr".to_string())
+                .build()
+                .unwrap(),
+        ];
+        Arc::new(
+            MergeRequestRemoteMock::builder()
+                .diffs(diffs)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn diff_cli_args(
+        stat: bool,
+        name_only: bool,
+        format: display::Format,
+    ) -> MergeRequestDiffCliArgs {
+        MergeRequestDiffCliArgs::builder()
+            .id(1)
+            .stat(stat)
+            .name_only(name_only)
+            .get_args(GetRemoteCliArgs::builder().format(format).build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_diff_merge_request_unified() {
+        let mut writer = Vec::new();
+        diff(
+            diff_remote(),
+            diff_cli_args(false, false, display::Format::PIPE),
+            &mut writer,
+        )
+        .unwrap();
+        let expected = r#"diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,3 @@
+ fn main() {
+-    println!("hi");
++    let name = "gr";
++    println!("{}", name);
+diff --git a/README.md b/README.md
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# gr
+"#;
+        assert_eq!(expected, String::from_utf8(writer).unwrap());
+    }
+
+    #[test]
+    fn test_diff_merge_request_stat() {
+        let mut writer = Vec::new();
+        diff(
+            diff_remote(),
+            diff_cli_args(true, false, display::Format::PIPE),
+            &mut writer,
+        )
+        .unwrap();
+        let expected = " src/main.rs | 3 ++-\n README.md   | 1 +\n 2 files changed, 3 insertions(+), 1 deletion(-)\n";
+        assert_eq!(expected, String::from_utf8(writer).unwrap());
+    }
+
+    #[test]
+    fn test_diff_merge_request_name_only() {
+        let mut writer = Vec::new();
+        diff(
+            diff_remote(),
+            diff_cli_args(false, true, display::Format::PIPE),
+            &mut writer,
+        )
+        .unwrap();
+        assert_eq!(
+            "src/main.rs\nREADME.md\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_diff_merge_request_name_only_json() {
+        let mut writer = Vec::new();
+        diff(
+            diff_remote(),
+            diff_cli_args(false, true, display::Format::JSON),
+            &mut writer,
+        )
+        .unwrap();
+        let output = String::from_utf8(writer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(2, lines.len());
+        let file: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!("README.md", file["path"]);
+        assert_eq!("added", file["status"]);
+        assert_eq!("1", file["additions"]);
+    }
+
+    #[test]
+    fn test_count_diff_lines() {
+        let diff = "--- a/file\n+++ b/file\n@@ -1,2 +1,2 @@\n-old\n+new\n+more\n context";
+        assert_eq!((2, 1), count_diff_lines(diff));
+    }
+
+    #[test]
+    fn test_count_diff_lines_counts_header_like_lines_in_hunks() {
+        // Removing a markdown rule "--" and adding a "++" counter.
+        let diff = "@@ -1,2 +1,2 @@\n---\n+++i;\n context";
+        assert_eq!((1, 1), count_diff_lines(diff));
+    }
+
+    #[test]
+    fn test_update_merge_request_ok() {
+        let update_response = MergeRequestResponse::builder()
+            .id(1)
+            .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![update_response])
+                .build()
+                .unwrap(),
+        );
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(1)
+            .title(Some("Fix typo".to_string()))
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        update(remote, args, &mut writer).unwrap();
+        assert_eq!(
+            "Merge request updated: https://gitlab.com/owner/repo/-/merge_requests/1\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_update_merge_request_nothing_to_update_is_error() {
+        let remote = Arc::new(MergeRequestRemoteMock::builder().build().unwrap());
+        let args = MergeRequestUpdateBodyArgs::builder().id(1).build().unwrap();
+        let mut writer = Vec::new();
+        let err = update(remote, args, &mut writer).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => (),
+            _ => panic!("Expected PreconditionNotMet error"),
+        }
+    }
+
+    struct UserInfoMock;
+
+    impl UserInfo for UserInfoMock {
+        fn get_auth_user(&self) -> Result<Member> {
+            Ok(Member::default())
+        }
+
+        fn get(&self, args: &UserCliArgs) -> Result<Member> {
+            Ok(Member::builder()
+                .id(42)
+                .username(args.username.clone())
+                .build()
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_update_body_args_resolve_usernames() {
+        let cli_args = MergeRequestUpdateCliArgs::builder()
+            .id(3)
+            .assignee(Some("tsawyer".to_string()))
+            .reviewers(vec!["hfinn".to_string(), "tsawyer".to_string()])
+            .draft(Some(false))
+            .build()
+            .unwrap();
+        let members = resolve_members(
+            Arc::new(UserInfoMock),
+            &config(),
+            &["hfinn".to_string(), "tsawyer".to_string()],
+        )
+        .unwrap();
+        let args = update_body_args(&cli_args, None, &members);
+        assert_eq!(3, args.id);
+        assert_eq!(None, args.title);
+        let assignee = args.assignee.unwrap();
+        assert_eq!("tsawyer", assignee.username);
+        assert_eq!(42, assignee.id);
+        assert_eq!(MrMemberType::Filled, assignee.mr_member_type);
+        let reviewers = args.reviewers.unwrap();
+        assert_eq!(2, reviewers.len());
+        assert_eq!("hfinn", reviewers[0].username);
+        assert_eq!(Some(false), args.draft);
+    }
+
     #[test]
     fn test_cmds_fetch_cli_arg() {
         let remote = Arc::new(MockRemoteProject::default());
@@ -1827,4 +3315,162 @@ mod tests {
             String::from_utf8(buf).unwrap(),
         );
     }
+
+    fn checkout_remote() -> Arc<MergeRequestRemoteMock> {
+        Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![MergeRequestResponse::builder()
+                    .id(23)
+                    .source_branch("feature".to_string())
+                    .build()
+                    .unwrap()])
+                .build()
+                .unwrap(),
+        )
+    }
+
+    /// Runner replying to the git commands issued by checkout, given in the
+    /// order they are run.
+    fn checkout_runner(bodies: &[&str]) -> MockRunner<ShellResponse> {
+        MockRunner::new(
+            bodies
+                .iter()
+                .rev()
+                .map(|body| {
+                    ShellResponse::builder()
+                        .body(body.to_string())
+                        .build()
+                        .unwrap()
+                })
+                .collect(),
+        )
+    }
+
+    const MAIN_WORKTREE: &str = "worktree /home/user/repo\nHEAD 1111111\nbranch refs/heads/main\n";
+
+    #[test]
+    fn test_checkout_merge_request_into_namespaced_branch() {
+        let runner = checkout_runner(&["", "abc123", MAIN_WORKTREE, "", "", ""]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .build()
+            .unwrap();
+        checkout(checkout_remote(), &runner, cli_args).unwrap();
+        assert_eq!(6, *runner.run_count.borrow());
+        assert_eq!("git checkout mr/23/feature", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_branch_update_error_stops_checkout() {
+        let mut responses = vec![ShellResponse::builder().status(1).build().unwrap()];
+        responses.extend(["", MAIN_WORKTREE, "abc123", ""].iter().map(|body| {
+            ShellResponse::builder()
+                .body(body.to_string())
+                .build()
+                .unwrap()
+        }));
+        let runner = MockRunner::new(responses);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .build()
+            .unwrap();
+        assert!(checkout(checkout_remote(), &runner, cli_args).is_err());
+        assert_eq!(5, *runner.run_count.borrow());
+        assert_eq!("git branch -f mr/23/feature abc123", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_into_worktree() {
+        let runner = checkout_runner(&["", "abc123", MAIN_WORKTREE, "", "", ""]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .worktree(Some("../review".to_string()))
+            .build()
+            .unwrap();
+        checkout(checkout_remote(), &runner, cli_args).unwrap();
+        assert_eq!("git worktree add ../review mr/23/feature", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_detached() {
+        let runner = checkout_runner(&["", "abc123", ""]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .detach(true)
+            .build()
+            .unwrap();
+        checkout(checkout_remote(), &runner, cli_args).unwrap();
+        assert_eq!("git checkout --detach abc123", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_fetches_into_fetch_head() {
+        let runner = MockRunner::new(vec![
+            ShellResponse::builder().build().unwrap(),
+            ShellResponse::builder()
+                .status(1)
+                .body("fatal: couldn't find remote ref refs/pull/23/head".to_string())
+                .build()
+                .unwrap(),
+        ]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .build()
+            .unwrap();
+        assert!(checkout(checkout_remote(), &runner, cli_args).is_err());
+        assert_eq!(1, *runner.run_count.borrow());
+        assert_eq!("git fetch origin refs/pull/23/head", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_again_fast_forwards_existing_branch() {
+        let runner =
+            checkout_runner(&["", "abc123", MAIN_WORKTREE, "mr/23/feature\n", "0", "", ""]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .build()
+            .unwrap();
+        checkout(checkout_remote(), &runner, cli_args).unwrap();
+        assert_eq!(7, *runner.run_count.borrow());
+        assert_eq!("git checkout mr/23/feature", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_again_in_its_worktree() {
+        let worktrees = format!(
+            "{}\nworktree /home/user/review\nHEAD 2222222\nbranch refs/heads/mr/23/feature\n",
+            MAIN_WORKTREE
+        );
+        let runner = checkout_runner(&["", "abc123", &worktrees, "mr/23/feature\n", "0", ""]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .worktree(Some("/home/user/review".to_string()))
+            .build()
+            .unwrap();
+        checkout(checkout_remote(), &runner, cli_args).unwrap();
+        // The branch is checked out, so it is fast-forwarded in place and no
+        // worktree is added.
+        assert_eq!(6, *runner.run_count.borrow());
+        assert_eq!(
+            "git -C /home/user/review merge --ff-only abc123",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_checkout_merge_request_again_refuses_to_drop_local_commits() {
+        let runner = checkout_runner(&["", "abc123", MAIN_WORKTREE, "mr/23/feature\n", "2"]);
+        let cli_args = MergeRequestCheckoutCliArgs::builder()
+            .id(23)
+            .build()
+            .unwrap();
+        let err = checkout(checkout_remote(), &runner, cli_args).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("mr/23/feature has 2 commit(s)"))
+            }
+            _ => panic!("Expected PreconditionNotMet error"),
+        }
+        assert_eq!("git rev-list --count abc123..mr/23/feature", *runner.cmd());
+    }
 }
@@ -0,0 +1,122 @@
+//! Commits of a merge request along with their CI status.
+
+use crate::{
+    api_traits::Timestamp,
+    display::{Column, DisplayBody},
+    remote::{ListBodyArgs, ListRemoteCliArgs},
+};
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct Commit {
+    pub sha: String,
+    pub author: String,
+    /// First line of the commit message.
+    pub title: String,
+    pub date: String,
+    /// Combined status of the CI jobs or checks run on the commit. Only
+    /// retrieved when displaying optional columns.
+    pub ci_status: String,
+}
+
+impl Commit {
+    pub fn builder() -> CommitBuilder {
+        CommitBuilder::default()
+    }
+}
+
+impl Timestamp for Commit {
+    fn created_at(&self) -> String {
+        self.date.clone()
+    }
+}
+
+impl From<Commit> for DisplayBody {
+    fn from(commit: Commit) -> Self {
+        DisplayBody::new(vec![
+            Column::new("SHA", commit.sha),
+            Column::new("Author", commit.author),
+            Column::new("Title", commit.title),
+            Column::new("Date", commit.date),
+            Column::builder()
+                .name("CI status".to_string())
+                .value(commit.ci_status)
+                .optional(true)
+                .build()
+                .unwrap(),
+        ])
+    }
+}
+
+#[derive(Builder)]
+pub struct MergeRequestCommitListCliArgs {
+    pub id: i64,
+    pub list_args: ListRemoteCliArgs,
+}
+
+impl MergeRequestCommitListCliArgs {
+    pub fn builder() -> MergeRequestCommitListCliArgsBuilder {
+        MergeRequestCommitListCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct MergeRequestCommitListBodyArgs {
+    pub id: i64,
+    pub list_args: Option<ListBodyArgs>,
+    /// Retrieve the CI status of each commit. Takes one extra API call per
+    /// commit.
+    #[builder(default)]
+    pub ci_status: bool,
+}
+
+impl MergeRequestCommitListBodyArgs {
+    pub fn builder() -> MergeRequestCommitListBodyArgsBuilder {
+        MergeRequestCommitListBodyArgsBuilder::default()
+    }
+}
+
+/// Status of a single CI job or check, normalized across remotes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CiStatus {
+    Success,
+    Failed,
+    Pending,
+}
+
+/// Combines the status of all the jobs or checks of a commit. Any failure
+/// fails the commit, otherwise any job still running keeps it pending. No jobs
+/// at all gives an empty status.
+pub fn combined_status(statuses: &[CiStatus]) -> String {
+    if statuses.is_empty() {
+        "".to_string()
+    } else if statuses.contains(&CiStatus::Failed) {
+        "failed".to_string()
+    } else if statuses.contains(&CiStatus::Pending) {
+        "pending".to_string()
+    } else {
+        "success".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combined_status() {
+        assert_eq!("", combined_status(&[]));
+        assert_eq!(
+            "success",
+            combined_status(&[CiStatus::Success, CiStatus::Success])
+        );
+        assert_eq!(
+            "pending",
+            combined_status(&[CiStatus::Success, CiStatus::Pending])
+        );
+        assert_eq!(
+            "failed",
+            combined_status(&[CiStatus::Pending, CiStatus::Failed, CiStatus::Success])
+        );
+    }
+}
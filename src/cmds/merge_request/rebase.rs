@@ -0,0 +1,461 @@
+//! Brings a merge request up to date with its target branch, either asking the
+//! remote to do it or rebasing the source branch locally and force pushing it.
+
+use std::{io::Write, sync::Arc};
+
+use crate::{
+    api_traits::MergeRequest,
+    error::GRError,
+    git,
+    io::{CmdInfo, ShellResponse, TaskRunner},
+    Result,
+};
+
+use super::{MergeRequestListBodyArgs, MergeRequestResponse, MergeRequestState};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RebaseMode {
+    /// Gitlab rebases the source branch, Github merges the target branch into
+    /// it.
+    #[default]
+    Remote,
+    Local,
+}
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct MergeRequestRebaseCliArgs {
+    /// Defaults to the open merge request of the current branch.
+    pub id: Option<i64>,
+    pub mode: RebaseMode,
+}
+
+impl MergeRequestRebaseCliArgs {
+    pub fn builder() -> MergeRequestRebaseCliArgsBuilder {
+        MergeRequestRebaseCliArgsBuilder::default()
+    }
+}
+
+fn current_branch(runner: Arc<impl TaskRunner<Response = ShellResponse>>) -> Result<String> {
+    match git::current_branch(runner)? {
+        CmdInfo::Branch(branch) => Ok(branch),
+        _ => Err(GRError::ApplicationError("Could not get the current branch".to_string()).into()),
+    }
+}
+
+/// Open merge request whose source branch is `branch`.
+fn branch_merge_request(
+    remote: &Arc<dyn MergeRequest>,
+    branch: &str,
+) -> Result<MergeRequestResponse> {
+    let args = MergeRequestListBodyArgs::builder()
+        .state(MergeRequestState::Opened)
+        .list_args(None)
+        .source_branch(Some(branch.to_string()))
+        .build()?;
+    match remote.list(args)?.into_iter().next() {
+        Some(merge_request) => Ok(merge_request),
+        None => Err(GRError::PreconditionNotMet(format!(
+            "No open merge request found for branch {}",
+            branch
+        ))
+        .into()),
+    }
+}
+
+pub fn rebase<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    runner: Arc<impl TaskRunner<Response = ShellResponse>>,
+    cli_args: MergeRequestRebaseCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    match cli_args.mode {
+        RebaseMode::Remote => {
+            let id = match cli_args.id {
+                Some(id) => id,
+                None => branch_merge_request(&remote, &current_branch(runner)?)?.id,
+            };
+            remote.rebase(id)?;
+            writeln!(
+                writer,
+                "Merge request {} is being updated with its target branch",
+                id
+            )?;
+            Ok(())
+        }
+        RebaseMode::Local => {
+            let branch = current_branch(runner.clone())?;
+            let merge_request = match cli_args.id {
+                Some(id) => remote.get(id)?,
+                None => branch_merge_request(&remote, &branch)?,
+            };
+            local_rebase(runner, &branch, &merge_request, &mut writer)
+        }
+    }
+}
+
+fn local_rebase<W: Write>(
+    runner: Arc<impl TaskRunner<Response = ShellResponse>>,
+    branch: &str,
+    merge_request: &MergeRequestResponse,
+    writer: &mut W,
+) -> Result<()> {
+    if branch != merge_request.source_branch {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Merge request {} is for branch {}, but the current branch is {}. \
+            Please check out {} first",
+            merge_request.id, merge_request.source_branch, branch, merge_request.source_branch
+        ))
+        .into());
+    }
+    // assume origin for now
+    git::fetch(runner.clone(), "origin".to_string())?;
+    let remote_branch = format!("origin/{}", branch);
+    let remote_tip = git::rev_parse(runner.as_ref(), &remote_branch)?;
+    // A rebase stopped on conflicts records the remote tip it started from.
+    // Once continued, the remote commits it rewrote are no longer in the
+    // local branch, which is fine as long as nobody pushed since.
+    let lease_key = format!("branch.{}.grlease", branch);
+    let recorded_tip = git::config_value(runner.as_ref(), &lease_key)?;
+    if recorded_tip.as_ref() != Some(&remote_tip) {
+        // Commits pushed to the source branch by someone else would be lost
+        // when force pushing the rebased branch.
+        let missing = git::count_commits(runner.as_ref(), branch, &remote_branch)?;
+        if missing > 0 {
+            return Err(GRError::PreconditionNotMet(format!(
+                "{} has {} commit(s) not in the local branch {}. Pull them before rebasing",
+                remote_branch, missing, branch
+            ))
+            .into());
+        }
+    }
+    let upstream = format!("origin/{}", merge_request.target_branch);
+    if let Err(err) = git::rebase(runner.as_ref(), &upstream) {
+        let conflicts = git::conflicted_files(runner.as_ref())?;
+        if conflicts.is_empty() {
+            return Err(err);
+        }
+        git::set_config(runner.as_ref(), &lease_key, &remote_tip)?;
+        return Err(GRError::PreconditionNotMet(format!(
+            "Rebasing {} onto {} stopped on conflicts in:\n{}\n\
+            Resolve them and run `git rebase --continue`, then `gr mr rebase --local` \
+            again to push. Run `git rebase --abort` to give up",
+            branch,
+            upstream,
+            conflicts
+                .iter()
+                .map(|file| format!("  {}", file))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ))
+        .into());
+    }
+    git::push_with_lease(runner.as_ref(), "origin", branch, &remote_tip)?;
+    if recorded_tip.is_some() {
+        git::unset_config(runner.as_ref(), &lease_key)?;
+    }
+    writeln!(
+        writer,
+        "Rebased {} onto {} and force pushed it",
+        branch, upstream
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{
+        api_traits::NumberDeltaErr,
+        cmds::merge_request::{
+            MergeRequestBodyArgs, MergeRequestDiff, MergeRequestMergeBodyArgs,
+            MergeRequestUpdateBodyArgs,
+        },
+        test::utils::MockRunner,
+    };
+
+    use super::*;
+
+    struct RebaseMock {
+        merge_request: MergeRequestResponse,
+        rebased: Mutex<Option<i64>>,
+    }
+
+    impl RebaseMock {
+        fn new(id: i64, source_branch: &str) -> Self {
+            RebaseMock {
+                merge_request: MergeRequestResponse::builder()
+                    .id(id)
+                    .source_branch(source_branch.to_string())
+                    .target_branch("main".to_string())
+                    .build()
+                    .unwrap(),
+                rebased: Mutex::new(None),
+            }
+        }
+    }
+
+    impl MergeRequest for RebaseMock {
+        fn open(&self, _args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
+            if args.source_branch == Some(self.merge_request.source_branch.clone()) {
+                return Ok(vec![self.merge_request.clone()]);
+            }
+            Ok(vec![])
+        }
+        fn merge(&self, _args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn get(&self, _id: i64) -> Result<MergeRequestResponse> {
+            Ok(self.merge_request.clone())
+        }
+        fn close(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn reopen(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn update(&self, _args: MergeRequestUpdateBodyArgs) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn approve(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn diff(&self, _id: i64) -> Result<Vec<MergeRequestDiff>> {
+            unimplemented!()
+        }
+        fn head_ref(&self, _id: i64) -> String {
+            unimplemented!()
+        }
+        fn rebase(&self, id: i64) -> Result<()> {
+            *self.rebased.lock().unwrap() = Some(id);
+            Ok(())
+        }
+        fn num_pages(&self, _args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
+            unimplemented!()
+        }
+        fn num_resources(&self, _args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            unimplemented!()
+        }
+    }
+
+    fn shell_response(status: i32, body: &str) -> ShellResponse {
+        ShellResponse::builder()
+            .status(status)
+            .body(body.to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_remote_rebase_merge_request_by_id() {
+        let remote = Arc::new(RebaseMock::new(42, "feature"));
+        let runner = Arc::new(MockRunner::new(vec![]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .id(Some(42))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        rebase(remote.clone(), runner, cli_args, &mut buf).unwrap();
+        assert_eq!(Some(42), *remote.rebased.lock().unwrap());
+        assert_eq!(
+            "Merge request 42 is being updated with its target branch\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_remote_rebase_merge_request_of_current_branch() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        let runner = Arc::new(MockRunner::new(vec![shell_response(0, "feature")]));
+        let cli_args = MergeRequestRebaseCliArgs::builder().build().unwrap();
+        rebase(remote.clone(), runner, cli_args, Vec::new()).unwrap();
+        assert_eq!(Some(7), *remote.rebased.lock().unwrap());
+    }
+
+    #[test]
+    fn test_remote_rebase_no_merge_request_for_current_branch_is_err() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        let runner = Arc::new(MockRunner::new(vec![shell_response(0, "docs")]));
+        let cli_args = MergeRequestRebaseCliArgs::builder().build().unwrap();
+        let err = rebase(remote, runner, cli_args, Vec::new()).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("No open merge request found for branch docs", msg)
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_local_rebase_force_pushes_current_branch() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        // push, rebase, commits missing locally, recorded remote tip, remote
+        // tip, fetch, current branch
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(0, ""),
+            shell_response(0, ""),
+            shell_response(0, "0"),
+            shell_response(0, "\n"),
+            shell_response(0, "3ec4f8d"),
+            shell_response(0, ""),
+            shell_response(0, "feature"),
+        ]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .mode(RebaseMode::Local)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        rebase(remote.clone(), runner.clone(), cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "git push --force-with-lease=feature:3ec4f8d origin feature",
+            *runner.cmd()
+        );
+        assert_eq!(None, *remote.rebased.lock().unwrap());
+        assert_eq!(
+            "Rebased feature onto origin/main and force pushed it\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_local_rebase_refuses_to_drop_remote_commits() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        // commits missing locally, recorded remote tip, remote tip, fetch,
+        // current branch
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(0, "2"),
+            shell_response(0, "\n"),
+            shell_response(0, "3ec4f8d"),
+            shell_response(0, ""),
+            shell_response(0, "feature"),
+        ]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .mode(RebaseMode::Local)
+            .build()
+            .unwrap();
+        let err = rebase(remote, runner.clone(), cli_args, Vec::new()).unwrap_err();
+        assert_eq!(
+            "git rev-list --count feature..origin/feature",
+            *runner.cmd()
+        );
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => assert_eq!(
+                "origin/feature has 2 commit(s) not in the local branch feature. Pull them before rebasing",
+                msg
+            ),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_local_rebase_reports_conflicts() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        // record remote tip, conflicted files, rebase, commits missing
+        // locally, recorded remote tip, remote tip, fetch, current branch
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(0, ""),
+            shell_response(0, "src/main.rs\nREADME.md\n"),
+            shell_response(1, "CONFLICT (content): Merge conflict in src/main.rs"),
+            shell_response(0, "0"),
+            shell_response(0, "\n"),
+            shell_response(0, "3ec4f8d"),
+            shell_response(0, ""),
+            shell_response(0, "feature"),
+        ]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .id(Some(7))
+            .mode(RebaseMode::Local)
+            .build()
+            .unwrap();
+        let err = rebase(remote, runner.clone(), cli_args, Vec::new()).unwrap_err();
+        assert_eq!("git config branch.feature.grlease 3ec4f8d", *runner.cmd());
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.starts_with(
+                    "Rebasing feature onto origin/main stopped on conflicts in:\n  src/main.rs\n  README.md\n"
+                ));
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_local_rebase_pushes_after_continuing_a_conflicted_rebase() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        // The rebase was continued, so the rewritten remote commits are not
+        // in the local branch, but nobody pushed since it started.
+        // forget remote tip, push, rebase, recorded remote tip, remote tip,
+        // fetch, current branch
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(0, ""),
+            shell_response(0, ""),
+            shell_response(0, "Current branch feature is up to date."),
+            shell_response(0, "3ec4f8d\n"),
+            shell_response(0, "3ec4f8d"),
+            shell_response(0, ""),
+            shell_response(0, "feature"),
+        ]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .mode(RebaseMode::Local)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        rebase(remote, runner.clone(), cli_args, &mut buf).unwrap();
+        assert_eq!(7, *runner.run_count.borrow());
+        assert_eq!("git config --unset branch.feature.grlease", *runner.cmd());
+        assert_eq!(
+            "Rebased feature onto origin/main and force pushed it\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_local_rebase_after_conflicts_refuses_commits_pushed_since() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        // commits missing locally, recorded remote tip, remote tip, fetch,
+        // current branch
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(0, "3"),
+            shell_response(0, "3ec4f8d\n"),
+            shell_response(0, "9fceb02"),
+            shell_response(0, ""),
+            shell_response(0, "feature"),
+        ]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .mode(RebaseMode::Local)
+            .build()
+            .unwrap();
+        let err = rebase(remote, runner.clone(), cli_args, Vec::new()).unwrap_err();
+        assert_eq!(
+            "git rev-list --count feature..origin/feature",
+            *runner.cmd()
+        );
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => assert!(msg.contains("Pull them")),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_local_rebase_requires_merge_request_branch() {
+        let remote = Arc::new(RebaseMock::new(7, "feature"));
+        let runner = Arc::new(MockRunner::new(vec![shell_response(0, "main")]));
+        let cli_args = MergeRequestRebaseCliArgs::builder()
+            .id(Some(7))
+            .mode(RebaseMode::Local)
+            .build()
+            .unwrap();
+        let err = rebase(remote, runner.clone(), cli_args, Vec::new()).unwrap_err();
+        assert_eq!(1, *runner.run_count.borrow());
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("Please check out feature first"))
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Line-anchored review comments submitted as a single review with a verdict.
+//!
+//! Comments are read from a simple text format, one comment per line, so they
+//! can be produced by linters:
+//!
+//! ```text
+//! src/main.rs:42: Prefer expect with a message over unwrap
+//! src/lib.rs:10:5: The column is optional and ignored
+//! src/old.rs:-7: A negative line comments on a removed line
+//! ```
+
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{BufRead, Write},
+    sync::Arc,
+};
+
+use regex::Regex;
+
+use crate::{api_traits::ReviewMergeRequest, error::GRError, Result};
+
+/// Side of the diff a comment is anchored to.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReviewSide {
+    /// Line in the target branch, i.e. a removed line.
+    Old,
+    /// Line in the source branch, i.e. an added or unchanged line.
+    #[default]
+    New,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReviewVerdict {
+    Approve,
+    RequestChanges,
+    #[default]
+    Comment,
+}
+
+impl Display for ReviewVerdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewVerdict::Approve => write!(f, "approve"),
+            ReviewVerdict::RequestChanges => write!(f, "request changes"),
+            ReviewVerdict::Comment => write!(f, "comment"),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct ReviewComment {
+    pub path: String,
+    pub line: i64,
+    #[builder(default)]
+    pub side: ReviewSide,
+    pub body: String,
+}
+
+impl ReviewComment {
+    pub fn builder() -> ReviewCommentBuilder {
+        ReviewCommentBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct MergeRequestReviewBodyArgs {
+    pub id: i64,
+    #[builder(default)]
+    pub comments: Vec<ReviewComment>,
+    #[builder(default)]
+    pub verdict: ReviewVerdict,
+    /// Summary of the review.
+    #[builder(default)]
+    pub body: Option<String>,
+}
+
+impl MergeRequestReviewBodyArgs {
+    pub fn builder() -> MergeRequestReviewBodyArgsBuilder {
+        MergeRequestReviewBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct MergeRequestReviewCliArgs {
+    pub id: i64,
+    /// File with the review comments. "-" reads from STDIN.
+    #[builder(default)]
+    pub comments_from_file: Option<String>,
+    #[builder(default)]
+    pub verdict: ReviewVerdict,
+    #[builder(default)]
+    pub body: Option<String>,
+}
+
+impl MergeRequestReviewCliArgs {
+    pub fn builder() -> MergeRequestReviewCliArgsBuilder {
+        MergeRequestReviewCliArgsBuilder::default()
+    }
+}
+
+/// Parse review comments in the `path:line: message` format. Empty lines and
+/// lines starting with `#` are skipped.
+pub fn parse_review_comments<R: BufRead>(reader: R) -> Result<Vec<ReviewComment>> {
+    let comment_regex = Regex::new(r"^([^:]+):(-?\d+)(?::\d+)?:\s*(.+)$").unwrap();
+    let mut comments = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let captures = comment_regex.captures(line).ok_or_else(|| {
+            GRError::PreconditionNotMet(format!(
+                "Invalid review comment at line {}, expected path:line: message but got: {}",
+                index + 1,
+                line
+            ))
+        })?;
+        let number = captures[2].parse::<i64>()?;
+        let side = if number < 0 {
+            ReviewSide::Old
+        } else {
+            ReviewSide::New
+        };
+        comments.push(
+            ReviewComment::builder()
+                .path(captures[1].to_string())
+                .line(number.abs())
+                .side(side)
+                .body(captures[3].to_string())
+                .build()
+                .unwrap(),
+        );
+    }
+    Ok(comments)
+}
+
+pub fn review<W: Write>(
+    remote: Arc<dyn ReviewMergeRequest>,
+    cli_args: MergeRequestReviewCliArgs,
+    comments: Vec<ReviewComment>,
+    mut writer: W,
+) -> Result<()> {
+    if comments.is_empty() && cli_args.body.is_none() && cli_args.verdict != ReviewVerdict::Approve
+    {
+        return Err(GRError::PreconditionNotMet(
+            "Nothing to review. Provide review comments, a body or approve the merge request"
+                .to_string(),
+        )
+        .into());
+    }
+    let num_comments = comments.len();
+    let args = MergeRequestReviewBodyArgs::builder()
+        .id(cli_args.id)
+        .comments(comments)
+        .verdict(cli_args.verdict)
+        .body(cli_args.body)
+        .build()?;
+    remote.review(args)?;
+    writeln!(
+        writer,
+        "Review submitted on merge request {} with {} comment{}: {}",
+        cli_args.id,
+        num_comments,
+        if num_comments == 1 { "" } else { "s" },
+        cli_args.verdict
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, sync::Mutex};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_review_comments() {
+        let input = "# clippy findings
+src/main.rs:42: Prefer expect over unwrap
+
+src/lib.rs:10:5: Unused import: std::fs
+src/old.rs:-7: Why was this removed?
+";
+        let comments = parse_review_comments(Cursor::new(input)).unwrap();
+        assert_eq!(3, comments.len());
+        assert_eq!("src/main.rs", comments[0].path);
+        assert_eq!(42, comments[0].line);
+        assert_eq!(ReviewSide::New, comments[0].side);
+        assert_eq!("Prefer expect over unwrap", comments[0].body);
+        assert_eq!(10, comments[1].line);
+        assert_eq!("Unused import: std::fs", comments[1].body);
+        assert_eq!(7, comments[2].line);
+        assert_eq!(ReviewSide::Old, comments[2].side);
+    }
+
+    #[test]
+    fn test_parse_review_comments_invalid_line_is_error() {
+        let input = "src/main.rs:42: Fine\nnot a comment\n";
+        let err = parse_review_comments(Cursor::new(input)).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => assert!(msg.contains("line 2")),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[derive(Default)]
+    struct ReviewMock {
+        args: Mutex<Option<MergeRequestReviewBodyArgs>>,
+    }
+
+    impl ReviewMergeRequest for ReviewMock {
+        fn review(&self, args: MergeRequestReviewBodyArgs) -> Result<()> {
+            *self.args.lock().unwrap() = Some(args);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_review_submits_comments_with_verdict() {
+        let remote = Arc::new(ReviewMock::default());
+        let cli_args = MergeRequestReviewCliArgs::builder()
+            .id(12)
+            .verdict(ReviewVerdict::RequestChanges)
+            .build()
+            .unwrap();
+        let comments = parse_review_comments(Cursor::new("src/main.rs:3: Typo")).unwrap();
+        let mut writer = Vec::new();
+        review(remote.clone(), cli_args, comments, &mut writer).unwrap();
+        assert_eq!(
+            "Review submitted on merge request 12 with 1 comment: request changes\n",
+            String::from_utf8(writer).unwrap()
+        );
+        let args = remote.args.lock().unwrap().clone().unwrap();
+        assert_eq!(ReviewVerdict::RequestChanges, args.verdict);
+        assert_eq!("Typo", args.comments[0].body);
+    }
+
+    #[test]
+    fn test_review_with_nothing_to_submit_is_error() {
+        let remote = Arc::new(ReviewMock::default());
+        let cli_args = MergeRequestReviewCliArgs::builder().id(12).build().unwrap();
+        assert!(review(remote.clone(), cli_args, vec![], Vec::new()).is_err());
+        assert!(remote.args.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,343 @@
+//! Reviewer selection policies for new merge requests. A policy is configured
+//! per domain or project in the merge requests configuration section and is
+//! applied when no reviewers are given in the command line.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{api_traits::MergeRequest, cmds::project::Member, error::GRError, Result};
+
+use super::{MergeRequestListBodyArgs, MergeRequestState};
+
+/// Locations where a CODEOWNERS file is looked up, in order of precedence.
+pub const CODEOWNERS_FILES: [&str; 4] = [
+    ".github/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+];
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReviewerPolicy {
+    Random,
+    RoundRobin,
+    LeastLoaded,
+    Codeowners,
+}
+
+/// Pick `count` distinct members at random.
+pub fn random(members: &[Member], count: usize) -> Vec<Member> {
+    let mut candidates = members.to_vec();
+    let mut reviewers = Vec::new();
+    while reviewers.len() < count && !candidates.is_empty() {
+        let index = rand::random::<usize>() % candidates.len();
+        reviewers.push(candidates.remove(index));
+    }
+    reviewers
+}
+
+/// Position in the round-robin rotation after picking reviewers.
+#[derive(Debug)]
+pub struct Rotation {
+    state_file: PathBuf,
+    next: usize,
+}
+
+impl Rotation {
+    /// Persists the position in the rotation. Done once the merge request has
+    /// been opened, so an aborted or failed one does not skip reviewers.
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.state_file, self.next.to_string())?;
+        Ok(())
+    }
+}
+
+/// Pick the next `count` members in the rotation. The position in the rotation
+/// is read from `state_file` and the returned `Rotation` persists the new one,
+/// so consecutive merge requests go to different reviewers.
+pub fn round_robin(members: &[Member], count: usize, state_file: &Path) -> (Vec<Member>, Rotation) {
+    // A missing or corrupted state file starts the rotation over.
+    let next = fs::read_to_string(state_file)
+        .ok()
+        .and_then(|index| index.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    let count = count.min(members.len());
+    let reviewers = (0..count)
+        .map(|offset| members[(next + offset) % members.len()].clone())
+        .collect();
+    let rotation = Rotation {
+        state_file: state_file.to_path_buf(),
+        next: (next + count).checked_rem(members.len()).unwrap_or(0),
+    };
+    (reviewers, rotation)
+}
+
+/// Number of open merge requests awaiting the review of a member.
+pub fn review_load(remote: &Arc<dyn MergeRequest>, member: &Member) -> Result<usize> {
+    let args = MergeRequestListBodyArgs::builder()
+        .state(MergeRequestState::Opened)
+        .list_args(None)
+        .reviewer(Some(member.clone()))
+        .build()?;
+    Ok(remote.list(args)?.len())
+}
+
+/// Pick the `count` members with the fewest open merge requests awaiting
+/// their review, as given by `load`. Ties are resolved by the order in the
+/// configuration. The author of the merge request is never picked.
+pub fn least_loaded(
+    members: &[Member],
+    count: usize,
+    author: &str,
+    load: impl Fn(&Member) -> Result<usize>,
+) -> Result<Vec<Member>> {
+    let mut loads = Vec::new();
+    for member in members.iter().filter(|member| member.username != author) {
+        loads.push((load(member)?, member));
+    }
+    // Stable sort keeps the configuration order for the same load.
+    loads.sort_by_key(|(load, _)| *load);
+    Ok(loads
+        .into_iter()
+        .take(count)
+        .map(|(_, member)| member.clone())
+        .collect())
+}
+
+/// Usernames owning the given paths according to a CODEOWNERS file. Only user
+/// owners (`@username`) are considered, teams and emails cannot be requested
+/// as reviewers.
+pub fn codeowners(content: &str, paths: &[String]) -> Result<Vec<String>> {
+    let rules = parse_codeowners(content)?;
+    let mut owners = Vec::new();
+    for path in paths {
+        // The last matching pattern takes precedence.
+        if let Some((_, path_owners)) = rules.iter().rev().find(|(regex, _)| regex.is_match(path)) {
+            for owner in path_owners {
+                if !owners.iter().any(|known| known == owner) {
+                    owners.push(owner.to_string());
+                }
+            }
+        }
+    }
+    Ok(owners)
+}
+
+fn parse_codeowners(content: &str) -> Result<Vec<(Regex, Vec<&str>)>> {
+    let mut rules = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        // Skip comments and Gitlab's section headers, ex. [Documentation]
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let pattern = fields.next().unwrap();
+        let owners = fields
+            .filter_map(|owner| owner.strip_prefix('@'))
+            .filter(|owner| !owner.contains('/'))
+            .collect();
+        rules.push((
+            Regex::new(&pattern_to_regex(pattern)).map_err(|err| {
+                GRError::PreconditionNotMet(format!(
+                    "Invalid CODEOWNERS pattern {}: {}",
+                    pattern, err
+                ))
+            })?,
+            owners,
+        ));
+    }
+    Ok(rules)
+}
+
+// CODEOWNERS patterns follow the gitignore rules. Patterns with a leading or
+// middle slash are relative to the root of the repository, otherwise they match
+// at any depth. A pattern matching a directory matches everything under it,
+// except for a trailing `/*` that only matches the files directly in it.
+fn pattern_to_regex(pattern: &str) -> String {
+    let shallow = pattern.ends_with("/*");
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let trimmed = trimmed.trim_start_matches('/');
+    let mut regex = String::from("^");
+    if !anchored {
+        regex.push_str("(?:.*/)?");
+    }
+    let mut chars = trimmed.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    if !shallow {
+        regex.push_str("(?:/.*)?");
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::cmds::project::MrMemberType;
+
+    fn member(username: &str) -> Member {
+        Member::builder()
+            .username(username.to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .build()
+            .unwrap()
+    }
+
+    fn usernames(members: &[Member]) -> Vec<&str> {
+        members
+            .iter()
+            .map(|member| member.username.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_random_picks_distinct_members() {
+        let members = vec![member("tom"), member("huck"), member("jim")];
+        let reviewers = random(&members, 2);
+        assert_eq!(2, reviewers.len());
+        assert_ne!(reviewers[0].username, reviewers[1].username);
+        assert_eq!(3, random(&members, 5).len());
+    }
+
+    #[test]
+    fn test_round_robin_rotates_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("reviewers");
+        let members = vec![member("tom"), member("huck"), member("jim")];
+        let (reviewers, rotation) = round_robin(&members, 2, &state_file);
+        assert_eq!(vec!["tom", "huck"], usernames(&reviewers));
+        rotation.save().unwrap();
+        let (reviewers, rotation) = round_robin(&members, 2, &state_file);
+        assert_eq!(vec!["jim", "tom"], usernames(&reviewers));
+        rotation.save().unwrap();
+        let (reviewers, _) = round_robin(&members, 1, &state_file);
+        assert_eq!(vec!["huck"], usernames(&reviewers));
+    }
+
+    #[test]
+    fn test_round_robin_unsaved_rotation_does_not_advance() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("reviewers");
+        let members = vec![member("tom"), member("huck")];
+        let (reviewers, _) = round_robin(&members, 1, &state_file);
+        assert_eq!(vec!["tom"], usernames(&reviewers));
+        assert!(!state_file.exists());
+        let (reviewers, _) = round_robin(&members, 1, &state_file);
+        assert_eq!(vec!["tom"], usernames(&reviewers));
+    }
+
+    #[test]
+    fn test_round_robin_no_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("reviewers");
+        let (reviewers, rotation) = round_robin(&[], 2, &state_file);
+        assert!(reviewers.is_empty());
+        rotation.save().unwrap();
+    }
+
+    #[test]
+    fn test_round_robin_corrupted_state_starts_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("reviewers");
+        fs::write(&state_file, "garbage").unwrap();
+        let members = vec![member("tom"), member("huck")];
+        let (reviewers, _) = round_robin(&members, 1, &state_file);
+        assert_eq!(vec!["tom"], usernames(&reviewers));
+    }
+
+    #[test]
+    fn test_least_loaded() {
+        let members = vec![member("tom"), member("huck"), member("jim")];
+        let load = |member: &Member| match member.username.as_str() {
+            "tom" => Ok(3),
+            _ => Ok(1),
+        };
+        let reviewers = least_loaded(&members, 2, "becky", load).unwrap();
+        assert_eq!(vec!["huck", "jim"], usernames(&reviewers));
+    }
+
+    #[test]
+    fn test_least_loaded_skips_author() {
+        let members = vec![member("tom"), member("huck"), member("jim")];
+        let load = |member: &Member| match member.username.as_str() {
+            "huck" => Ok(0),
+            "tom" => Ok(3),
+            _ => Ok(1),
+        };
+        let reviewers = least_loaded(&members, 2, "huck", load).unwrap();
+        assert_eq!(vec!["jim", "tom"], usernames(&reviewers));
+    }
+
+    const CODEOWNERS: &str = "
+# Default owners
+*           @tom
+*.rs        @huck @rust-team/reviewers
+/docs/      @jim becky@example.com
+
+[Frontend]
+src/web/**/*.js @becky
+";
+
+    #[test]
+    fn test_codeowners() {
+        let paths = vec![
+            "src/main.rs".to_string(),
+            "docs/README.md".to_string(),
+            "src/web/app/index.js".to_string(),
+            "src/lib.rs".to_string(),
+        ];
+        let owners = codeowners(CODEOWNERS, &paths).unwrap();
+        assert_eq!(vec!["huck", "jim", "becky"], owners);
+    }
+
+    #[test]
+    fn test_codeowners_last_match_wins() {
+        let owners = codeowners(CODEOWNERS, &["Makefile".to_string()]).unwrap();
+        assert_eq!(vec!["tom"], owners);
+        // /docs/ is anchored to the root of the repository.
+        let owners = codeowners(CODEOWNERS, &["src/docs/main.c".to_string()]).unwrap();
+        assert_eq!(vec!["tom"], owners);
+    }
+
+    #[test]
+    fn test_pattern_to_regex() {
+        let matches = |pattern: &str, path: &str| {
+            Regex::new(&pattern_to_regex(pattern))
+                .unwrap()
+                .is_match(path)
+        };
+        assert!(matches("*.md", "docs/guide/intro.md"));
+        assert!(matches("build/", "src/build/output.o"));
+        assert!(!matches("/build/", "src/build/output.o"));
+        assert!(matches("docs/*", "docs/intro.md"));
+        assert!(!matches("docs/*", "docs/guide/intro.md"));
+        assert!(!matches("docs/*.md", "docs/guide/intro.md"));
+        assert!(matches("docs/**/intro.md", "docs/guide/intro.md"));
+        assert!(matches("apps/github", "apps/github/file.rs"));
+    }
+}
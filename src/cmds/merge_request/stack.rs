@@ -0,0 +1,664 @@
+//! Stacked merge requests. A stack is a chain of local branches, each one built
+//! on top of the previous one. Every branch gets its own merge request
+//! targeting the branch below it, so a large change can be reviewed in small
+//! pieces and merged from the bottom up.
+
+use std::{io::Write, sync::Arc};
+
+use crate::{
+    api_traits::MergeRequest,
+    error::GRError,
+    git::{self, Repo},
+    io::{CmdInfo, ShellResponse, TaskRunner},
+    Result,
+};
+
+use super::{
+    in_feature_branch, MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestResponse,
+    MergeRequestState, MergeRequestUpdateBodyArgs,
+};
+
+const STACK_TABLE_START: &str = "<!-- gr stack -->";
+const STACK_TABLE_END: &str = "<!-- gr stack end -->";
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct MergeRequestStackCliArgs {
+    /// Branch the bottom of the stack targets. Defaults to the default branch
+    /// of the project.
+    pub base: Option<String>,
+    pub draft: bool,
+    pub force: bool,
+    pub dry_run: bool,
+}
+
+impl MergeRequestStackCliArgs {
+    pub fn builder() -> MergeRequestStackCliArgsBuilder {
+        MergeRequestStackCliArgsBuilder::default()
+    }
+}
+
+/// A local branch of the stack along with its latest merge request, if any.
+#[derive(Clone, Debug)]
+pub struct StackBranch {
+    pub name: String,
+    /// SHA of the branch before restacking.
+    pub tip: String,
+    pub merge_request: Option<MergeRequestResponse>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StackEntry {
+    pub branch: String,
+    /// Closest branch below that has not been merged yet, or the base.
+    pub target_branch: String,
+    /// Commit the branch was built on, used as upstream when rebasing.
+    pub upstream: String,
+    /// Open merge request of the branch, if any.
+    pub merge_request: Option<MergeRequestResponse>,
+    /// A branch below has been merged, so this branch needs to be rebased.
+    pub restack: bool,
+}
+
+/// Branches of the stack the current branch belongs to, from the bottom,
+/// closest to the base, to the top. Branches already merged in the base are
+/// not part of the stack.
+pub fn detect(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    base_ref: &str,
+    current_branch: &str,
+) -> Result<Vec<String>> {
+    let merged_in_base = git::merged_branches(runner, base_ref)?;
+    let mut branches = git::merged_branches(runner, current_branch)?;
+    for branch in git::containing_branches(runner, current_branch)? {
+        if !branches.contains(&branch) {
+            branches.push(branch);
+        }
+    }
+    branches.retain(|branch| !merged_in_base.contains(branch));
+    let mut stack = Vec::new();
+    for branch in branches {
+        let ahead = git::count_commits(runner, base_ref, &branch)?;
+        stack.push((ahead, branch));
+    }
+    stack.sort();
+    for pair in stack.windows(2) {
+        let (lower, upper) = (&pair[0].1, &pair[1].1);
+        if git::count_commits(runner, upper, lower)? > 0 {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Branches {} and {} diverge. A stack must be a single chain of branches",
+                lower, upper
+            ))
+            .into());
+        }
+    }
+    Ok(stack.into_iter().map(|(_, branch)| branch).collect())
+}
+
+/// Works out the target of each branch of the stack. Branches whose merge
+/// request has been merged are left out, and the ones above get retargeted to
+/// the closest branch below still open and flagged to be rebased. Returns the
+/// stack entries and the merged branches.
+pub fn plan(
+    base: &str,
+    base_ref: &str,
+    branches: Vec<StackBranch>,
+) -> (Vec<StackEntry>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut merged = Vec::new();
+    let mut target_branch = base.to_string();
+    let mut upstream = base_ref.to_string();
+    let mut restack = false;
+    for branch in branches {
+        let merge_request = branch.merge_request;
+        if merge_request
+            .as_ref()
+            .is_some_and(|mr| mr.state == MergeRequestState::Merged.to_string())
+        {
+            merged.push(branch.name);
+            upstream = branch.tip;
+            restack = true;
+            continue;
+        }
+        entries.push(StackEntry {
+            branch: branch.name.clone(),
+            target_branch: target_branch.clone(),
+            upstream,
+            merge_request: merge_request
+                .filter(|mr| mr.state == MergeRequestState::Opened.to_string()),
+            restack,
+        });
+        target_branch = branch.name;
+        upstream = branch.tip;
+    }
+    (entries, merged)
+}
+
+/// Navigation table added to the description of every merge request in the
+/// stack. `current` is the position of the merge request being described.
+pub fn stack_table(entries: &[StackEntry], current: usize) -> String {
+    let mut table = format!(
+        "{}\n**Stack**\n\n| | Branch | Merge request |\n|---|---|---|\n",
+        STACK_TABLE_START
+    );
+    for (index, entry) in entries.iter().enumerate() {
+        let marker = if index == current { "👉" } else { "" };
+        let web_url = entry
+            .merge_request
+            .as_ref()
+            .map_or("", |mr| mr.web_url.as_str());
+        table.push_str(&format!(
+            "| {} | {} | {} |\n",
+            marker, entry.branch, web_url
+        ));
+    }
+    table.push_str(STACK_TABLE_END);
+    table
+}
+
+/// Replaces the navigation table in a description, or appends it if there is
+/// none yet. Anything else in the description is left as is.
+pub fn replace_stack_table(description: &str, table: &str) -> String {
+    if let Some(start) = description.find(STACK_TABLE_START) {
+        if let Some(end) = description[start..].find(STACK_TABLE_END) {
+            let end = start + end + STACK_TABLE_END.len();
+            return format!("{}{}{}", &description[..start], table, &description[end..]);
+        }
+    }
+    if description.trim().is_empty() {
+        return table.to_string();
+    }
+    format!("{}\n\n{}", description.trim_end(), table)
+}
+
+fn latest_merge_request(
+    remote: &Arc<dyn MergeRequest>,
+    branch: &str,
+) -> Result<Option<MergeRequestResponse>> {
+    let args = MergeRequestListBodyArgs::builder()
+        .state(MergeRequestState::All)
+        .list_args(None)
+        .source_branch(Some(branch.to_string()))
+        .build()?;
+    Ok(remote
+        .list(args)?
+        .into_iter()
+        .max_by_key(|merge_request| merge_request.id))
+}
+
+/// Opens or updates one merge request per branch of the stack the current
+/// branch belongs to. Branches above a merged one are rebased and retargeted.
+pub fn stack<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    runner: Arc<impl TaskRunner<Response = ShellResponse>>,
+    base: &str,
+    cli_args: MergeRequestStackCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    git::fetch(runner.clone(), "origin".to_string())?;
+    let current_branch = match git::current_branch(runner.clone())? {
+        CmdInfo::Branch(branch) => branch,
+        _ => {
+            return Err(
+                GRError::ApplicationError("Could not get the current branch".to_string()).into(),
+            )
+        }
+    };
+    in_feature_branch(&current_branch, base)?;
+    let base_ref = format!("origin/{}", base);
+    let mut branches = Vec::new();
+    for name in detect(runner.as_ref(), &base_ref, &current_branch)? {
+        in_feature_branch(&name, base)?;
+        let tip = git::rev_parse(runner.as_ref(), &name)?;
+        let merge_request = latest_merge_request(&remote, &name)?;
+        branches.push(StackBranch {
+            name,
+            tip,
+            merge_request,
+        });
+    }
+    let (mut entries, merged) = plan(base, &base_ref, branches);
+    for branch in &merged {
+        writeln!(writer, "{} has been merged, skipping", branch)?;
+    }
+    if entries.is_empty() {
+        writeln!(writer, "All the branches of the stack have been merged")?;
+        return Ok(());
+    }
+    if cli_args.dry_run {
+        for entry in &entries {
+            let action = if entry.restack { "rebase, " } else { "" };
+            let merge_request = entry
+                .merge_request
+                .as_ref()
+                .map_or("open merge request".to_string(), |mr| {
+                    format!("update {}", mr.web_url)
+                });
+            writeln!(
+                writer,
+                "{} -> {}: {}{}",
+                entry.branch, entry.target_branch, action, merge_request
+            )?;
+        }
+        return Ok(());
+    }
+
+    if entries.iter().any(|entry| entry.restack) {
+        for entry in entries.iter().filter(|entry| entry.restack) {
+            let new_base = if entry.target_branch == base {
+                &base_ref
+            } else {
+                &entry.target_branch
+            };
+            git::rebase_onto(runner.as_ref(), new_base, &entry.upstream, &entry.branch)?;
+        }
+        git::switch(runner.as_ref(), &current_branch)?;
+    }
+
+    for entry in entries.iter_mut() {
+        if cli_args.force || entry.restack {
+            // Only overwrite what was fetched, so commits pushed by someone
+            // else are not lost. An empty lease expects the branch not to be
+            // on the remote yet.
+            let remote_tip = git::remote_branch_tip(runner.as_ref(), "origin", &entry.branch)?;
+            git::push_with_lease(
+                runner.as_ref(),
+                "origin",
+                &entry.branch,
+                &remote_tip.unwrap_or_default(),
+            )?;
+        } else {
+            let mut repo = Repo::new();
+            repo.with_current_branch(&entry.branch);
+            git::push(runner.as_ref(), "origin", &repo, false)?;
+        }
+        if entry.merge_request.is_none() {
+            let title = match git::commit_summary(runner.clone(), &Some(entry.branch.clone()))? {
+                CmdInfo::CommitSummary(title) => title,
+                _ => entry.branch.clone(),
+            };
+            let description =
+                match git::commit_message(runner.clone(), &Some(entry.branch.clone()))? {
+                    CmdInfo::CommitMessage(message) => message,
+                    _ => "".to_string(),
+                };
+            let title = if cli_args.draft {
+                format!("DRAFT: {}", title)
+            } else {
+                title
+            };
+            let args = MergeRequestBodyArgs::builder()
+                .title(title)
+                .description(description.clone())
+                .source_branch(entry.branch.clone())
+                .target_branch(entry.target_branch.clone())
+                .draft(cli_args.draft)
+                .build()?;
+            let mut merge_request = remote.open(args)?;
+            merge_request.description = description;
+            entry.merge_request = Some(merge_request);
+        }
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let merge_request = entry.merge_request.as_ref().unwrap();
+        let description =
+            replace_stack_table(&merge_request.description, &stack_table(&entries, index));
+        remote.update(
+            MergeRequestUpdateBodyArgs::builder()
+                .id(merge_request.id)
+                .description(Some(description))
+                .target_branch(Some(entry.target_branch.clone()))
+                .build()?,
+        )?;
+        writeln!(
+            writer,
+            "{} -> {}: {}",
+            entry.branch, entry.target_branch, merge_request.web_url
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{
+        api_traits::NumberDeltaErr,
+        cmds::merge_request::{MergeRequestDiff, MergeRequestMergeBodyArgs},
+        test::utils::MockRunner,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct StackMock {
+        merge_requests: Vec<MergeRequestResponse>,
+        opened: Mutex<Vec<(String, String)>>,
+        updated: Mutex<Vec<MergeRequestUpdateBodyArgs>>,
+    }
+
+    impl MergeRequest for StackMock {
+        fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
+            self.opened
+                .lock()
+                .unwrap()
+                .push((args.source_branch.clone(), args.target_branch.clone()));
+            Ok(MergeRequestResponse::builder()
+                .id(10)
+                .source_branch(args.source_branch)
+                .web_url("https://gitlab.com/owner/repo/-/merge_requests/10".to_string())
+                .build()
+                .unwrap())
+        }
+        fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
+            Ok(self
+                .merge_requests
+                .iter()
+                .filter(|mr| args.source_branch == Some(mr.source_branch.clone()))
+                .cloned()
+                .collect())
+        }
+        fn merge(&self, _args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn get(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn close(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn reopen(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn update(&self, args: MergeRequestUpdateBodyArgs) -> Result<MergeRequestResponse> {
+            self.updated.lock().unwrap().push(args);
+            Ok(MergeRequestResponse::default())
+        }
+        fn approve(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn diff(&self, _id: i64) -> Result<Vec<MergeRequestDiff>> {
+            unimplemented!()
+        }
+        fn head_ref(&self, _id: i64) -> String {
+            unimplemented!()
+        }
+        fn rebase(&self, _id: i64) -> Result<()> {
+            unimplemented!()
+        }
+        fn num_pages(&self, _args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
+            unimplemented!()
+        }
+        fn num_resources(&self, _args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            unimplemented!()
+        }
+    }
+
+    fn branch_merge_request(id: i64, branch: &str, state: &str) -> MergeRequestResponse {
+        let mut merge_request = merge_request(id, state);
+        merge_request.source_branch = branch.to_string();
+        merge_request
+    }
+
+    fn shell_response(body: &str) -> ShellResponse {
+        ShellResponse::builder()
+            .body(body.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn merge_request(id: i64, state: &str) -> MergeRequestResponse {
+        MergeRequestResponse::builder()
+            .id(id)
+            .state(state.to_string())
+            .web_url(format!(
+                "https://gitlab.com/owner/repo/-/merge_requests/{}",
+                id
+            ))
+            .build()
+            .unwrap()
+    }
+
+    fn stack_branch(name: &str, merge_request: Option<MergeRequestResponse>) -> StackBranch {
+        StackBranch {
+            name: name.to_string(),
+            tip: format!("{}-sha", name),
+            merge_request,
+        }
+    }
+
+    #[test]
+    fn test_detect_stack_orders_branches_from_the_base() {
+        // Responses are popped from the end.
+        let runner = MockRunner::new(vec![
+            shell_response("0"),
+            shell_response("0"),
+            shell_response("3"),
+            shell_response("2"),
+            shell_response("1"),
+            shell_response("feature-2\nfeature-3"),
+            shell_response("feature-1\nfeature-2\nmain"),
+            shell_response("main"),
+        ]);
+        let stack = detect(&runner, "origin/main", "feature-2").unwrap();
+        assert_eq!(vec!["feature-1", "feature-2", "feature-3"], stack);
+        assert_eq!("git rev-list --count feature-3..feature-2", *runner.cmd());
+    }
+
+    #[test]
+    fn test_detect_stack_diverging_branches_is_err() {
+        let runner = MockRunner::new(vec![
+            shell_response("1"),
+            shell_response("2"),
+            shell_response("1"),
+            shell_response("feature-2\nfeature-3"),
+            shell_response("feature-2"),
+            shell_response("main"),
+        ]);
+        let err = detect(&runner, "origin/main", "feature-2").unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("feature-2 and feature-3 diverge"))
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_plan_new_stack_targets_previous_branch() {
+        let (entries, merged) = plan(
+            "main",
+            "origin/main",
+            vec![
+                stack_branch("feature-1", Some(merge_request(1, "opened"))),
+                stack_branch("feature-2", None),
+            ],
+        );
+        assert!(merged.is_empty());
+        assert_eq!("main", entries[0].target_branch);
+        assert_eq!("origin/main", entries[0].upstream);
+        assert_eq!(1, entries[0].merge_request.as_ref().unwrap().id);
+        assert_eq!("feature-1", entries[1].target_branch);
+        assert_eq!("feature-1-sha", entries[1].upstream);
+        assert!(entries[1].merge_request.is_none());
+        assert!(!entries.iter().any(|entry| entry.restack));
+    }
+
+    #[test]
+    fn test_plan_merged_parent_retargets_and_restacks_children() {
+        let (entries, merged) = plan(
+            "main",
+            "origin/main",
+            vec![
+                stack_branch("feature-1", Some(merge_request(1, "merged"))),
+                stack_branch("feature-2", Some(merge_request(2, "opened"))),
+                stack_branch("feature-3", Some(merge_request(3, "closed"))),
+            ],
+        );
+        assert_eq!(vec!["feature-1"], merged);
+        assert_eq!(2, entries.len());
+        assert_eq!("main", entries[0].target_branch);
+        assert_eq!("feature-1-sha", entries[0].upstream);
+        assert!(entries[0].restack);
+        assert_eq!("feature-2", entries[1].target_branch);
+        assert!(entries[1].restack);
+        // A closed merge request gets a new one opened.
+        assert!(entries[1].merge_request.is_none());
+    }
+
+    #[test]
+    fn test_stack_table_marks_current_merge_request() {
+        let (entries, _) = plan(
+            "main",
+            "origin/main",
+            vec![
+                stack_branch("feature-1", Some(merge_request(1, "opened"))),
+                stack_branch("feature-2", Some(merge_request(2, "opened"))),
+            ],
+        );
+        assert_eq!(
+            "<!-- gr stack -->\n**Stack**\n\n| | Branch | Merge request |\n|---|---|---|\n\
+             |  | feature-1 | https://gitlab.com/owner/repo/-/merge_requests/1 |\n\
+             | 👉 | feature-2 | https://gitlab.com/owner/repo/-/merge_requests/2 |\n\
+             <!-- gr stack end -->",
+            stack_table(&entries, 1)
+        );
+    }
+
+    #[test]
+    fn test_stack_opens_merge_request_for_new_branch() {
+        let remote = Arc::new(StackMock::default());
+        // Responses are popped from the end: commit message, commit summary,
+        // push, tip, commits ahead of the base, containing branches, merged
+        // in the current branch, merged in the base, current branch, fetch.
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response("Longer description"),
+            shell_response("Add feature"),
+            shell_response(""),
+            shell_response("f1sha"),
+            shell_response("1"),
+            shell_response("feature-1"),
+            shell_response("feature-1\nmain"),
+            shell_response("main"),
+            shell_response("feature-1"),
+            shell_response(""),
+        ]));
+        let cli_args = MergeRequestStackCliArgs::builder().build().unwrap();
+        let mut buf = Vec::new();
+        stack(remote.clone(), runner.clone(), "main", cli_args, &mut buf).unwrap();
+        assert!(runner
+            .cmds()
+            .contains(&"git push origin feature-1".to_string()));
+        assert_eq!(
+            vec![("feature-1".to_string(), "main".to_string())],
+            *remote.opened.lock().unwrap()
+        );
+        let updated = remote.updated.lock().unwrap();
+        assert_eq!(10, updated[0].id);
+        let description = updated[0].description.as_ref().unwrap();
+        assert!(description.starts_with("Longer description\n\n<!-- gr stack -->"));
+        assert_eq!(
+            "feature-1 -> main: https://gitlab.com/owner/repo/-/merge_requests/10\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stack_merged_parent_restacks_pushes_with_lease_and_retargets() {
+        let remote = Arc::new(StackMock {
+            merge_requests: vec![
+                branch_merge_request(1, "feature-1", "merged"),
+                branch_merge_request(2, "feature-2", "opened"),
+            ],
+            ..Default::default()
+        });
+        // push, remote tip, switch back, rebase, tips, stack order check,
+        // commits ahead of the base, containing branches, merged in the
+        // current branch, merged in the base, current branch, fetch.
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(""),
+            shell_response("f2remote\n"),
+            shell_response(""),
+            shell_response(""),
+            shell_response("f2sha"),
+            shell_response("f1sha"),
+            shell_response("0"),
+            shell_response("2"),
+            shell_response("1"),
+            shell_response("feature-2"),
+            shell_response("feature-1\nfeature-2\nmain"),
+            shell_response("main"),
+            shell_response("feature-2"),
+            shell_response(""),
+        ]));
+        let cli_args = MergeRequestStackCliArgs::builder().build().unwrap();
+        let mut buf = Vec::new();
+        stack(remote.clone(), runner.clone(), "main", cli_args, &mut buf).unwrap();
+        let cmds = runner.cmds();
+        assert!(cmds.contains(&"git rebase --onto origin/main f1sha feature-2".to_string()));
+        assert_eq!(
+            "git push --force-with-lease=feature-2:f2remote origin feature-2",
+            *runner.cmd()
+        );
+        assert!(remote.opened.lock().unwrap().is_empty());
+        let updated = remote.updated.lock().unwrap();
+        assert_eq!(1, updated.len());
+        assert_eq!(2, updated[0].id);
+        assert_eq!(Some("main".to_string()), updated[0].target_branch);
+        assert_eq!(
+            "feature-1 has been merged, skipping\n\
+             feature-2 -> main: https://gitlab.com/owner/repo/-/merge_requests/2\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stack_force_push_of_unpushed_branch_expects_no_remote_branch() {
+        let remote = Arc::new(StackMock {
+            merge_requests: vec![branch_merge_request(1, "feature-1", "opened")],
+            ..Default::default()
+        });
+        // push, remote tip, tip, commits ahead of the base, containing
+        // branches, merged in the current branch, merged in the base, current
+        // branch, fetch.
+        let runner = Arc::new(MockRunner::new(vec![
+            shell_response(""),
+            shell_response(""),
+            shell_response("f1sha"),
+            shell_response("1"),
+            shell_response("feature-1"),
+            shell_response("feature-1\nmain"),
+            shell_response("main"),
+            shell_response("feature-1"),
+            shell_response(""),
+        ]));
+        let cli_args = MergeRequestStackCliArgs::builder()
+            .force(true)
+            .build()
+            .unwrap();
+        stack(remote, runner.clone(), "main", cli_args, Vec::new()).unwrap();
+        assert_eq!(
+            "git push --force-with-lease=feature-1: origin feature-1",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_replace_stack_table() {
+        let table = "<!-- gr stack -->\nnew\n<!-- gr stack end -->";
+        assert_eq!(table, replace_stack_table("", table));
+        assert_eq!(
+            format!("Description\n\n{}", table),
+            replace_stack_table("Description\n", table)
+        );
+        assert_eq!(
+            format!("Description\n\n{}\nFooter", table),
+            replace_stack_table(
+                "Description\n\n<!-- gr stack -->\nold\n<!-- gr stack end -->\nFooter",
+                table
+            )
+        );
+    }
+}
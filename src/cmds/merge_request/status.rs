@@ -0,0 +1,488 @@
+//! Readiness report of a merge request. Gathers everything that is usually
+//! checked before merging: pipeline, approvals, unresolved threads, conflicts,
+//! draft flag and how far behind the target branch the source branch is.
+
+use std::{io::Write, sync::Arc};
+
+use crate::{
+    api_traits::{MergeRequestReadiness, MergeRequestThread},
+    display::{self, Column, DisplayBody, Format},
+    error::GRError,
+    exec,
+    remote::GetRemoteCliArgs,
+    Cmd, Result,
+};
+
+use super::thread::count_unresolved;
+
+/// Mergeability of a merge request as reported by the remote.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct Mergeability {
+    pub draft: bool,
+    pub has_conflicts: bool,
+    /// Commits in the target branch missing in the source branch. None if the
+    /// remote could not tell.
+    pub behind_by: Option<u64>,
+    /// Combined status of the CI on the last commit, one of success, failed or
+    /// pending. Empty if no CI ran.
+    pub ci_status: String,
+}
+
+impl Mergeability {
+    pub fn builder() -> MergeabilityBuilder {
+        MergeabilityBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct Approvals {
+    pub approved: u32,
+    /// None if the remote does not expose the number of required approvals,
+    /// ex. Github branch protection requires admin permissions.
+    pub required: Option<u32>,
+}
+
+impl Approvals {
+    pub fn builder() -> ApprovalsBuilder {
+        ApprovalsBuilder::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CheckState {
+    Passed,
+    Failed,
+    /// Worth knowing, but does not prevent merging.
+    Warning,
+    /// Not decided yet, ex. the pipeline is still running.
+    Pending,
+}
+
+impl CheckState {
+    fn mark(&self) -> &str {
+        match self {
+            CheckState::Passed => "✔",
+            CheckState::Failed => "✖",
+            CheckState::Warning => "!",
+            CheckState::Pending => "…",
+        }
+    }
+}
+
+impl std::fmt::Display for CheckState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckState::Passed => write!(f, "passed"),
+            CheckState::Failed => write!(f, "failed"),
+            CheckState::Warning => write!(f, "warning"),
+            CheckState::Pending => write!(f, "pending"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub state: CheckState,
+    pub value: String,
+}
+
+impl ReadinessCheck {
+    fn new(name: &str, passed: bool, value: String) -> Self {
+        ReadinessCheck {
+            name: name.to_string(),
+            state: if passed {
+                CheckState::Passed
+            } else {
+                CheckState::Failed
+            },
+            value,
+        }
+    }
+}
+
+impl From<ReadinessCheck> for DisplayBody {
+    fn from(check: ReadinessCheck) -> Self {
+        DisplayBody::new(vec![
+            Column::new("Check", check.name),
+            Column::new("Status", check.state.to_string()),
+            Column::new("Value", check.value),
+        ])
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Readiness {
+    pub mergeability: Mergeability,
+    pub approvals: Approvals,
+    pub unresolved_threads: usize,
+}
+
+impl Readiness {
+    pub fn checks(&self) -> Vec<ReadinessCheck> {
+        let ci_status = &self.mergeability.ci_status;
+        let approvals = match self.approvals.required {
+            Some(required) => format!("{}/{}", self.approvals.approved, required),
+            None => format!("{} (required unknown)", self.approvals.approved),
+        };
+        let behind = match self.mergeability.behind_by {
+            Some(0) => ReadinessCheck::new("Behind target", true, "0 commits".to_string()),
+            Some(behind_by) => ReadinessCheck {
+                name: "Behind target".to_string(),
+                state: CheckState::Warning,
+                value: format!("{} commits", behind_by),
+            },
+            None => ReadinessCheck {
+                name: "Behind target".to_string(),
+                state: CheckState::Warning,
+                value: "unknown".to_string(),
+            },
+        };
+        let pipeline = ReadinessCheck {
+            name: "Pipeline".to_string(),
+            state: match ci_status.as_str() {
+                "" | "success" => CheckState::Passed,
+                "pending" => CheckState::Pending,
+                _ => CheckState::Failed,
+            },
+            value: if ci_status.is_empty() {
+                "none".to_string()
+            } else {
+                ci_status.to_string()
+            },
+        };
+        vec![
+            pipeline,
+            ReadinessCheck::new(
+                "Approvals",
+                self.approvals
+                    .required
+                    .is_none_or(|required| self.approvals.approved >= required),
+                approvals,
+            ),
+            ReadinessCheck::new(
+                "Unresolved threads",
+                self.unresolved_threads == 0,
+                self.unresolved_threads.to_string(),
+            ),
+            ReadinessCheck::new(
+                "Conflicts",
+                !self.mergeability.has_conflicts,
+                if self.mergeability.has_conflicts {
+                    "yes".to_string()
+                } else {
+                    "no".to_string()
+                },
+            ),
+            ReadinessCheck::new(
+                "Draft",
+                !self.mergeability.draft,
+                if self.mergeability.draft {
+                    "yes".to_string()
+                } else {
+                    "no".to_string()
+                },
+            ),
+            behind,
+        ]
+    }
+
+    /// A pending check is not a failure, but the merge request is not ready
+    /// until it passes.
+    pub fn is_mergeable(&self) -> bool {
+        self.checks()
+            .iter()
+            .all(|check| check.state == CheckState::Passed || check.state == CheckState::Warning)
+    }
+
+    fn is_pending(&self) -> bool {
+        let checks = self.checks();
+        checks
+            .iter()
+            .any(|check| check.state == CheckState::Pending)
+            && checks.iter().all(|check| check.state != CheckState::Failed)
+    }
+}
+
+enum ReadinessInfo {
+    Mergeability(Mergeability),
+    Approvals(Approvals),
+    UnresolvedThreads(usize),
+}
+
+/// Gathers the readiness of the merge request, querying the remote in
+/// parallel.
+pub fn readiness(
+    remote: Arc<dyn MergeRequestReadiness + Send + Sync>,
+    thread_remote: Arc<dyn MergeRequestThread + Send + Sync>,
+    id: i64,
+) -> Result<Readiness> {
+    let mergeability_remote = remote.clone();
+    let cmds: Vec<Cmd<ReadinessInfo>> = vec![
+        Box::new(move || -> Result<ReadinessInfo> {
+            Ok(ReadinessInfo::Mergeability(
+                mergeability_remote.mergeability(id)?,
+            ))
+        }),
+        Box::new(move || -> Result<ReadinessInfo> {
+            Ok(ReadinessInfo::Approvals(remote.approvals(id)?))
+        }),
+        Box::new(move || -> Result<ReadinessInfo> {
+            let threads = thread_remote.list(id)?;
+            Ok(ReadinessInfo::UnresolvedThreads(count_unresolved(&threads)))
+        }),
+    ];
+    let mut readiness = Readiness::default();
+    for info in exec::parallel_stream(cmds) {
+        match info? {
+            ReadinessInfo::Mergeability(mergeability) => readiness.mergeability = mergeability,
+            ReadinessInfo::Approvals(approvals) => readiness.approvals = approvals,
+            ReadinessInfo::UnresolvedThreads(count) => readiness.unresolved_threads = count,
+        }
+    }
+    Ok(readiness)
+}
+
+/// Prints the readiness of the merge request as a checklist, or in the
+/// requested format. Fails if the merge request cannot be merged yet.
+pub fn status<W: Write>(
+    remote: Arc<dyn MergeRequestReadiness + Send + Sync>,
+    thread_remote: Arc<dyn MergeRequestThread + Send + Sync>,
+    id: i64,
+    get_args: GetRemoteCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let readiness = readiness(remote, thread_remote, id)?;
+    write_status(&readiness, id, get_args, &mut writer)?;
+    if readiness.is_pending() {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Merge request {} is not ready to be merged yet, its pipeline is pending",
+            id
+        ))
+        .into());
+    }
+    if !readiness.is_mergeable() {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Merge request {} is not ready to be merged",
+            id
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+fn write_status<W: Write>(
+    readiness: &Readiness,
+    id: i64,
+    get_args: GetRemoteCliArgs,
+    writer: &mut W,
+) -> Result<()> {
+    let checks = readiness.checks();
+    match get_args.format {
+        Format::PIPE => {
+            for check in checks {
+                writeln!(
+                    writer,
+                    "{} {}: {}",
+                    check.state.mark(),
+                    check.name,
+                    check.value
+                )?;
+            }
+        }
+        Format::JSON => {
+            // A single document, so bots do not need to put the checks
+            // together.
+            let checks = checks
+                .into_iter()
+                .map(|check| {
+                    serde_json::json!({
+                        "check": check.name.to_lowercase(),
+                        "status": check.state.to_string(),
+                        "value": check.value,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let status = serde_json::json!({
+                "id": id,
+                "mergeable": readiness.is_mergeable(),
+                "checks": checks,
+            });
+            writeln!(writer, "{}", serde_json::to_string(&status)?)?;
+        }
+        _ => display::print(writer, checks, get_args)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmds::merge_request::thread::Thread;
+
+    use super::*;
+
+    struct ReadinessMock {
+        mergeability: Mergeability,
+        approvals: Approvals,
+    }
+
+    impl MergeRequestReadiness for ReadinessMock {
+        fn mergeability(&self, _id: i64) -> Result<Mergeability> {
+            Ok(self.mergeability.clone())
+        }
+        fn approvals(&self, _id: i64) -> Result<Approvals> {
+            Ok(self.approvals.clone())
+        }
+    }
+
+    struct ThreadMock {
+        threads: Vec<Thread>,
+    }
+
+    impl MergeRequestThread for ThreadMock {
+        fn list(&self, _id: i64) -> Result<Vec<Thread>> {
+            Ok(self.threads.clone())
+        }
+        fn reply(&self, _id: i64, _thread_id: &str, _body: String) -> Result<()> {
+            Ok(())
+        }
+        fn resolve(&self, _id: i64, _thread_id: &str, _resolved: bool) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn ready_remote() -> Arc<ReadinessMock> {
+        Arc::new(ReadinessMock {
+            mergeability: Mergeability::builder()
+                .behind_by(Some(0))
+                .ci_status("success".to_string())
+                .build()
+                .unwrap(),
+            approvals: Approvals::builder()
+                .approved(1)
+                .required(Some(1))
+                .build()
+                .unwrap(),
+        })
+    }
+
+    fn no_threads() -> Arc<ThreadMock> {
+        Arc::new(ThreadMock { threads: vec![] })
+    }
+
+    #[test]
+    fn test_status_ready_to_merge_checklist() {
+        let mut writer = Vec::new();
+        let get_args = GetRemoteCliArgs::builder().build().unwrap();
+        status(ready_remote(), no_threads(), 23, get_args, &mut writer).unwrap();
+        assert_eq!(
+            "✔ Pipeline: success\n\
+             ✔ Approvals: 1/1\n\
+             ✔ Unresolved threads: 0\n\
+             ✔ Conflicts: no\n\
+             ✔ Draft: no\n\
+             ✔ Behind target: 0 commits\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_status_not_mergeable_is_err() {
+        let remote = Arc::new(ReadinessMock {
+            mergeability: Mergeability::builder()
+                .draft(true)
+                .has_conflicts(true)
+                .behind_by(Some(3))
+                .ci_status("failed".to_string())
+                .build()
+                .unwrap(),
+            approvals: Approvals::builder()
+                .approved(1)
+                .required(Some(2))
+                .build()
+                .unwrap(),
+        });
+        let threads = Arc::new(ThreadMock {
+            threads: vec![Thread::builder()
+                .id("abc".to_string())
+                .resolvable(true)
+                .build()
+                .unwrap()],
+        });
+        let mut writer = Vec::new();
+        let get_args = GetRemoteCliArgs::builder().build().unwrap();
+        let err = status(remote, threads, 23, get_args, &mut writer).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => (),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+        assert_eq!(
+            "✖ Pipeline: failed\n\
+             ✖ Approvals: 1/2\n\
+             ✖ Unresolved threads: 1\n\
+             ✖ Conflicts: yes\n\
+             ✖ Draft: yes\n\
+             ! Behind target: 3 commits\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_status_pending_pipeline_is_not_ready() {
+        let remote = Arc::new(ReadinessMock {
+            mergeability: Mergeability::builder()
+                .behind_by(Some(0))
+                .ci_status("pending".to_string())
+                .build()
+                .unwrap(),
+            approvals: Approvals::builder()
+                .approved(1)
+                .required(Some(1))
+                .build()
+                .unwrap(),
+        });
+        let mut writer = Vec::new();
+        let get_args = GetRemoteCliArgs::builder().build().unwrap();
+        let err = status(remote, no_threads(), 23, get_args, &mut writer).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => assert_eq!(
+                "Merge request 23 is not ready to be merged yet, its pipeline is pending",
+                msg
+            ),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+        // Reported as pending, not as failed.
+        assert!(String::from_utf8(writer)
+            .unwrap()
+            .starts_with("… Pipeline: pending\n"));
+    }
+
+    #[test]
+    fn test_behind_target_and_unknown_approvals_do_not_block() {
+        let readiness = Readiness {
+            mergeability: Mergeability::builder().behind_by(Some(5)).build().unwrap(),
+            approvals: Approvals::default(),
+            unresolved_threads: 0,
+        };
+        assert!(readiness.is_mergeable());
+    }
+
+    #[test]
+    fn test_status_json_format() {
+        let mut writer = Vec::new();
+        let get_args = GetRemoteCliArgs::builder()
+            .format(Format::JSON)
+            .build()
+            .unwrap();
+        status(ready_remote(), no_threads(), 23, get_args, &mut writer).unwrap();
+        let status: serde_json::Value = serde_json::from_slice(&writer).unwrap();
+        assert_eq!(23, status["id"]);
+        assert_eq!(true, status["mergeable"]);
+        assert_eq!(6, status["checks"].as_array().unwrap().len());
+        assert_eq!("pipeline", status["checks"][0]["check"]);
+        assert_eq!("passed", status["checks"][0]["status"]);
+        assert_eq!("success", status["checks"][0]["value"]);
+    }
+}
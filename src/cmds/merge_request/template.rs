@@ -0,0 +1,264 @@
+//! Merge request templates found in the repository. Github and Gitlab both
+//! support them, so any of them can be used to fill in the description of a
+//! new merge request.
+
+use std::{fs, path::Path};
+
+use crate::{error::GRError, Result};
+
+/// Single template file names supported by Github, relative to the root of the
+/// repository.
+const GITHUB_TEMPLATE_FILES: [&str; 2] = [
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    ".github/pull_request_template.md",
+];
+
+/// Directories holding several templates, one per markdown file.
+const TEMPLATE_DIRS: [&str; 2] = [
+    ".github/PULL_REQUEST_TEMPLATE",
+    ".gitlab/merge_request_templates",
+];
+
+/// Name given to a single Github template file.
+const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    pub name: String,
+    pub content: String,
+}
+
+/// Values substituted in a template. Placeholders are written as
+/// `{{name}}` in the template, ex. `{{commits}}`.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct TemplateValues {
+    pub title: String,
+    /// Description gathered from the commit message or the cli.
+    pub description: String,
+    /// One line per outgoing commit.
+    pub commits: String,
+    pub source_branch: String,
+    pub target_branch: String,
+}
+
+impl TemplateValues {
+    pub fn builder() -> TemplateValuesBuilder {
+        TemplateValuesBuilder::default()
+    }
+}
+
+/// Templates found under the root of the repository, sorted by name.
+pub fn discover(root: &Path) -> Result<Vec<Template>> {
+    let mut templates = Vec::new();
+    for file in GITHUB_TEMPLATE_FILES {
+        let path = root.join(file);
+        if path.is_file() {
+            templates.push(Template {
+                name: DEFAULT_TEMPLATE_NAME.to_string(),
+                content: fs::read_to_string(path)?,
+            });
+            // Both names point to the same file on case insensitive file
+            // systems.
+            break;
+        }
+    }
+    for dir in TEMPLATE_DIRS {
+        let path = root.join(dir);
+        if !path.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                continue;
+            }
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default()
+                .to_string();
+            if templates.iter().any(|template| template.name == name) {
+                continue;
+            }
+            templates.push(Template {
+                name,
+                content: fs::read_to_string(&path)?,
+            });
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Picks the template to use. A template requested by name must exist. Without
+/// a name, a single template is used as is and the user chooses among several
+/// ones with `choose`, which gets the template names. Nothing is chosen when
+/// `interactive` is false and there are several templates.
+pub fn select(
+    templates: Vec<Template>,
+    name: Option<&str>,
+    interactive: bool,
+    choose: impl Fn(Vec<String>) -> Result<String>,
+) -> Result<Option<Template>> {
+    if let Some(name) = name {
+        let names = templates
+            .iter()
+            .map(|template| template.name.clone())
+            .collect::<Vec<String>>();
+        return match templates.into_iter().find(|template| template.name == name) {
+            Some(template) => Ok(Some(template)),
+            None => Err(GRError::PreconditionNotMet(format!(
+                "Merge request template {} not found. Available templates: [{}]",
+                name,
+                names.join(", ")
+            ))
+            .into()),
+        };
+    }
+    match templates.len() {
+        0 => Ok(None),
+        1 => Ok(templates.into_iter().next()),
+        _ if !interactive => Ok(None),
+        _ => {
+            let names = templates
+                .iter()
+                .map(|template| template.name.clone())
+                .collect();
+            let chosen = choose(names)?;
+            Ok(templates
+                .into_iter()
+                .find(|template| template.name == chosen))
+        }
+    }
+}
+
+/// Fills in the placeholders of a template. Unknown placeholders are left
+/// untouched.
+pub fn render(template: &Template, values: &TemplateValues) -> String {
+    template
+        .content
+        .replace("{{title}}", &values.title)
+        .replace("{{description}}", &values.description)
+        .replace("{{commits}}", &values.commits)
+        .replace("{{source_branch}}", &values.source_branch)
+        .replace("{{target_branch}}", &values.target_branch)
+}
+
+/// Whether rendering the template requires the list of outgoing commits, which
+/// takes an extra git command to gather.
+pub fn needs_commits(template: &Template) -> bool {
+    template.content.contains("{{commits}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, content: &str) -> Template {
+        Template {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_discover_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".github/PULL_REQUEST_TEMPLATE")).unwrap();
+        fs::create_dir_all(dir.path().join(".gitlab/merge_request_templates")).unwrap();
+        fs::write(
+            dir.path().join(".github/PULL_REQUEST_TEMPLATE.md"),
+            "## Summary",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".github/PULL_REQUEST_TEMPLATE/bugfix.md"),
+            "## Bug",
+        )
+        .unwrap();
+        fs::write(
+            dir.path()
+                .join(".gitlab/merge_request_templates/feature.md"),
+            "## Feature",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".gitlab/merge_request_templates/notes.txt"),
+            "ignored",
+        )
+        .unwrap();
+        let templates = discover(dir.path()).unwrap();
+        assert_eq!(
+            vec![
+                template("bugfix", "## Bug"),
+                template("default", "## Summary"),
+                template("feature", "## Feature"),
+            ],
+            templates
+        );
+    }
+
+    #[test]
+    fn test_discover_no_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(discover(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_select_template_by_name() {
+        let templates = vec![template("bugfix", "## Bug"), template("feature", "")];
+        let selected = select(templates, Some("bugfix"), true, |_| {
+            panic!("No prompt expected")
+        })
+        .unwrap();
+        assert_eq!(Some(template("bugfix", "## Bug")), selected);
+    }
+
+    #[test]
+    fn test_select_unknown_template_is_err() {
+        let templates = vec![template("bugfix", ""), template("feature", "")];
+        let err = select(templates, Some("docs"), true, |_| {
+            panic!("No prompt expected")
+        })
+        .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("Available templates: [bugfix, feature]"))
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_select_prompts_among_several_templates() {
+        let templates = vec![template("bugfix", ""), template("feature", "## Feature")];
+        let selected = select(templates.clone(), None, true, |names| {
+            assert_eq!(vec!["bugfix", "feature"], names);
+            Ok("feature".to_string())
+        })
+        .unwrap();
+        assert_eq!(Some(template("feature", "## Feature")), selected);
+        let selected = select(templates, None, false, |_| panic!("No prompt expected")).unwrap();
+        assert_eq!(None, selected);
+    }
+
+    #[test]
+    fn test_render_template() {
+        let template = template(
+            "default",
+            "## {{title}}\n\n{{description}}\n\n### Commits\n\n{{commits}}\n\n{{unknown}}",
+        );
+        let values = TemplateValues::builder()
+            .title("Add stacks".to_string())
+            .description("Stacked merge requests".to_string())
+            .commits("Add stack - 1a2b3c".to_string())
+            .build()
+            .unwrap();
+        assert!(needs_commits(&template));
+        assert_eq!(
+            "## Add stacks\n\nStacked merge requests\n\n### Commits\n\nAdd stack - 1a2b3c\n\n{{unknown}}",
+            render(&template, &values)
+        );
+    }
+}
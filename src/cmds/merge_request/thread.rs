@@ -0,0 +1,300 @@
+//! Discussion threads on merge requests. Gitlab discussions and Github review
+//! threads group notes that can be replied to and resolved.
+
+use std::{
+    io::{BufRead, Write},
+    sync::Arc,
+};
+
+use crate::{
+    api_traits::{MergeRequestThread, Timestamp},
+    display::{self, Column, DisplayBody},
+    remote::GetRemoteCliArgs,
+    Result,
+};
+
+use super::{comment_body, Comment};
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct Thread {
+    pub id: String,
+    /// Whether the thread can be resolved. Gitlab plain comments cannot.
+    pub resolvable: bool,
+    pub resolved: bool,
+    /// File and line the thread is anchored to in the diff, if any.
+    pub path: Option<String>,
+    pub line: Option<i64>,
+    pub notes: Vec<Comment>,
+    pub created_at: String,
+}
+
+impl Thread {
+    pub fn builder() -> ThreadBuilder {
+        ThreadBuilder::default()
+    }
+
+    pub fn is_unresolved(&self) -> bool {
+        self.resolvable && !self.resolved
+    }
+}
+
+impl Timestamp for Thread {
+    fn created_at(&self) -> String {
+        self.created_at.clone()
+    }
+}
+
+impl From<Thread> for DisplayBody {
+    fn from(thread: Thread) -> Self {
+        let state = match (thread.resolvable, thread.resolved) {
+            (false, _) => "",
+            (true, true) => "resolved",
+            (true, false) => "unresolved",
+        };
+        let position = match (&thread.path, thread.line) {
+            (Some(path), Some(line)) => format!("{}:{}", path, line),
+            (Some(path), None) => path.to_string(),
+            _ => "".to_string(),
+        };
+        let first_note = thread.notes.first().cloned().unwrap_or_default();
+        DisplayBody::new(vec![
+            Column::new("ID", thread.id),
+            Column::new("State", state.to_string()),
+            Column::new("Position", position),
+            Column::new("Author", first_note.author),
+            Column::new("Notes", thread.notes.len().to_string()),
+            Column::new("Body", first_note.body),
+            Column::new("Created at", thread.created_at),
+        ])
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct MergeRequestThreadListCliArgs {
+    pub id: i64,
+    /// Show only the threads pending to be resolved.
+    #[builder(default)]
+    pub unresolved: bool,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl MergeRequestThreadListCliArgs {
+    pub fn builder() -> MergeRequestThreadListCliArgsBuilder {
+        MergeRequestThreadListCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct MergeRequestThreadReplyCliArgs {
+    pub id: i64,
+    pub thread_id: String,
+    #[builder(default)]
+    pub comment: Option<String>,
+    #[builder(default)]
+    pub comment_from_file: Option<String>,
+}
+
+impl MergeRequestThreadReplyCliArgs {
+    pub fn builder() -> MergeRequestThreadReplyCliArgsBuilder {
+        MergeRequestThreadReplyCliArgsBuilder::default()
+    }
+}
+
+pub fn count_unresolved(threads: &[Thread]) -> usize {
+    threads
+        .iter()
+        .filter(|thread| thread.is_unresolved())
+        .count()
+}
+
+pub fn list<W: Write>(
+    remote: Arc<dyn MergeRequestThread>,
+    cli_args: MergeRequestThreadListCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let mut threads = remote.list(cli_args.id)?;
+    if cli_args.unresolved {
+        threads.retain(|thread| thread.is_unresolved());
+    }
+    if threads.is_empty() {
+        writer.write_all(b"No resources found.\n")?;
+        return Ok(());
+    }
+    display::print(&mut writer, threads, cli_args.get_args)?;
+    Ok(())
+}
+
+pub fn reply<R: BufRead>(
+    remote: Arc<dyn MergeRequestThread>,
+    cli_args: MergeRequestThreadReplyCliArgs,
+    reader: Option<R>,
+) -> Result<()> {
+    let comment = comment_body(cli_args.comment, reader)?;
+    remote.reply(cli_args.id, &cli_args.thread_id, comment)
+}
+
+pub fn resolve<W: Write>(
+    remote: Arc<dyn MergeRequestThread>,
+    id: i64,
+    thread_id: String,
+    resolved: bool,
+    mut writer: W,
+) -> Result<()> {
+    remote.resolve(id, &thread_id, resolved)?;
+    writeln!(
+        writer,
+        "Thread {} {}",
+        thread_id,
+        if resolved { "resolved" } else { "unresolved" }
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, sync::Mutex};
+
+    use super::*;
+
+    fn note(author: &str, body: &str) -> Comment {
+        Comment::builder()
+            .id(1)
+            .author(author.to_string())
+            .body(body.to_string())
+            .created_at("2024-01-01".to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[derive(Default)]
+    struct ThreadMock {
+        threads: Vec<Thread>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl MergeRequestThread for ThreadMock {
+        fn list(&self, _id: i64) -> Result<Vec<Thread>> {
+            Ok(self.threads.clone())
+        }
+        fn reply(&self, id: i64, thread_id: &str, body: String) -> Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("reply {} {} {}", id, thread_id, body));
+            Ok(())
+        }
+        fn resolve(&self, id: i64, thread_id: &str, resolved: bool) -> Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("resolve {} {} {}", id, thread_id, resolved));
+            Ok(())
+        }
+    }
+
+    fn threads() -> Vec<Thread> {
+        vec![
+            Thread::builder()
+                .id("abc".to_string())
+                .resolvable(true)
+                .path(Some("src/main.rs".to_string()))
+                .line(Some(42))
+                .notes(vec![note("tom", "Typo"), note("huck", "Fixed")])
+                .created_at("2024-01-01".to_string())
+                .build()
+                .unwrap(),
+            Thread::builder()
+                .id("def".to_string())
+                .resolvable(true)
+                .resolved(true)
+                .notes(vec![note("jim", "Why?")])
+                .created_at("2024-01-02".to_string())
+                .build()
+                .unwrap(),
+            Thread::builder()
+                .id("ghi".to_string())
+                .notes(vec![note("becky", "Nice")])
+                .created_at("2024-01-03".to_string())
+                .build()
+                .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_list_threads() {
+        let remote = Arc::new(ThreadMock {
+            threads: threads(),
+            ..Default::default()
+        });
+        let cli_args = MergeRequestThreadListCliArgs::builder()
+            .id(1)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        list(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "ID|State|Position|Author|Notes|Body|Created at\n\
+             abc|unresolved|src/main.rs:42|tom|2|Typo|2024-01-01\n\
+             def|resolved||jim|1|Why?|2024-01-02\n\
+             ghi|||becky|1|Nice|2024-01-03\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_unresolved_threads() {
+        let remote = Arc::new(ThreadMock {
+            threads: threads(),
+            ..Default::default()
+        });
+        let cli_args = MergeRequestThreadListCliArgs::builder()
+            .id(1)
+            .unresolved(true)
+            .get_args(
+                GetRemoteCliArgs::builder()
+                    .no_headers(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        list(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "abc|unresolved|src/main.rs:42|tom|2|Typo|2024-01-01\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_count_unresolved() {
+        assert_eq!(1, count_unresolved(&threads()));
+    }
+
+    #[test]
+    fn test_reply_from_reader() {
+        let remote = Arc::new(ThreadMock::default());
+        let cli_args = MergeRequestThreadReplyCliArgs::builder()
+            .id(1)
+            .thread_id("abc".to_string())
+            .comment_from_file(Some("-".to_string()))
+            .build()
+            .unwrap();
+        reply(remote.clone(), cli_args, Some(Cursor::new("Done"))).unwrap();
+        assert_eq!(vec!["reply 1 abc Done"], *remote.calls.lock().unwrap());
+    }
+
+    #[test]
+    fn test_unresolve_thread() {
+        let remote = Arc::new(ThreadMock::default());
+        let mut writer = Vec::new();
+        resolve(remote.clone(), 1, "abc".to_string(), false, &mut writer).unwrap();
+        assert_eq!(
+            "Thread abc unresolved\n",
+            String::from_utf8(writer).unwrap()
+        );
+        assert_eq!(vec!["resolve 1 abc false"], *remote.calls.lock().unwrap());
+    }
+}
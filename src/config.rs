@@ -2,6 +2,7 @@
 
 use crate::api_defaults::{EXPIRE_IMMEDIATELY, RATE_LIMIT_REMAINING_THRESHOLD, REST_API_MAX_PAGES};
 use crate::api_traits::ApiOperation;
+use crate::cmds::merge_request::{reviewer::ReviewerPolicy, MergeMethod};
 use crate::cmds::project::{Member, MrMemberType};
 use crate::error::{self, GRError};
 use crate::remote::RemoteURL;
@@ -25,6 +26,31 @@ pub trait ConfigProperties: Send + Sync {
         ""
     }
 
+    /// Labels applied to new merge requests when none are given in the cli.
+    fn merge_request_labels(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Policy used to pick reviewers when none are given in the cli.
+    fn merge_request_reviewer_policy(&self) -> Option<ReviewerPolicy> {
+        None
+    }
+
+    /// Number of reviewers picked by the reviewer policy.
+    fn merge_request_num_reviewers(&self) -> usize {
+        1
+    }
+
+    /// Default merge method when merging. None leaves it to the remote.
+    fn merge_request_merge_method(&self) -> Option<MergeMethod> {
+        None
+    }
+
+    /// Delete the source branch after merging by default.
+    fn merge_request_delete_branch(&self) -> bool {
+        false
+    }
+
     fn get_cache_expiration(&self, _api_operation: &ApiOperation) -> &str {
         // Defaults to regular HTTP cache expiration mechanisms.
         "0s"
@@ -104,6 +130,11 @@ struct MergeRequestConfig {
     preferred_assignee_username: Option<UserInfo>,
     members: Option<Vec<UserInfo>>,
     description_signature: Option<String>,
+    labels: Option<Vec<String>>,
+    reviewer_policy: Option<ReviewerPolicy>,
+    num_reviewers: Option<usize>,
+    merge_method: Option<MergeMethod>,
+    delete_branch: Option<bool>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -226,6 +257,20 @@ impl ConfigFile {
         }
     }
 
+    /// Merge request setting in the project section, falling back to the
+    /// domain section if the project does not define it.
+    fn merge_request_setting<T>(
+        &self,
+        setting: impl Fn(&MergeRequestConfig) -> Option<T>,
+    ) -> Option<T> {
+        let domain_config = self.inner.domains.get(&self.domain_key)?;
+        domain_config
+            .projects
+            .get(&self.project_path_key)
+            .and_then(|project_config| project_config.merge_requests.as_ref().and_then(&setting))
+            .or_else(|| domain_config.merge_requests.as_ref().and_then(&setting))
+    }
+
     fn get_members(&self, merge_request_config: &MergeRequestConfig) -> Option<Vec<Member>> {
         merge_request_config.members.as_ref().map(|users| {
             users
@@ -380,6 +425,31 @@ impl ConfigProperties for ConfigFile {
         }
     }
 
+    fn merge_request_labels(&self) -> Vec<String> {
+        self.merge_request_setting(|merge_request_config| merge_request_config.labels.clone())
+            .unwrap_or_default()
+    }
+
+    fn merge_request_reviewer_policy(&self) -> Option<ReviewerPolicy> {
+        self.merge_request_setting(|merge_request_config| {
+            merge_request_config.reviewer_policy.clone()
+        })
+    }
+
+    fn merge_request_num_reviewers(&self) -> usize {
+        self.merge_request_setting(|merge_request_config| merge_request_config.num_reviewers)
+            .unwrap_or(1)
+    }
+
+    fn merge_request_merge_method(&self) -> Option<MergeMethod> {
+        self.merge_request_setting(|merge_request_config| merge_request_config.merge_method)
+    }
+
+    fn merge_request_delete_branch(&self) -> bool {
+        self.merge_request_setting(|merge_request_config| merge_request_config.delete_branch)
+            .unwrap_or_default()
+    }
+
     fn get_cache_expiration(&self, api_operation: &ApiOperation) -> &str {
         self.inner
             .domains
@@ -449,6 +519,26 @@ impl ConfigProperties for Arc<ConfigFile> {
     fn merge_request_members(&self) -> Vec<Member> {
         self.as_ref().merge_request_members()
     }
+
+    fn merge_request_labels(&self) -> Vec<String> {
+        self.as_ref().merge_request_labels()
+    }
+
+    fn merge_request_reviewer_policy(&self) -> Option<ReviewerPolicy> {
+        self.as_ref().merge_request_reviewer_policy()
+    }
+
+    fn merge_request_num_reviewers(&self) -> usize {
+        self.as_ref().merge_request_num_reviewers()
+    }
+
+    fn merge_request_merge_method(&self) -> Option<MergeMethod> {
+        self.as_ref().merge_request_merge_method()
+    }
+
+    fn merge_request_delete_branch(&self) -> bool {
+        self.as_ref().merge_request_delete_branch()
+    }
 }
 
 #[cfg(test)]
@@ -486,6 +576,7 @@ mod test {
         release = 7
         gist = 8
         repository_tag = 9
+        issue = 10
 
         [gitlab_com.cache_expirations]
         merge_request = "30m"
@@ -496,6 +587,7 @@ mod test {
         release = "4h"
         gist = "1w"
         repository_tag = "0s"
+        issue = "1h"
         "#;
         let domain = "gitlab.com";
         let reader = vec![std::io::Cursor::new(config_data)];
@@ -523,6 +615,7 @@ mod test {
         assert_eq!(7, config.get_max_pages(&ApiOperation::Release));
         assert_eq!(8, config.get_max_pages(&ApiOperation::Gist));
         assert_eq!(9, config.get_max_pages(&ApiOperation::RepositoryTag));
+        assert_eq!(10, config.get_max_pages(&ApiOperation::Issue));
 
         assert_eq!(
             "30m",
@@ -541,6 +634,7 @@ mod test {
             "0s",
             config.get_cache_expiration(&ApiOperation::RepositoryTag)
         );
+        assert_eq!("1h", config.get_cache_expiration(&ApiOperation::Issue));
         let members = config.merge_request_members();
         assert_eq!(2, members.len());
         assert_eq!("jdoe", members[0].username);
@@ -615,6 +709,108 @@ mod test {
         assert_eq!(1234, members[0].id);
     }
 
+    #[test]
+    fn test_config_merge_request_labels() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.merge_requests]
+        labels = ["needs-review"]
+
+        [gitlab_com.datateam_projecta.merge_requests]
+        labels = ["needs-review", "data"]"#;
+
+        let domain = "gitlab.com";
+        let url = RemoteURL::new(domain.to_string(), "datateam/projecta".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(vec!["needs-review", "data"], config.merge_request_labels());
+
+        let url = RemoteURL::new(domain.to_string(), "datateam/projectb".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(vec!["needs-review"], config.merge_request_labels());
+    }
+
+    #[test]
+    fn test_config_merge_request_reviewer_policy() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.merge_requests]
+        reviewer_policy = "round-robin"
+        num_reviewers = 2
+
+        [gitlab_com.datateam_projecta.merge_requests]
+        reviewer_policy = "codeowners""#;
+
+        let domain = "gitlab.com";
+        let url = RemoteURL::new(domain.to_string(), "datateam/projecta".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            Some(ReviewerPolicy::Codeowners),
+            config.merge_request_reviewer_policy()
+        );
+        assert_eq!(2, config.merge_request_num_reviewers());
+
+        let url = RemoteURL::new(domain.to_string(), "datateam/projectb".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            Some(ReviewerPolicy::RoundRobin),
+            config.merge_request_reviewer_policy()
+        );
+    }
+
+    #[test]
+    fn test_config_merge_request_reviewer_policy_defaults() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'"#;
+
+        let domain = "gitlab.com";
+        let url = RemoteURL::new(domain.to_string(), "datateam/projecta".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(None, config.merge_request_reviewer_policy());
+        assert_eq!(1, config.merge_request_num_reviewers());
+    }
+
+    #[test]
+    fn test_config_merge_request_merge_options() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.merge_requests]
+        merge_method = "squash"
+        delete_branch = true
+
+        [gitlab_com.datateam_projecta.merge_requests]
+        merge_method = "rebase""#;
+
+        let domain = "gitlab.com";
+        let url = RemoteURL::new(domain.to_string(), "datateam/projecta".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            Some(MergeMethod::Rebase),
+            config.merge_request_merge_method()
+        );
+        assert!(config.merge_request_delete_branch());
+
+        let url = RemoteURL::new(domain.to_string(), "datateam/projectb".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            Some(MergeMethod::Squash),
+            config.merge_request_merge_method()
+        );
+    }
+
     #[test]
     fn test_config_with_overridden_project_specific_settings_multiple_readers() {
         let config_data = r#"
@@ -9,7 +9,7 @@ use dialoguer::FuzzySelect;
 use dialoguer::Input;
 
 use crate::cmds::merge_request::MergeRequestBodyArgs;
-use crate::cmds::project::Member;
+use crate::cmds::project::{Member, MrMemberType};
 use crate::config::ConfigProperties;
 use crate::error;
 use crate::Result;
@@ -20,7 +20,7 @@ pub struct MergeRequestUserInput {
     pub description: String,
     pub assignee: Member,
     #[builder(default)]
-    pub reviewer: Member,
+    pub reviewers: Vec<Member>,
 }
 
 impl MergeRequestUserInput {
@@ -37,7 +37,7 @@ impl MergeRequestUserInput {
                 .username(username.to_string())
                 .build()
                 .unwrap(),
-            reviewer: Member::default(),
+            reviewers: vec![],
         }
     }
 }
@@ -105,11 +105,13 @@ impl MemberSelector {
 }
 
 /// Given a new merge request, prompt user for assignee, title and description.
+/// The reviewer is prompted for unless several default reviewers were already
+/// given in the cli or picked by the reviewer policy.
 pub fn prompt_user_merge_request_info(
     default_title: &str,
     default_description: &str,
     default_cli_assignee: Option<&Member>,
-    default_cli_reviewer: Option<&Member>,
+    default_reviewers: &[Member],
     config: &Arc<dyn ConfigProperties>,
 ) -> Result<MergeRequestUserInput> {
     let (title, description) = prompt_user_title_description(default_title, default_description);
@@ -125,15 +127,30 @@ pub fn prompt_user_merge_request_info(
     let assignee_index = gather_member(&assignee_list, "Assignee:");
     let assigned_member = assignee_list[assignee_index].clone();
 
-    // Prepare reviewer list excluding the selected assignee
-    let reviewer_list = selector.prepare_reviewer_list(default_cli_reviewer, &assigned_member);
-    let reviewer_index = gather_member(&reviewer_list, "Reviewer:");
+    let reviewers = if default_reviewers.len() > 1 {
+        default_reviewers
+            .iter()
+            .filter(|reviewer| reviewer.username != assigned_member.username)
+            .cloned()
+            .collect()
+    } else {
+        // Prepare reviewer list excluding the selected assignee
+        let reviewer_list =
+            selector.prepare_reviewer_list(default_reviewers.first(), &assigned_member);
+        let reviewer_index = gather_member(&reviewer_list, "Reviewer:");
+        vec![reviewer_list[reviewer_index].clone()]
+    };
 
     Ok(MergeRequestUserInput::builder()
         .title(title)
         .description(description)
         .assignee(assigned_member)
-        .reviewer(reviewer_list[reviewer_index].clone())
+        .reviewers(
+            reviewers
+                .into_iter()
+                .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+                .collect(),
+        )
         .build()
         .unwrap())
 }
@@ -227,7 +244,19 @@ pub fn show_summary_merge_request(
     show_outgoing_changes_summary(commit_str);
     show_input("Target branch", &args.target_branch, false, Style::Bold);
     show_input("Assignee", &args.assignee.username, false, Style::Bold);
-    show_input("Reviewer", &args.reviewer.username, false, Style::Bold);
+    let reviewers = args
+        .reviewers
+        .iter()
+        .map(|reviewer| reviewer.username.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    show_input("Reviewers", &reviewers, false, Style::Bold);
+    if !args.labels.is_empty() {
+        show_input("Labels", &args.labels.join(", "), false, Style::Bold);
+    }
+    if let Some(milestone) = &args.milestone {
+        show_input("Milestone", milestone, false, Style::Bold);
+    }
     show_input("Title", &args.title, false, Style::Bold);
     if !args.description.is_empty() {
         show_input("Description:", &args.description, true, Style::Bold);
@@ -259,14 +288,14 @@ pub fn prompt_args() -> String {
         .unwrap()
 }
 
-pub fn fuzzy_select(amps: Vec<String>) -> Result<String> {
+pub fn fuzzy_select(prompt: &str, items: Vec<String>) -> Result<String> {
     let selection = dialoguer::FuzzySelect::with_theme(&ColorfulTheme::default())
-        .with_prompt("amp:")
+        .with_prompt(prompt)
         .default(0)
-        .items(&amps)
+        .items(&items)
         .interact()
         .unwrap();
-    Ok(amps[selection].to_string())
+    Ok(items[selection].to_string())
 }
 
 #[cfg(test)]
@@ -195,6 +195,36 @@ pub fn outgoing_commits(
     Ok(response.body)
 }
 
+/// Paths changed by the outgoing commits, relative to the root of the
+/// repository.
+pub fn outgoing_files(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    remote: &str,
+    default_branch: &str,
+) -> Result<Vec<String>> {
+    let cmd = vec![
+        "git".to_string(),
+        "log".to_string(),
+        format!("{}/{}..", remote, default_branch),
+        "--name-only".to_string(),
+        "--pretty=format:".to_string(),
+    ];
+    let response = runner.run(cmd)?;
+    let mut files: Vec<String> = Vec::new();
+    for file in response.body.lines().filter(|line| !line.is_empty()) {
+        if !files.iter().any(|known| known == file) {
+            files.push(file.to_string());
+        }
+    }
+    Ok(files)
+}
+
+/// Absolute path of the root of the repository.
+pub fn toplevel(runner: &impl TaskRunner<Response = ShellResponse>) -> Result<String> {
+    let response = runner.run(vec!["git", "rev-parse", "--show-toplevel"])?;
+    Ok(response.body.trim().to_string())
+}
+
 pub fn patch<S: Into<String>, T: Into<String>>(
     runner: &impl TaskRunner<Response = ShellResponse>,
     current_branch: S,
@@ -218,6 +248,24 @@ pub fn push(runner: &impl TaskRunner, remote: &str, repo: &Repo, force: bool) ->
     Ok(CmdInfo::Ignore)
 }
 
+/// Force pushes `branch` only if the remote branch still points to `expected`,
+/// a commit or a reference to it, so commits pushed by someone else in the
+/// meantime are not overwritten.
+pub fn push_with_lease(
+    runner: &impl TaskRunner,
+    remote: &str,
+    branch: &str,
+    expected: &str,
+) -> Result<CmdInfo> {
+    let lease = format!("--force-with-lease={}:{}", branch, expected);
+    let cmd_params = ["git", "push", &lease, remote, branch];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to git push. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
 pub fn rebase(runner: &impl TaskRunner, remote_alias: &str) -> Result<CmdInfo> {
     let cmd = format!("git rebase {}", remote_alias);
     let cmd_params = cmd.split(' ').collect::<Vec<&str>>();
@@ -225,6 +273,219 @@ pub fn rebase(runner: &impl TaskRunner, remote_alias: &str) -> Result<CmdInfo> {
     Ok(CmdInfo::Ignore)
 }
 
+/// Rebase the commits of `branch` that are not in `upstream` on top of
+/// `new_base`. Used to move a branch to a new parent once the old one has been
+/// rewritten or merged.
+pub fn rebase_onto(
+    runner: &impl TaskRunner,
+    new_base: &str,
+    upstream: &str,
+    branch: &str,
+) -> Result<CmdInfo> {
+    let cmd_params = ["git", "rebase", "--onto", new_base, upstream, branch];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to git rebase. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+/// Local branches whose tip is reachable from `commit`.
+pub fn merged_branches(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    commit: &str,
+) -> Result<Vec<String>> {
+    let cmd_params = [
+        "git",
+        "branch",
+        "--format=%(refname:short)",
+        "--merged",
+        commit,
+    ];
+    let response = runner.run(cmd_params)?;
+    Ok(branch_names(&response.body))
+}
+
+/// Local branches that contain `commit` in their history.
+pub fn containing_branches(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    commit: &str,
+) -> Result<Vec<String>> {
+    let cmd_params = [
+        "git",
+        "branch",
+        "--format=%(refname:short)",
+        "--contains",
+        commit,
+    ];
+    let response = runner.run(cmd_params)?;
+    Ok(branch_names(&response.body))
+}
+
+fn branch_names(body: &str) -> Vec<String> {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Files with unresolved conflicts, ex. after a rebase stopped.
+pub fn conflicted_files(runner: &impl TaskRunner<Response = ShellResponse>) -> Result<Vec<String>> {
+    let cmd_params = ["git", "diff", "--name-only", "--diff-filter=U"];
+    let response = runner.run(cmd_params).err_context(format!(
+        "Failed to list conflicted files. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(response
+        .body
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Number of commits reachable from `to` but not from `from`.
+pub fn count_commits(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    from: &str,
+    to: &str,
+) -> Result<usize> {
+    let range = format!("{}..{}", from, to);
+    let response = runner.run(["git", "rev-list", "--count", &range])?;
+    response.body.trim().parse().map_err(|_| {
+        GRError::ApplicationError(format!(
+            "Could not count commits in {}. Got: {}",
+            range, response.body
+        ))
+        .into()
+    })
+}
+
+/// Value of a git configuration key. None if the key is not set.
+pub fn config_value(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    key: &str,
+) -> Result<Option<String>> {
+    // Without a default git exits with an error when the key is not set.
+    let response = runner.run(["git", "config", "--get", "--default=", key])?;
+    let value = response.body.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(value.to_string()))
+}
+
+pub fn set_config(runner: &impl TaskRunner, key: &str, value: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "config", key, value];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to set git configuration. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+pub fn unset_config(runner: &impl TaskRunner, key: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "config", "--unset", key];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to unset git configuration. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+/// SHA of the remote-tracking branch of `branch`. None if the branch has not
+/// been pushed.
+pub fn remote_branch_tip(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    remote: &str,
+    branch: &str,
+) -> Result<Option<String>> {
+    let reference = format!("refs/remotes/{}/{}", remote, branch);
+    let response = runner.run(["git", "for-each-ref", "--format=%(objectname)", &reference])?;
+    let tip = response.body.trim();
+    if tip.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(tip.to_string()))
+}
+
+/// SHA of the commit a reference points to.
+pub fn rev_parse(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    reference: &str,
+) -> Result<String> {
+    let response = runner.run(["git", "rev-parse", reference])?;
+    Ok(response.body.trim().to_string())
+}
+
+/// Whether a local branch exists.
+pub fn branch_exists(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    branch: &str,
+) -> Result<bool> {
+    let cmd_params = [
+        "git",
+        "branch",
+        "--list",
+        "--format=%(refname:short)",
+        branch,
+    ];
+    let response = runner.run(cmd_params)?;
+    Ok(branch_names(&response.body)
+        .iter()
+        .any(|name| name == branch))
+}
+
+/// Path of the worktree that has `branch` checked out, if any.
+pub fn branch_worktree(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    branch: &str,
+) -> Result<Option<String>> {
+    let cmd_params = ["git", "worktree", "list", "--porcelain"];
+    let response = runner.run(cmd_params)?;
+    let head = format!("branch refs/heads/{}", branch);
+    let mut path = None;
+    for line in response.body.lines() {
+        if let Some(worktree) = line.strip_prefix("worktree ") {
+            path = Some(worktree.to_string());
+        } else if line == head {
+            return Ok(path);
+        }
+    }
+    Ok(None)
+}
+
+/// Points `branch` at `commit`, creating it if it does not exist.
+pub fn update_branch(runner: &impl TaskRunner, branch: &str, commit: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "branch", "-f", branch, commit];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to update git branch. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+/// Fast-forwards the branch checked out in the worktree at `path` to `commit`.
+pub fn fast_forward(runner: &impl TaskRunner, path: &str, commit: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "-C", path, "merge", "--ff-only", commit];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to fast-forward. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+/// Switch to an existing local branch.
+pub fn switch(runner: &impl TaskRunner, branch: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "checkout", branch];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to git checkout branch. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
 pub fn commit_message(
     runner: Arc<impl TaskRunner<Response = ShellResponse>>,
     commit: &Option<String>,
@@ -237,14 +498,45 @@ pub fn commit_message(
     Ok(CmdInfo::CommitMessage(response.body))
 }
 
-pub fn checkout(runner: &impl TaskRunner<Response = ShellResponse>, branch: &str) -> Result<()> {
-    let git_cmd = format!("git checkout origin/{} -b {}", branch, branch);
-    let cmd_params = ["/bin/sh", "-c", &git_cmd];
+/// Fetches a single refspec from the remote, ex. the head of a merge request
+/// into FETCH_HEAD.
+pub fn fetch_ref(runner: &impl TaskRunner, remote_alias: &str, refspec: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "fetch", remote_alias, refspec];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to git fetch. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+pub fn checkout_detached(runner: &impl TaskRunner, commit: &str) -> Result<CmdInfo> {
+    let cmd_params = ["git", "checkout", "--detach", commit];
     runner.run(cmd_params).err_context(format!(
-        "Failed to git checkout remote branch. Command: {}",
+        "Failed to git checkout. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(CmdInfo::Ignore)
+}
+
+/// Checks out `commit` into a new worktree at `path`. `commit` is a local
+/// branch unless `detach` is set.
+pub fn worktree_add(
+    runner: &impl TaskRunner,
+    path: &str,
+    commit: &str,
+    detach: bool,
+) -> Result<CmdInfo> {
+    let mut cmd_params = vec!["git", "worktree", "add"];
+    if detach {
+        cmd_params.push("--detach");
+    }
+    cmd_params.push(path);
+    cmd_params.push(commit);
+    runner.run(&cmd_params).err_context(format!(
+        "Failed to add git worktree. Command: {}",
         cmd_params.join(" ")
     ))?;
-    Ok(())
+    Ok(CmdInfo::Ignore)
 }
 
 /// Repo represents a local git repository
@@ -557,6 +849,132 @@ mod tests {
         assert_eq!("git push origin new_feature", *runner.cmd());
     }
 
+    #[test]
+    fn test_git_push_with_lease_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        push_with_lease(&runner, "origin", "feature", "origin/feature").unwrap();
+        assert_eq!(
+            "git push --force-with-lease=feature:origin/feature origin feature",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_remote_branch_tip() {
+        let response = ShellResponse::builder()
+            .body("3ec4f8d\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert_eq!(
+            Some("3ec4f8d".to_string()),
+            remote_branch_tip(&runner, "origin", "feature").unwrap()
+        );
+        assert_eq!(
+            "git for-each-ref --format=%(objectname) refs/remotes/origin/feature",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_remote_branch_tip_not_pushed_is_none() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert_eq!(
+            None,
+            remote_branch_tip(&runner, "origin", "feature").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_config_value_not_set_is_none() {
+        let response = ShellResponse::builder()
+            .body("\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert_eq!(
+            None,
+            config_value(&runner, "branch.feature.grlease").unwrap()
+        );
+        assert_eq!(
+            "git config --get --default= branch.feature.grlease",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_config_value() {
+        let response = ShellResponse::builder()
+            .body("3ec4f8d\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert_eq!(
+            Some("3ec4f8d".to_string()),
+            config_value(&runner, "branch.feature.grlease").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_branch_exists() {
+        let response = ShellResponse::builder()
+            .body("mr/23/feature\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert!(branch_exists(&runner, "mr/23/feature").unwrap());
+        assert_eq!(
+            "git branch --list --format=%(refname:short) mr/23/feature",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_branch_does_not_exist() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert!(!branch_exists(&runner, "mr/23/feature").unwrap());
+    }
+
+    #[test]
+    fn test_branch_worktree_finds_checked_out_branch() {
+        let body = "worktree /home/user/repo\nHEAD 1111111\nbranch refs/heads/main\n\n\
+                    worktree /home/user/review\nHEAD 2222222\nbranch refs/heads/mr/23/feature\n\n\
+                    worktree /home/user/detached\nHEAD 3333333\ndetached\n";
+        let response = ShellResponse::builder()
+            .body(body.to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response.clone(), response]);
+        assert_eq!(
+            Some("/home/user/review".to_string()),
+            branch_worktree(&runner, "mr/23/feature").unwrap()
+        );
+        assert_eq!(None, branch_worktree(&runner, "mr/23").unwrap());
+        assert_eq!("git worktree list --porcelain", *runner.cmd());
+    }
+
+    #[test]
+    fn test_update_branch_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        update_branch(&runner, "mr/23/feature", "abc123").unwrap();
+        assert_eq!("git branch -f mr/23/feature abc123", *runner.cmd());
+    }
+
+    #[test]
+    fn test_fast_forward_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        fast_forward(&runner, "/home/user/review", "abc123").unwrap();
+        assert_eq!(
+            "git -C /home/user/review merge --ff-only abc123",
+            *runner.cmd()
+        );
+    }
+
     #[test]
     fn test_git_push_cmd_fails() {
         let response = ShellResponse::builder()
@@ -643,6 +1061,19 @@ mod tests {
         assert_eq!(expected_cmd, *runner.cmd());
     }
 
+    #[test]
+    fn test_outgoing_files() {
+        let response = ShellResponse::builder()
+            .body("src/main.rs\nREADME.md\n\nsrc/main.rs\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        let files = outgoing_files(&runner, "origin", "main").unwrap();
+        assert_eq!(vec!["src/main.rs", "README.md"], files);
+        let expected_cmd = "git log origin/main.. --name-only --pretty=format:".to_string();
+        assert_eq!(expected_cmd, *runner.cmd());
+    }
+
     #[test]
     fn test_outgoing_commits_cmd_error_no_summary_option() {
         let response = ShellResponse::builder().build().unwrap();
@@ -726,4 +1157,82 @@ mod tests {
         let runner = MockRunner::new(vec![response]);
         assert!(commit(&runner, "Add README").is_err());
     }
+
+    #[test]
+    fn test_git_fetch_ref_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        fetch_ref(&runner, "origin", "refs/pull/23/head").unwrap();
+        assert_eq!("git fetch origin refs/pull/23/head", *runner.cmd());
+    }
+
+    #[test]
+    fn test_git_worktree_add_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        worktree_add(&runner, "../review", "FETCH_HEAD", true).unwrap();
+        assert_eq!(
+            "git worktree add --detach ../review FETCH_HEAD",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_git_rebase_onto_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        rebase_onto(&runner, "origin/main", "abc123", "feature-2").unwrap();
+        assert_eq!(
+            "git rebase --onto origin/main abc123 feature-2",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_merged_branches() {
+        let response = ShellResponse::builder()
+            .body("feature-1\nfeature-2\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        let branches = merged_branches(&runner, "feature-2").unwrap();
+        assert_eq!(vec!["feature-1", "feature-2"], branches);
+        assert_eq!(
+            "git branch --format=%(refname:short) --merged feature-2",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_containing_branches_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        let branches = containing_branches(&runner, "feature-1").unwrap();
+        assert!(branches.is_empty());
+        assert_eq!(
+            "git branch --format=%(refname:short) --contains feature-1",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_count_commits() {
+        let response = ShellResponse::builder()
+            .body("3".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert_eq!(3, count_commits(&runner, "origin/main", "feature").unwrap());
+        assert_eq!("git rev-list --count origin/main..feature", *runner.cmd());
+    }
+
+    #[test]
+    fn test_count_commits_unexpected_output_is_err() {
+        let response = ShellResponse::builder()
+            .body("fatal".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert!(count_commits(&runner, "origin/main", "feature").is_err());
+    }
 }
@@ -1,24 +1,34 @@
 use super::Github;
-use crate::api_traits::{ApiOperation, CicdJob, CicdRunner, NumberDeltaErr};
+use crate::api_traits::{
+    ApiOperation, CicdJob, CicdJobArtifact, CicdRunner, NumberDeltaErr, Timestamp,
+};
 use crate::cmds::cicd::{
-    Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, RunnerListBodyArgs,
-    RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse, YamlBytes,
+    Job, JobArtifact, JobArtifactBodyArgs, JobListBodyArgs, JobLogBodyArgs, LintResponse, Pipeline,
+    PipelineBodyArgs, PipelineCreateBodyArgs, Runner, RunnerListBodyArgs, RunnerMetadata,
+    RunnerPostDataCliArgs, RunnerRegistrationResponse, RunnerStatus, RunnerType, YamlBytes,
 };
-use crate::remote::query;
+use crate::display::{self, DisplayBody};
+use crate::error::GRError;
+use crate::http::{self, Body};
+use crate::remote::{query, ListBodyArgs};
 use crate::{
     api_traits::Cicd,
     io::{HttpResponse, HttpRunner},
 };
-use crate::{http, time, Result};
+use crate::{error, time, Result};
+use std::io::Write;
 
 impl<R: HttpRunner<Response = HttpResponse>> Cicd for Github<R> {
     fn list(&self, args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
         // Doc:
         // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#list-workflow-runs-for-a-repository
-        let url = format!(
+        let mut url = format!(
             "{}/repos/{}/actions/runs",
             self.rest_api_basepath, self.path
         );
+        if let Some(branch) = &args.ref_name {
+            url.push_str(&format!("?branch={}", http::encode_query_value(branch)));
+        }
         query::paged(
             &self.runner,
             &url,
@@ -30,8 +40,21 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Github<R> {
         )
     }
 
-    fn get_pipeline(&self, _id: i64) -> Result<Pipeline> {
-        todo!()
+    fn get_pipeline(&self, id: i64) -> Result<Pipeline> {
+        // Doc:
+        // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#get-a-workflow-run
+        let url = format!(
+            "{}/repos/{}/actions/runs/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            |value| GithubPipelineFields::from(value).into(),
+        )
     }
 
     fn num_pages(&self) -> Result<Option<u32>> {
@@ -45,7 +68,94 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Github<R> {
     }
 
     fn lint(&self, _body: YamlBytes) -> Result<LintResponse> {
-        todo!()
+        Err(GRError::OperationNotSupported(
+            "Github does not provide an API to lint workflow files".to_string(),
+        )
+        .into())
+    }
+
+    fn create(&self, args: PipelineCreateBodyArgs) -> Result<Option<Pipeline>> {
+        // Doc:
+        // https://docs.github.com/en/rest/actions/workflows?apiVersion=2022-11-28#create-a-workflow-dispatch-event
+        let workflow = args.workflow.ok_or_else(|| {
+            error::gen("Github requires a workflow to dispatch. Use --workflow <FILE_NAME|ID>")
+        })?;
+        let url = format!(
+            "{}/repos/{}/actions/workflows/{}/dispatches",
+            self.rest_api_basepath, self.path, workflow
+        );
+        let mut body = Body::new();
+        body.add("ref", serde_json::Value::String(args.ref_name));
+        if !args.variables.is_empty() {
+            let inputs = args
+                .variables
+                .into_iter()
+                .map(|(key, value)| (key, serde_json::Value::String(value)))
+                .collect();
+            body.add("inputs", serde_json::Value::Object(inputs));
+        }
+        let response = query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        // Responds with a 204 No Content. The workflow run that gets
+        // triggered is not part of the response. A 422 is returned if the
+        // workflow has no workflow_dispatch trigger or the inputs are invalid,
+        // which POST also accepts.
+        if response.status != 204 {
+            return Err(query::query_error(&url, &response).into());
+        }
+        Ok(None)
+    }
+
+    fn retry(&self, id: i64) -> Result<()> {
+        // Doc:
+        // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#re-run-failed-jobs-from-a-workflow-run
+        let url = format!(
+            "{}/repos/{}/actions/runs/{}/rerun-failed-jobs",
+            self.rest_api_basepath, self.path, id
+        );
+        let response = query::send_raw::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        // POST also accepts 409 and 422 responses, ex. a workflow run that
+        // cannot be retried. Github responds 201 Created on success.
+        if response.status != 201 {
+            return Err(query::query_error(&url, &response).into());
+        }
+        Ok(())
+    }
+
+    fn cancel(&self, id: i64) -> Result<()> {
+        // Doc:
+        // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#cancel-a-workflow-run
+        let url = format!(
+            "{}/repos/{}/actions/runs/{}/cancel",
+            self.rest_api_basepath, self.path, id
+        );
+        let response = query::send_raw::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        // Github responds 202 Accepted on success and 409 Conflict if the
+        // workflow run has already completed, which POST also accepts.
+        if response.status != 202 {
+            return Err(query::query_error(&url, &response).into());
+        }
+        Ok(())
     }
 }
 
@@ -58,44 +168,606 @@ impl<R> Github<R> {
         let headers = self.request_headers();
         (url, headers)
     }
+
+    fn resource_jobs_metadata_url(&self, args: &JobListBodyArgs) -> (String, http::Headers) {
+        match args.pipeline_id {
+            Some(run_id) => (
+                format!(
+                    "{}/repos/{}/actions/runs/{}/jobs?page=1",
+                    self.rest_api_basepath, self.path, run_id
+                ),
+                self.request_headers(),
+            ),
+            None => self.resource_cicd_metadata_url(),
+        }
+    }
+
+    fn owner(&self) -> &str {
+        self.path.split('/').next().unwrap_or_default()
+    }
+
+    /// Self-hosted runners registered in the repository, or in the
+    /// organization that owns it if `org` is set.
+    fn runners_url(&self, org: bool) -> String {
+        if org {
+            format!(
+                "{}/orgs/{}/actions/runners",
+                self.rest_api_basepath,
+                self.owner()
+            )
+        } else {
+            format!(
+                "{}/repos/{}/actions/runners",
+                self.rest_api_basepath, self.path
+            )
+        }
+    }
 }
 
 impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Github<R> {
-    fn list(&self, _args: RunnerListBodyArgs) -> Result<Vec<crate::cmds::cicd::Runner>> {
-        todo!();
+    // Doc:
+    // https://docs.github.com/en/rest/actions/self-hosted-runners?apiVersion=2022-11-28#list-self-hosted-runners-for-a-repository
+    // https://docs.github.com/en/rest/actions/self-hosted-runners?apiVersion=2022-11-28#list-self-hosted-runners-for-an-organization
+    fn list(&self, args: RunnerListBodyArgs) -> Result<Vec<Runner>> {
+        let status = match args.status {
+            RunnerStatus::Online => Some(true),
+            RunnerStatus::Offline => Some(false),
+            RunnerStatus::All => None,
+            status => {
+                return Err(GRError::OperationNotSupported(format!(
+                    "Github runners can only be online or offline, got: {}",
+                    status
+                ))
+                .into())
+            }
+        };
+        let tags = args
+            .tags
+            .as_ref()
+            .map(|tags| tags.split(',').map(|tag| tag.trim()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        // Status and tags are not filters in the API, runners are filtered
+        // once retrieved. Flushing is disabled, so only filtered runners get
+        // displayed.
+        let list_args = args.list_args.map(|mut list_args| {
+            list_args.flush = false;
+            list_args
+        });
+        let org = args.all;
+        let runners = query::paged(
+            &self.runner,
+            &self.runners_url(org),
+            list_args,
+            self.request_headers(),
+            Some("runners"),
+            ApiOperation::Pipeline,
+            |value| {
+                let mut fields = GithubRunnerFields::from(value);
+                fields.runner.is_shared = org;
+                fields
+            },
+        )?;
+        Ok(runners
+            .into_iter()
+            .filter(|fields| status.is_none() || status == Some(fields.runner.online))
+            .filter(|fields| {
+                tags.iter()
+                    .all(|tag| fields.labels.iter().any(|l| l == tag))
+            })
+            .map(|fields| fields.into())
+            .collect())
+    }
+
+    // Doc:
+    // https://docs.github.com/en/rest/actions/self-hosted-runners?apiVersion=2022-11-28#get-a-self-hosted-runner-for-a-repository
+    fn get(&self, id: i64) -> Result<RunnerMetadata> {
+        let url = format!("{}/{}", self.runners_url(false), id);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            |value| GithubRunnerMetadataFields::from(value).into(),
+        )
+    }
+
+    fn num_pages(&self, args: RunnerListBodyArgs) -> Result<Option<u32>> {
+        let url = format!("{}?page=1", self.runners_url(args.all));
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )
+    }
+
+    fn num_resources(&self, args: RunnerListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = format!("{}?page=1", self.runners_url(args.all));
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )
     }
 
-    fn get(&self, _id: i64) -> Result<RunnerMetadata> {
-        todo!();
+    /// Github does not create runners through its REST API. Instead, a
+    /// registration token is requested that is used when configuring the
+    /// self-hosted runner on the host. Description and tags (labels) are set
+    /// at that time, so they are ignored here.
+    /// Doc:
+    /// https://docs.github.com/en/rest/actions/self-hosted-runners?apiVersion=2022-11-28#create-a-registration-token-for-a-repository
+    fn create(&self, args: RunnerPostDataCliArgs) -> Result<RunnerRegistrationResponse> {
+        let org = match args.kind {
+            RunnerType::Project => false,
+            RunnerType::Group => true,
+            RunnerType::Instance => {
+                return Err(GRError::OperationNotSupported(
+                    "Github instance runners are managed at the enterprise level".to_string(),
+                )
+                .into())
+            }
+        };
+        let url = format!("{}/registration-token", self.runners_url(org));
+        query::send::<_, String, _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            |value| GithubRunnerRegistrationFields::from(value).into(),
+            http::Method::POST,
+        )
     }
+}
+
+#[derive(Clone)]
+pub struct GithubRunnerFields {
+    runner: Runner,
+    labels: Vec<String>,
+}
 
-    fn num_pages(&self, _args: RunnerListBodyArgs) -> Result<Option<u32>> {
-        todo!();
+impl From<&serde_json::Value> for GithubRunnerFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let status = data["status"].as_str().unwrap_or_default();
+        GithubRunnerFields {
+            runner: Runner::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                // Github runners cannot be paused.
+                .active(true)
+                .description("".to_string())
+                .ip_address("".to_string())
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                .online(status == "online")
+                .paused(false)
+                .is_shared(false)
+                .runner_type("self-hosted".to_string())
+                .status(status.to_string())
+                .build()
+                .unwrap(),
+            labels: github_runner_labels(data, |_| true),
+        }
     }
+}
+
+impl From<GithubRunnerFields> for Runner {
+    fn from(fields: GithubRunnerFields) -> Self {
+        fields.runner
+    }
+}
+
+impl From<GithubRunnerFields> for DisplayBody {
+    fn from(fields: GithubRunnerFields) -> Self {
+        fields.runner.into()
+    }
+}
+
+impl Timestamp for GithubRunnerFields {
+    fn created_at(&self) -> String {
+        self.runner.created_at()
+    }
+}
+
+/// Names of the runner's labels that pass the filter. Labels have a type,
+/// `read-only` labels are assigned by Github (os, architecture and
+/// `self-hosted`) and `custom` labels by the user.
+fn github_runner_labels(data: &serde_json::Value, filter: impl Fn(&str) -> bool) -> Vec<String> {
+    data["labels"]
+        .as_array()
+        .map(|labels| {
+            labels
+                .iter()
+                .filter(|label| filter(label["type"].as_str().unwrap_or_default()))
+                .map(|label| label["name"].as_str().unwrap_or_default().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub struct GithubRunnerMetadataFields {
+    metadata: RunnerMetadata,
+}
+
+impl From<&serde_json::Value> for GithubRunnerMetadataFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let architecture = github_runner_labels(data, |kind| kind == "read-only")
+            .into_iter()
+            .find(|label| matches!(label.as_str(), "X64" | "X86" | "ARM64" | "ARM"))
+            .unwrap_or_default();
+        GithubRunnerMetadataFields {
+            metadata: RunnerMetadata::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                // Jobs are picked up by matching labels, there are no
+                // untagged jobs in Github.
+                .run_untagged(false)
+                .tag_list(github_runner_labels(data, |_| true))
+                .version("".to_string())
+                .architecture(architecture)
+                .platform(data["os"].as_str().unwrap_or_default().to_string())
+                .contacted_at("".to_string())
+                .revision("".to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubRunnerMetadataFields> for RunnerMetadata {
+    fn from(fields: GithubRunnerMetadataFields) -> Self {
+        fields.metadata
+    }
+}
+
+pub struct GithubRunnerRegistrationFields {
+    response: RunnerRegistrationResponse,
+}
+
+impl From<&serde_json::Value> for GithubRunnerRegistrationFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GithubRunnerRegistrationFields {
+            response: RunnerRegistrationResponse::builder()
+                // The runner does not exist until it is configured with the
+                // registration token.
+                .id(0)
+                .token(data["token"].as_str().unwrap_or_default().to_string())
+                .token_expiration(data["expires_at"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
 
-    fn num_resources(&self, _args: RunnerListBodyArgs) -> Result<Option<NumberDeltaErr>> {
-        todo!()
+impl From<GithubRunnerRegistrationFields> for RunnerRegistrationResponse {
+    fn from(fields: GithubRunnerRegistrationFields) -> Self {
+        fields.response
     }
+}
 
-    fn create(&self, _args: RunnerPostDataCliArgs) -> Result<RunnerRegistrationResponse> {
-        todo!()
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    // Doc:
+    // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#list-jobs-for-a-workflow-run
+    fn list_run_jobs(&self, run_id: i64, list_args: Option<ListBodyArgs>) -> Result<Vec<Job>> {
+        let url = format!(
+            "{}/repos/{}/actions/runs/{}/jobs",
+            self.rest_api_basepath, self.path, run_id
+        );
+        query::paged(
+            &self.runner,
+            &url,
+            list_args,
+            self.request_headers(),
+            Some("jobs"),
+            ApiOperation::Pipeline,
+            |value| GithubJobFields::from(value).into(),
+        )
     }
 }
 
 impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Github<R> {
-    fn list(&self, _args: JobListBodyArgs) -> Result<Vec<Job>> {
-        todo!();
+    /// Github has no project wide listing of jobs, jobs belong to workflow
+    /// runs. The workflow runs in the requested pages are listed and their
+    /// jobs gathered, so pagination options apply to workflow runs, or to the
+    /// jobs of the workflow run if one is given. Jobs cannot be filtered by
+    /// status server-side.
+    fn list(&self, args: JobListBodyArgs) -> Result<Vec<Job>> {
+        let flush = args
+            .list_args
+            .as_ref()
+            .map(|list_args| (list_args.flush, list_args.get_args.clone()));
+        // Jobs are flushed once filtered by status.
+        let list_args = args.list_args.clone().map(|mut list_args| {
+            list_args.flush = false;
+            list_args
+        });
+        if let Some(run_id) = args.pipeline_id {
+            let jobs = self
+                .list_run_jobs(run_id, list_args)?
+                .into_iter()
+                .filter(|job| args.status_matches(job))
+                .collect::<Vec<_>>();
+            return match flush {
+                Some((true, get_args)) => {
+                    display::print(&mut std::io::stdout(), jobs, get_args)?;
+                    Ok(Vec::new())
+                }
+                _ => Ok(jobs),
+            };
+        }
+        let url = format!(
+            "{}/repos/{}/actions/runs",
+            self.rest_api_basepath, self.path
+        );
+        let runs: Vec<Pipeline> = query::paged(
+            &self.runner,
+            &url,
+            list_args,
+            self.request_headers(),
+            Some("workflow_runs"),
+            ApiOperation::Pipeline,
+            |value| GithubPipelineFields::from(value).into(),
+        )?;
+        let mut jobs = Vec::new();
+        for run in runs {
+            let run_jobs = self
+                .list_run_jobs(run.id, None)?
+                .into_iter()
+                .filter(|job| args.status_matches(job))
+                .collect::<Vec<_>>();
+            match &flush {
+                Some((true, get_args)) => {
+                    display::print(&mut std::io::stdout(), run_jobs, get_args.clone())?
+                }
+                _ => jobs.extend(run_jobs),
+            }
+        }
+        Ok(jobs)
     }
 
-    fn num_pages(&self, _args: JobListBodyArgs) -> Result<Option<u32>> {
-        todo!();
+    fn get(&self, id: i64) -> Result<Job> {
+        // Doc:
+        // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#get-a-job-for-a-workflow-run
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            |value| GithubJobFields::from(value).into(),
+        )
+    }
+
+    fn log(&self, args: JobLogBodyArgs) -> Result<String> {
+        // Doc:
+        // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#download-job-logs-for-a-workflow-run
+        // Logs are not available while the job is in progress, the endpoint
+        // responds with a 404 until then.
+        if !CicdJob::get(self, args.id)?.is_finished() {
+            return Ok(String::new());
+        }
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}/logs",
+            self.rest_api_basepath, self.path, args.id
+        );
+        // Responds with a 302 redirect to a short lived URL to download the
+        // logs, which is followed by the HTTP client.
+        let response = query::get_raw_with_backoff(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            args.backoff_max_retries,
+            args.backoff_retry_after,
+        )?;
+        Ok(response.body)
+    }
+
+    fn num_pages(&self, args: JobListBodyArgs) -> Result<Option<u32>> {
+        let (url, headers) = self.resource_jobs_metadata_url(&args);
+        query::num_pages(&self.runner, &url, headers, ApiOperation::Pipeline)
     }
 
     fn num_resources(
         &self,
-        _args: JobListBodyArgs,
+        args: JobListBodyArgs,
     ) -> Result<Option<crate::api_traits::NumberDeltaErr>> {
-        todo!();
+        let (url, headers) = self.resource_jobs_metadata_url(&args);
+        query::num_resources(&self.runner, &url, headers, ApiOperation::Pipeline)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    // Artifacts in Github belong to the workflow run, not to the job. Resolve
+    // the run the job belongs to and list its artifacts.
+    fn run_artifacts(&self, job_id: i64) -> Result<Vec<serde_json::Value>> {
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}",
+            self.rest_api_basepath, self.path, job_id
+        );
+        let job = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        // Doc:
+        // https://docs.github.com/en/rest/actions/artifacts?apiVersion=2022-11-28#list-workflow-run-artifacts
+        let url = format!(
+            "{}/repos/{}/actions/runs/{}/artifacts",
+            self.rest_api_basepath,
+            self.path,
+            job["run_id"].as_i64().unwrap_or_default()
+        );
+        let artifacts = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        Ok(artifacts["artifacts"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdJobArtifact for Github<R> {
+    fn list(&self, job_id: i64) -> Result<Vec<JobArtifact>> {
+        Ok(self
+            .run_artifacts(job_id)?
+            .iter()
+            .map(|value| GithubJobArtifactFields::from(value).into())
+            .collect())
+    }
+
+    fn download(&self, args: JobArtifactBodyArgs, writer: &mut dyn Write) -> Result<()> {
+        let artifacts = self.run_artifacts(args.job_id)?;
+        let artifact = match args.path {
+            Some(name) => artifacts
+                .iter()
+                .find(|artifact| artifact["name"].as_str() == Some(name.as_str()))
+                .ok_or_else(|| {
+                    error::gen(format!(
+                        "Artifact {} not found for job {}",
+                        name, args.job_id
+                    ))
+                })?,
+            None if artifacts.len() == 1 => &artifacts[0],
+            None if artifacts.is_empty() => {
+                return Err(error::gen(format!(
+                    "No artifacts found for job {}",
+                    args.job_id
+                )))
+            }
+            None => {
+                let names = artifacts
+                    .iter()
+                    .map(|artifact| artifact["name"].as_str().unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(error::gen(format!(
+                    "Multiple artifacts found for job {}, select one with --path: {}",
+                    args.job_id, names
+                )));
+            }
+        };
+        // Doc:
+        // https://docs.github.com/en/rest/actions/artifacts?apiVersion=2022-11-28#download-an-artifact
+        // Responds with a 302 redirect to a short lived URL to download the
+        // zip archive, which is followed by the HTTP client.
+        let url = format!(
+            "{}/repos/{}/actions/artifacts/{}/zip",
+            self.rest_api_basepath,
+            self.path,
+            artifact["id"].as_i64().unwrap_or_default()
+        );
+        query::download(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            writer,
+        )?;
+        Ok(())
+    }
+
+    fn downloads_archive(&self, _path: Option<&str>) -> bool {
+        // Artifacts are always downloaded as zip archives.
+        true
+    }
+}
+
+pub struct GithubJobArtifactFields {
+    artifact: JobArtifact,
+}
+
+impl From<&serde_json::Value> for GithubJobArtifactFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GithubJobArtifactFields {
+            artifact: JobArtifact::builder()
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                // Github artifacts are always zip archives.
+                .file_type("archive".to_string())
+                .size(data["size_in_bytes"].as_u64().unwrap_or_default())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubJobArtifactFields> for JobArtifact {
+    fn from(fields: GithubJobArtifactFields) -> Self {
+        fields.artifact
+    }
+}
+
+pub struct GithubJobFields {
+    job: Job,
+}
+
+impl From<&serde_json::Value> for GithubJobFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let started_at = data["started_at"].as_str().unwrap_or_default();
+        let completed_at = data["completed_at"].as_str().unwrap_or_default();
+        let duration = if !started_at.is_empty() && !completed_at.is_empty() {
+            time::compute_duration(started_at, completed_at)
+        } else {
+            0
+        };
+        GithubJobFields {
+            job: Job::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                .branch(data["head_branch"].as_str().unwrap_or_default().to_string())
+                .url(data["html_url"].as_str().unwrap_or_default().to_string())
+                // Jobs are not linked to a user in Github.
+                .author_name("".to_string())
+                .commit_sha(data["head_sha"].as_str().unwrap_or_default().to_string())
+                .pipeline_id(data["run_id"].as_i64().unwrap_or_default())
+                .runner_tags(
+                    data["labels"]
+                        .as_array()
+                        .map(|labels| {
+                            labels
+                                .iter()
+                                .map(|v| v.as_str().unwrap_or_default().to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .stage(
+                    data["workflow_name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                // Same as in workflow runs, conclusion is the final state
+                // and it is not present while the job is running.
+                .status(
+                    data["conclusion"]
+                        .as_str()
+                        .unwrap_or_else(|| data["status"].as_str().unwrap_or("unknown"))
+                        .to_string(),
+                )
+                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
+                .started_at(started_at.to_string())
+                .finished_at(completed_at.to_string())
+                .duration(duration.to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubJobFields> for Job {
+    fn from(fields: GithubJobFields) -> Self {
+        fields.job
     }
 }
 
@@ -150,7 +822,6 @@ impl From<GithubPipelineFields> for Pipeline {
 mod test {
 
     use crate::{
-        error,
         http::Headers,
         remote::ListBodyArgs,
         setup_client,
@@ -158,6 +829,7 @@ mod test {
     };
 
     use super::*;
+    use crate::cmds::cicd::JobStatus;
 
     #[test]
     fn test_list_actions() {
@@ -273,6 +945,26 @@ mod test {
         assert_eq!(Some(1), github.num_pages().unwrap());
     }
 
+    #[test]
+    fn test_list_actions_by_branch() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_pipelines.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let args = PipelineBodyArgs::builder()
+            .from_to_page(None)
+            .ref_name(Some("fix/a&b".to_string()))
+            .build()
+            .unwrap();
+        github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs?branch=fix%2Fa%26b",
+            *client.url(),
+        );
+    }
+
     #[test]
     fn test_list_actions_from_page_set_in_url() {
         let contracts = ResponseContracts::new(ContractType::Github).add_contract(
@@ -342,4 +1034,510 @@ mod test {
         let runs = github.list(args).unwrap();
         assert_eq!("unknown", runs[0].status);
     }
+
+    #[test]
+    fn test_create_workflow_dispatch() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(204, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .variables(vec![("environment".to_string(), "staging".to_string())])
+            .workflow(Some("deploy.yml".to_string()))
+            .build()
+            .unwrap();
+        assert!(github.create(args).unwrap().is_none());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/workflows/deploy.yml/dispatches",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+        let body = client.request_body.borrow();
+        assert!(body.contains(r#""ref":"main""#));
+        assert!(body.contains(r#""inputs":{"environment":"staging"}"#));
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_workflow_dispatch_rejected_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            422,
+            Some(r#"{"message": "Workflow does not have 'workflow_dispatch' trigger"}"#),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .workflow(Some("build.yml".to_string()))
+            .build()
+            .unwrap();
+        let err = github.create(args).unwrap_err();
+        assert!(err.to_string().contains("workflow_dispatch"));
+    }
+
+    #[test]
+    fn test_create_workflow_dispatch_requires_workflow() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .build()
+            .unwrap();
+        assert!(github.create(args).is_err());
+    }
+
+    #[test]
+    fn test_rerun_failed_jobs_workflow_run() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(201, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        github.retry(8139128296).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/8139128296/rerun-failed-jobs",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_rerun_failed_jobs_workflow_run_rejected_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            403,
+            Some(r#"{"message": "This workflow run cannot be rerun"}"#),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        assert!(github.retry(8139128296).is_err());
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            422,
+            Some(r#"{"message": "Unprocessable Entity"}"#),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        assert!(github.retry(8139128296).is_err());
+    }
+
+    #[test]
+    fn test_cancel_workflow_run() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(202, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        github.cancel(8139128296).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/8139128296/cancel",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_cancel_workflow_run_already_completed_is_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(409, None, None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        assert!(github.cancel(8139128296).is_err());
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(422, None, None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        assert!(github.cancel(8139128296).is_err());
+    }
+
+    #[test]
+    fn test_get_workflow_job() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(200, "job.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let job = github.get(22374524870).unwrap();
+        assert!(job.is_finished());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/22374524870",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_workflow_job_log() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some("2024-03-04T06:30:17.0Z cargo test\n".to_string()),
+                None,
+            )
+            .add_contract(200, "job.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobLogBodyArgs::builder().id(22374524870).build().unwrap();
+        let log = github.log(args).unwrap();
+        assert_eq!("2024-03-04T06:30:17.0Z cargo test\n", log);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/22374524870/logs",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_get_workflow_job_log_in_progress_is_empty() {
+        let contract_json = get_contract(ContractType::Github, "job.json")
+            .replace(r#""status": "completed""#, r#""status": "in_progress""#)
+            .replace(r#""conclusion": "success""#, r#""conclusion": null"#);
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some(contract_json), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobLogBodyArgs::builder().id(22374524870).build().unwrap();
+        assert_eq!("", github.log(args).unwrap());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/22374524870",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_workflow_job_artifacts() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_run_artifacts.json", None)
+            .add_contract(200, "job.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJobArtifact);
+        let artifacts = github.list(22374524870).unwrap();
+        assert_eq!(2, artifacts.len());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/8139128296/artifacts",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_download_workflow_job_artifact_by_name() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some("PK\u{3}\u{4}".to_string()), None)
+            .add_contract(200, "list_run_artifacts.json", None)
+            .add_contract(200, "job.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJobArtifact);
+        let args = JobArtifactBodyArgs::builder()
+            .job_id(22374524870)
+            .path(Some("coverage".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        github.download(args, &mut buf).unwrap();
+        assert_eq!(b"PK\x03\x04".to_vec(), buf);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/artifacts/1284951377/zip",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_download_workflow_job_artifact_ambiguous_requires_name() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_run_artifacts.json", None)
+            .add_contract(200, "job.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn CicdJobArtifact);
+        let args = JobArtifactBodyArgs::builder()
+            .job_id(22374524870)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        let err = github.download(args, &mut buf).unwrap_err();
+        assert!(err.to_string().contains("coverage"));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_download_workflow_job_artifact_not_found() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_run_artifacts.json", None)
+            .add_contract(200, "job.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn CicdJobArtifact);
+        let args = JobArtifactBodyArgs::builder()
+            .job_id(22374524870)
+            .path(Some("docs".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        assert!(github.download(args, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_get_workflow_run() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "workflow_run.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let run = github.get_pipeline(7881917826).unwrap();
+        assert_eq!(7881917826, run.id);
+        assert_eq!("success", run.status);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/7881917826",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_lint_workflow_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let result = github.lint(YamlBytes::new(b"on: push"));
+        match result.err().unwrap().downcast_ref::<GRError>() {
+            Some(GRError::OperationNotSupported(_)) => (),
+            _ => panic!("Expected GRError::OperationNotSupported"),
+        }
+    }
+
+    #[test]
+    fn test_list_jobs_gathers_jobs_from_workflow_runs() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_run_jobs.json", None)
+            .add_contract(200, "list_pipelines.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        let jobs = github.list(args).unwrap();
+        assert_eq!(2, jobs.len());
+        assert!(jobs[0].is_finished());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/7881917826/jobs",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_list_workflow_run_jobs_filtered_by_status() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_run_jobs.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobListBodyArgs::builder()
+            .list_args(None)
+            .pipeline_id(Some(8139128296))
+            .status(vec![JobStatus::Failed])
+            .build()
+            .unwrap();
+        let jobs = github.list(args).unwrap();
+        assert_eq!(1, jobs.len());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/8139128296/jobs",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_workflow_run_jobs_from_page() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_run_jobs.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobListBodyArgs::builder()
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .page(2)
+                    .max_pages(1)
+                    .build()
+                    .unwrap(),
+            ))
+            .pipeline_id(Some(8139128296))
+            .build()
+            .unwrap();
+        github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/8139128296/jobs?page=2",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_jobs_no_workflow_runs() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+            200,
+            Some(r#"{"workflow_runs":[]}"#.to_string()),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        assert!(github.list(args).unwrap().is_empty());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_jobs_num_pages_are_workflow_runs_pages() {
+        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/actions/runs?page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/actions/runs?page=3>; rel="last""#;
+        let mut headers = Headers::new();
+        headers.set("link", link_header);
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let args = JobListBodyArgs::builder().list_args(None).build().unwrap();
+        assert_eq!(Some(3), github.num_pages(args).unwrap());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs?page=1",
+            *client.url(),
+        );
+    }
+
+    fn runner_list_args(status: RunnerStatus, tags: Option<&str>, all: bool) -> RunnerListBodyArgs {
+        RunnerListBodyArgs::builder()
+            .list_args(None)
+            .status(status)
+            .tags(tags.map(|tags| tags.to_string()))
+            .all(all)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_list_repository_runners() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_runners.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        let runners = github
+            .list(runner_list_args(RunnerStatus::All, None, false))
+            .unwrap();
+        assert_eq!(2, runners.len());
+        assert_eq!("build-box-1", runners[0].name);
+        assert!(runners[0].online);
+        assert!(!runners[0].is_shared);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runners",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_list_organization_runners_filter_by_status() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_runners.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        let runners = github
+            .list(runner_list_args(RunnerStatus::Offline, None, true))
+            .unwrap();
+        assert_eq!(1, runners.len());
+        assert_eq!("mac-mini", runners[0].name);
+        assert!(runners[0].is_shared);
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin/actions/runners",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_runners_filter_by_tags() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_runners.json",
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        let runners = github
+            .list(runner_list_args(
+                RunnerStatus::All,
+                Some("Linux,docker"),
+                false,
+            ))
+            .unwrap();
+        assert_eq!(1, runners.len());
+        assert_eq!(23, runners[0].id);
+    }
+
+    #[test]
+    fn test_list_runners_stale_status_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        let result = github.list(runner_list_args(RunnerStatus::Stale, None, false));
+        match result.err().unwrap().downcast_ref::<GRError>() {
+            Some(GRError::OperationNotSupported(_)) => (),
+            _ => panic!("Expected GRError::OperationNotSupported"),
+        }
+    }
+
+    #[test]
+    fn test_get_runner_details() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(200, "runner.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        let metadata = github.get(23).unwrap();
+        assert_eq!(23, metadata.id);
+        assert_eq!("Linux", metadata.platform);
+        assert_eq!("X64", metadata.architecture);
+        assert_eq!(
+            vec!["self-hosted", "Linux", "X64", "docker"],
+            metadata.tag_list
+        );
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runners/23",
+            *client.url(),
+        );
+    }
+
+    fn runner_post_args(kind: RunnerType) -> RunnerPostDataCliArgs {
+        RunnerPostDataCliArgs::builder()
+            .description(None)
+            .tags(None)
+            .kind(kind)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_repository_runner_registration_token() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            201,
+            "runner_registration_token.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        let response = github
+            .create(runner_post_args(RunnerType::Project))
+            .unwrap();
+        assert_eq!("LLBF3JGZDX3P5PMEXLND6TS6FCWO6", response.token);
+        assert_eq!("2024-03-05T06:30:00.000-08:00", response.token_expiration);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runners/registration-token",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_create_organization_runner_registration_token() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            201,
+            "runner_registration_token.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        github.create(runner_post_args(RunnerType::Group)).unwrap();
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin/actions/runners/registration-token",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_create_instance_runner_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn CicdRunner);
+        assert!(github
+            .create(runner_post_args(RunnerType::Instance))
+            .is_err());
+    }
 }
@@ -0,0 +1,373 @@
+use super::Github;
+use crate::{
+    api_traits::{ApiOperation, CommentIssue, NumberDeltaErr, RemoteIssue},
+    cmds::{
+        issue::{
+            CommentIssueBodyArgs, CommentIssueListBodyArgs, Issue, IssueBodyArgs,
+            IssueListBodyArgs, IssueState,
+        },
+        merge_request::Comment,
+    },
+    http::{self, Body},
+    io::{HttpResponse, HttpRunner},
+    json_loads,
+    remote::query,
+    Result,
+};
+
+use super::merge_request::GithubMergeRequestCommentFields;
+
+// https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28
+
+impl<R> Github<R> {
+    /// Github's REST API considers every pull request an issue, so the issues
+    /// endpoint would list pull requests and count them in the number of
+    /// pages and resources. The search API can restrict the results to issues.
+    /// https://docs.github.com/en/rest/search/search?apiVersion=2022-11-28#search-issues-and-pull-requests
+    fn url_list_issues(&self, args: &IssueListBodyArgs) -> String {
+        let state = match args.state {
+            IssueState::Opened => "open",
+            IssueState::Closed => "closed",
+        };
+        format!(
+            "{}/search/issues?q=repo:{}+is:issue+is:{}",
+            self.rest_api_basepath, self.path, state
+        )
+    }
+
+    fn resource_issue_comments_metadata_url(&self, args: CommentIssueListBodyArgs) -> String {
+        format!(
+            "{}/repos/{}/issues/{}/comments?page=1",
+            self.rest_api_basepath, self.path, args.id
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> RemoteIssue for Github<R> {
+    fn create(&self, args: IssueBodyArgs) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues", self.rest_api_basepath, self.path);
+        let mut body = Body::new();
+        body.add("title", args.title);
+        body.add("body", args.description);
+        let response = query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )?;
+        // POST also accepts 422, ex. validation failed on an empty title.
+        if response.status != 201 {
+            return Err(query::query_error(&url, &response).into());
+        }
+        Ok(GithubIssueFields::from(&json_loads(&response.body)?).into())
+    }
+
+    fn list(&self, args: IssueListBodyArgs) -> Result<Vec<Issue>> {
+        let url = self.url_list_issues(&args);
+        query::paged(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.request_headers(),
+            Some("items"),
+            ApiOperation::Issue,
+            |value| GithubIssueFields::from(value).into(),
+        )
+    }
+
+    fn get(&self, id: i64) -> Result<Issue> {
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Issue,
+            |value| GithubIssueFields::from(value).into(),
+        )
+    }
+
+    fn close(&self, id: i64) -> Result<Issue> {
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let mut body = Body::new();
+        body.add("state", "closed");
+        query::send::<_, &str, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Issue,
+            |value| GithubIssueFields::from(value).into(),
+            http::Method::PATCH,
+        )
+    }
+
+    fn num_pages(&self, args: IssueListBodyArgs) -> Result<Option<u32>> {
+        let url = self.url_list_issues(&args) + "&page=1";
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+
+    fn num_resources(&self, args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.url_list_issues(&args) + "&page=1";
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CommentIssue for Github<R> {
+    fn create(&self, args: CommentIssueBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/issues/{}/comments",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    fn list(&self, args: CommentIssueListBodyArgs) -> Result<Vec<Comment>> {
+        let url = format!(
+            "{}/repos/{}/issues/{}/comments",
+            self.rest_api_basepath, self.path, args.id
+        );
+        query::paged(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.request_headers(),
+            None,
+            ApiOperation::Issue,
+            |value| GithubMergeRequestCommentFields::from(value).into(),
+        )
+    }
+
+    fn num_pages(&self, args: CommentIssueListBodyArgs) -> Result<Option<u32>> {
+        let url = self.resource_issue_comments_metadata_url(args);
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+
+    fn num_resources(&self, args: CommentIssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.resource_issue_comments_metadata_url(args);
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+}
+
+pub struct GithubIssueFields {
+    issue: Issue,
+}
+
+impl From<&serde_json::Value> for GithubIssueFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let state = match data["state"].as_str().unwrap_or_default() {
+            "open" => "opened",
+            state => state,
+        };
+        GithubIssueFields {
+            issue: Issue::builder()
+                .id(data["number"].as_i64().unwrap_or_default())
+                .title(data["title"].as_str().unwrap_or_default().to_string())
+                .state(state.to_string())
+                .author(
+                    data["user"]["login"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .web_url(data["html_url"].as_str().unwrap_or_default().to_string())
+                .description(data["body"].as_str().unwrap_or_default().to_string())
+                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
+                .updated_at(data["updated_at"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubIssueFields> for Issue {
+    fn from(fields: GithubIssueFields) -> Self {
+        fields.issue
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        http::Headers,
+        setup_client,
+        test::utils::{default_github, ContractType, ResponseContracts},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_list_issues_uses_search_api_to_exclude_pull_requests() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(
+                r#"{"total_count": 1, "items": [{"number": 24, "title": "Test issue",
+                "state": "open", "html_url": "https://github.com/jordilin/githapi/issues/24",
+                "created_at": "2024-03-01T00:00:00Z", "updated_at": "2024-03-01T00:00:00Z",
+                "user": {"login": "jdoe"}, "body": "This is a test issue"}]}"#,
+            ),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteIssue);
+        let args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let issues = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/search/issues?q=repo:jordilin/githapi+is:issue+is:open",
+            *client.url()
+        );
+        assert_eq!(1, issues.len());
+        assert_eq!(24, issues[0].id);
+        assert_eq!("opened", issues[0].state);
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(200, "issue.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteIssue);
+        let issue = github.get(24).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/24",
+            *client.url()
+        );
+        assert_eq!("Test issue", issue.title);
+        assert_eq!("jdoe", issue.author);
+        assert_eq!("https://github.com/jdoe/githapi/issues/24", issue.web_url);
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(201, "issue.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteIssue);
+        let args = IssueBodyArgs::builder()
+            .title("Test issue".to_string())
+            .description("This is a test issue".to_string())
+            .build()
+            .unwrap();
+        github.create(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+        let body = client.request_body.borrow();
+        assert!(body.contains("Test issue"));
+    }
+
+    #[test]
+    fn test_create_issue_rejected_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            422,
+            Some(r#"{"message": "Validation Failed", "errors": [{"field": "title"}]}"#),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn RemoteIssue);
+        let args = IssueBodyArgs::builder()
+            .title("".to_string())
+            .description("This is a test issue".to_string())
+            .build()
+            .unwrap();
+        let err = github.create(args).unwrap_err();
+        assert!(err.to_string().contains("Validation Failed"));
+    }
+
+    #[test]
+    fn test_close_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(200, "issue.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteIssue);
+        github.close(24).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/24",
+            *client.url()
+        );
+        assert_eq!(http::Method::PATCH, client.http_method.borrow()[0]);
+        assert!(client.request_body.borrow().contains("closed"));
+    }
+
+    #[test]
+    fn test_issues_num_pages() {
+        let link_header = r#"<https://api.github.com/search/issues?q=repo%3Ajordilin%2Fgithapi+is%3Aissue+is%3Aclosed&page=2>; rel="next", <https://api.github.com/search/issues?q=repo%3Ajordilin%2Fgithapi+is%3Aissue+is%3Aclosed&page=4>; rel="last""#;
+        let mut headers = Headers::new();
+        headers.set("link", link_header);
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteIssue);
+        let args = IssueListBodyArgs::builder()
+            .state(IssueState::Closed)
+            .list_args(None)
+            .build()
+            .unwrap();
+        assert_eq!(Some(4), github.num_pages(args).unwrap());
+        assert_eq!(
+            "https://api.github.com/search/issues?q=repo:jordilin/githapi+is:issue+is:closed&page=1",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_create_issue_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(201, "comment.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentIssue);
+        let args = CommentIssueBodyArgs::builder()
+            .id(24)
+            .comment("Cannot reproduce".to_string())
+            .build()
+            .unwrap();
+        github.create(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/24/comments",
+            *client.url()
+        );
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+}
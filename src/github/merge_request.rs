@@ -1,15 +1,24 @@
 use super::Github;
 use crate::{
-    api_traits::{ApiOperation, CommentMergeRequest, MergeRequest, NumberDeltaErr, RemoteProject},
+    api_traits::{
+        ApiOperation, CommentMergeRequest, MergeRequest, MergeRequestCommit, MergeRequestReadiness,
+        MergeRequestThread, NumberDeltaErr, RemoteProject, ReviewMergeRequest,
+    },
     cli::browse::BrowseOptions,
     cmds::{
         merge_request::{
+            commit::{combined_status, CiStatus, Commit, MergeRequestCommitListBodyArgs},
+            review::{MergeRequestReviewBodyArgs, ReviewSide, ReviewVerdict},
+            status::{Approvals, Mergeability},
+            thread::Thread,
             Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs,
-            MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestResponse,
-            MergeRequestState,
+            CommentMergeRequestUpdateBodyArgs, MergeRequestBodyArgs, MergeRequestDiff,
+            MergeRequestListBodyArgs, MergeRequestMergeBodyArgs, MergeRequestResponse,
+            MergeRequestState, MergeRequestUpdateBodyArgs, Reaction,
         },
         project::MrMemberType,
     },
+    display::{Column, DisplayBody},
     http::{self, Body},
     io::{HttpResponse, HttpRunner},
     json_loads,
@@ -20,11 +29,28 @@ use crate::{error, Result};
 
 impl<R> Github<R> {
     fn url_list_merge_requests(&self, args: &MergeRequestListBodyArgs) -> String {
+        if let Some(reviewer) = &args.reviewer {
+            return self.url_search_review_requests(args, &reviewer.username);
+        }
+        let url = self.url_list_merge_requests_by_state(args);
+        if args.labels.is_empty() {
+            return url;
+        }
+        let labels: Vec<String> = args
+            .labels
+            .iter()
+            .map(|label| http::encode_query_value(label))
+            .collect();
+        format!("{}&labels={}", url, labels.join(","))
+    }
+
+    fn url_list_merge_requests_by_state(&self, args: &MergeRequestListBodyArgs) -> String {
         let state = match args.state {
             MergeRequestState::Opened => "open".to_string(),
             // Github has no distinction between closed and merged. A merged
             // pull request is considered closed.
             MergeRequestState::Closed | MergeRequestState::Merged => "closed".to_string(),
+            MergeRequestState::All => "all".to_string(),
         };
         if args.assignee.is_some() {
             return format!(
@@ -37,9 +63,55 @@ impl<R> Github<R> {
                 self.rest_api_basepath, state
             );
         }
-        format!(
+        if !args.labels.is_empty() {
+            // The pulls API cannot filter by labels, the issues API can.
+            return format!(
+                "{}/repos/{}/issues?state={}",
+                self.rest_api_basepath, self.path, state
+            );
+        }
+        let url = format!(
             "{}/repos/{}/pulls?state={}",
             self.rest_api_basepath, self.path, state
+        );
+        match &args.source_branch {
+            // Pull requests are filtered by head in the owner:branch format.
+            Some(source_branch) => {
+                let owner = self.path.split('/').next().unwrap_or_default();
+                format!(
+                    "{}&head={}:{}",
+                    url,
+                    owner,
+                    http::encode_query_value(source_branch)
+                )
+            }
+            None => url,
+        }
+    }
+
+    /// Neither the pulls nor the issues API can filter by requested reviewer,
+    /// the search API can.
+    /// https://docs.github.com/en/search-github/searching-on-github/searching-issues-and-pull-requests#search-for-pull-requests-requested-for-review
+    fn url_search_review_requests(
+        &self,
+        args: &MergeRequestListBodyArgs,
+        username: &str,
+    ) -> String {
+        let mut query = vec![format!("repo:{}", self.path), "is:pr".to_string()];
+        match args.state {
+            MergeRequestState::Opened => query.push("is:open".to_string()),
+            MergeRequestState::Closed => query.push("is:closed+is:unmerged".to_string()),
+            MergeRequestState::Merged => query.push("is:merged".to_string()),
+            MergeRequestState::All => {}
+        }
+        query.push(format!("review-requested:{}", username));
+        for label in &args.labels {
+            query.push(format!("label:%22{}%22", http::encode_query_value(label)));
+        }
+        format!(
+            "{}/search/issues?q={}",
+            self.rest_api_basepath,
+            query.join("+")
         )
     }
 
@@ -52,6 +124,180 @@ impl<R> Github<R> {
     }
 }
 
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    /// Milestones are given by title, but pull requests reference them by
+    /// number.
+    fn milestone_number(&self, title: &str) -> Result<i64> {
+        // https://docs.github.com/en/rest/issues/milestones?apiVersion=2022-11-28#list-milestones
+        // Milestones cannot be filtered by title, go through the pages until
+        // it is found.
+        const PER_PAGE: usize = 100;
+        let mut page = 1;
+        loop {
+            let url = format!(
+                "{}/repos/{}/milestones?state=all&per_page={}&page={}",
+                self.rest_api_basepath, self.path, PER_PAGE, page
+            );
+            let milestones = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::MergeRequest,
+            )?;
+            let milestones = milestones.as_array().cloned().unwrap_or_default();
+            if let Some(number) = milestones
+                .iter()
+                .find(|milestone| milestone["title"].as_str() == Some(title))
+                .and_then(|milestone| milestone["number"].as_i64())
+            {
+                return Ok(number);
+            }
+            if milestones.len() < PER_PAGE {
+                return Err(error::GRError::PreconditionNotMet(format!(
+                    "Milestone {} not found",
+                    title
+                ))
+                .into());
+            }
+            page += 1;
+        }
+    }
+
+    /// Converts a pull request to draft or marks it as ready for review. The
+    /// REST API does not support changing the draft state of an existing pull
+    /// request, so this goes through the GraphQL API.
+    fn set_draft(&self, id: i64, draft: bool) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let pull_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        if pull_request["draft"].as_bool() == Some(draft) {
+            return Ok(());
+        }
+        let mutation = if draft {
+            "convertPullRequestToDraft"
+        } else {
+            "markPullRequestReadyForReview"
+        };
+        let query = format!(
+            "mutation($id: ID!) {{ {}(input: {{pullRequestId: $id}}) {{ clientMutationId }} }}",
+            mutation
+        );
+        let variables = serde_json::json!({ "id": pull_request["node_id"] });
+        self.graphql(query, variables).map_err(|err| {
+            error::gen(format!(
+                "Failed to update the draft state of pull request {}: {}",
+                id, err
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Source branch of a pull request. Branches in forks belong to another
+    /// repository and cannot be deleted.
+    fn source_branch(&self, id: i64) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let pull_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        if pull_request["head"]["repo"]["full_name"].as_str() != Some(self.path.as_str()) {
+            return Err(error::GRError::OperationNotSupported(format!(
+                "Cannot delete the source branch of pull request {}, it belongs to a fork",
+                id
+            ))
+            .into());
+        }
+        Ok(pull_request["head"]["ref"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Enables auto-merge, so the pull request is merged once all the required
+    /// checks pass. Only available through the GraphQL API.
+    fn enable_auto_merge(&self, args: &MergeRequestMergeBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let pull_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let query = "mutation($id: ID!, $method: PullRequestMergeMethod, $headline: String, \
+            $body: String, $sha: GitObjectID) { enablePullRequestAutoMerge(input: \
+            {pullRequestId: $id, mergeMethod: $method, commitHeadline: $headline, \
+            commitBody: $body, expectedHeadOid: $sha}) { clientMutationId } }"
+            .to_string();
+        let (headline, body) = commit_title_message(args.squash_message.as_deref());
+        let variables = serde_json::json!({
+            "id": pull_request["node_id"],
+            "method": args.merge_method.map(|method| method.to_string().to_uppercase()),
+            "headline": headline,
+            "body": body,
+            "sha": args.sha,
+        });
+        self.graphql(query, variables).map_err(|err| {
+            error::gen(format!(
+                "Failed to enable auto-merge for pull request {}: {}",
+                args.id, err
+            ))
+        })?;
+        Ok(())
+    }
+
+    fn graphql(&self, query: String, variables: serde_json::Value) -> Result<serde_json::Value> {
+        let mut body = Body::new();
+        body.add("query", serde_json::Value::from(query));
+        body.add("variables", variables);
+        let graphql_url = format!("{}/graphql", self.rest_api_basepath);
+        let response = query::send_json(
+            &self.runner,
+            &graphql_url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        // GraphQL errors are reported with a 200 status code.
+        if let Some(errors) = response["errors"].as_array() {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|error| error["message"].as_str())
+                .collect();
+            return Err(error::gen(messages.join(", ")));
+        }
+        Ok(response["data"].clone())
+    }
+}
+
+/// Splits a commit message into its title, the first line, and the rest.
+fn commit_title_message(message: Option<&str>) -> (Option<&str>, Option<&str>) {
+    match message.map(|message| message.split_once('\n')) {
+        Some(Some((title, body))) => (Some(title), Some(body.trim_start_matches('\n'))),
+        Some(None) => (message, None),
+        None => (None, None),
+    }
+}
+
 impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
     fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
         // https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#create-a-pull-request
@@ -114,55 +360,67 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
                         let body = response.body;
                         let merge_request_json = json_loads(&body)?;
                         let id = merge_request_json["number"].as_i64().unwrap();
-                        match args.assignee.mr_member_type {
-                            MrMemberType::Empty => (),
-                            MrMemberType::Filled => {
-                                // Assignee API
-                                // https://docs.github.com/en/rest/issues/issues#update-an-issue
-                                let issues_url = format!(
-                                    "{}/repos/{}/issues/{}",
-                                    self.rest_api_basepath, self.path, id
-                                );
-                                let mut body = Body::new();
-                                let assignees = vec![args.assignee.username.as_str()];
-                                body.add("assignees", &assignees);
-                                query::send_raw(
-                                    &self.runner,
-                                    &issues_url,
-                                    Some(&body),
-                                    self.request_headers(),
-                                    ApiOperation::MergeRequest,
-                                    http::Method::PATCH,
-                                )?;
-                            }
+                        // Assignee, labels and milestone API
+                        // https://docs.github.com/en/rest/issues/issues#update-an-issue
+                        let mut body = Body::new();
+                        if let MrMemberType::Filled = args.assignee.mr_member_type {
+                            body.add(
+                                "assignees",
+                                serde_json::Value::from(vec![args.assignee.username.as_str()]),
+                            );
+                        }
+                        if !args.labels.is_empty() {
+                            body.add("labels", serde_json::Value::from(args.labels.clone()));
+                        }
+                        if let Some(milestone) = &args.milestone {
+                            body.add(
+                                "milestone",
+                                serde_json::Value::from(self.milestone_number(milestone)?),
+                            );
+                        }
+                        if !body.is_empty() {
+                            let issues_url = format!(
+                                "{}/repos/{}/issues/{}",
+                                self.rest_api_basepath, self.path, id
+                            );
+                            query::send_raw(
+                                &self.runner,
+                                &issues_url,
+                                Some(&body),
+                                self.request_headers(),
+                                ApiOperation::MergeRequest,
+                                http::Method::PATCH,
+                            )?;
                         }
                         // Requested reviewers API
                         // https://docs.github.com/en/rest/pulls/review-requests?apiVersion=2022-11-28#request-reviewers-for-a-pull-request
-                        match args.reviewer.mr_member_type {
-                            MrMemberType::Empty => (),
-                            MrMemberType::Filled => {
-                                let mut body = Body::new();
-                                let reviewers = vec![args.reviewer.username.as_str()];
-                                body.add("reviewers", &reviewers);
-                                let requested_reviewers_url =
-                                    format!("{}/{}/requested_reviewers", mr_url, id);
-
-                                let response = query::send_raw(
-                                    &self.runner,
+                        let reviewers: Vec<&str> = args
+                            .reviewers
+                            .iter()
+                            .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+                            .map(|reviewer| reviewer.username.as_str())
+                            .collect();
+                        if !reviewers.is_empty() {
+                            let mut body = Body::new();
+                            body.add("reviewers", &reviewers);
+                            let requested_reviewers_url =
+                                format!("{}/{}/requested_reviewers", mr_url, id);
+
+                            let response = query::send_raw(
+                                &self.runner,
+                                &requested_reviewers_url,
+                                Some(&body),
+                                self.request_headers(),
+                                ApiOperation::MergeRequest,
+                                http::Method::POST,
+                            )?;
+                            // Consider 422 failure - Reviewer not a collaborator
+                            if response.status != 201 {
+                                return Err(query::query_error(
                                     &requested_reviewers_url,
-                                    Some(&body),
-                                    self.request_headers(),
-                                    ApiOperation::MergeRequest,
-                                    http::Method::POST,
-                                )?;
-                                // Consider 422 failure - Reviewer not a collaborator
-                                if response.status != 201 {
-                                    return Err(query::query_error(
-                                        &requested_reviewers_url,
-                                        &response,
-                                    )
-                                    .into());
-                                }
+                                    &response,
+                                )
+                                .into());
                             }
                         }
                         Ok(GithubMergeRequestFields::from(&merge_request_json).into())
@@ -242,18 +500,30 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
     }
 
     fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
+        if !args.labels.is_empty() && args.source_branch.is_some() {
+            // Labels are filtered through the issues API, whose results carry
+            // no head branch to filter on.
+            return Err(error::GRError::OperationNotSupported(
+                "Github cannot filter pull requests by labels and source branch at the same time"
+                    .to_string(),
+            )
+            .into());
+        }
         let url = self.url_list_merge_requests(&args);
+        // Search results are wrapped in the items array.
+        let iter_over_sub_array = args.reviewer.as_ref().map(|_| "items");
         let response = query::paged::<_, MergeRequestResponse>(
             &self.runner,
             &url,
             args.list_args,
             self.request_headers(),
-            None,
+            iter_over_sub_array,
             ApiOperation::MergeRequest,
             |value| GithubMergeRequestFields::from(value).into(),
         );
-        if args.assignee.is_some() || args.author.is_some() {
-            // Pull requests for the current authenticated user.
+        if args.assignee.is_some() || args.author.is_some() || !args.labels.is_empty() {
+            // Pull requests for the current authenticated user or filtered by
+            // labels.
             // Filter those reponses that have pull_request not empty See ref:
             // https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28#list-issues-assigned-to-the-authenticated-user
             // Quoting Github's docs: Note: GitHub's REST API considers every
@@ -274,17 +544,55 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
         response
     }
 
-    fn merge(&self, id: i64) -> Result<MergeRequestResponse> {
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+        if args.delete_branch && args.auto_merge {
+            return Err(error::GRError::OperationNotSupported(
+                "Deleting the source branch on auto-merge is not supported in Github. Enable \
+                automatically deleting head branches in the repository settings instead"
+                    .to_string(),
+            )
+            .into());
+        }
+        let id = args.id;
+        if args.auto_merge {
+            self.enable_auto_merge(&args)?;
+            return Ok(MergeRequestResponse::builder()
+                .id(id)
+                .web_url(self.get_url(BrowseOptions::MergeRequestId(id)))
+                .build()
+                .unwrap());
+        }
         // https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#merge-a-pull-request
         //  /repos/{owner}/{repo}/pulls/{pull_number}/merge
         let url = format!(
             "{}/repos/{}/pulls/{}/merge",
             self.rest_api_basepath, self.path, id
         );
-        query::send_json::<_, ()>(
+        let mut body = Body::new();
+        if let Some(merge_method) = args.merge_method {
+            body.add("merge_method", merge_method.to_string());
+        }
+        let (commit_title, commit_message) = commit_title_message(args.squash_message.as_deref());
+        if let Some(commit_title) = commit_title {
+            body.add("commit_title", commit_title.to_string());
+        }
+        if let Some(commit_message) = commit_message {
+            body.add("commit_message", commit_message.to_string());
+        }
+        if let Some(sha) = args.sha {
+            body.add("sha", sha);
+        }
+        // Gather the source branch before merging, so a branch that cannot be
+        // deleted fails before the merge takes place.
+        let source_branch = if args.delete_branch {
+            Some(self.source_branch(id)?)
+        } else {
+            None
+        };
+        query::send_json(
             &self.runner,
             &url,
-            None,
+            Some(&body),
             self.request_headers(),
             ApiOperation::MergeRequest,
             http::Method::PUT,
@@ -295,6 +603,20 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
         //     "merged": true,
         //     "message": "Pull Request successfully merged"
         // }
+        if let Some(source_branch) = source_branch {
+            let url = format!(
+                "{}/repos/{}/git/refs/heads/{}",
+                self.rest_api_basepath, self.path, source_branch
+            );
+            query::send_raw::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::MergeRequest,
+                http::Method::DELETE,
+            )?;
+        }
 
         // We do not have the id nor the url available in the response. Compute
         // it and return it to the client so we can open the url if needed.
@@ -305,6 +627,45 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
             .unwrap())
     }
 
+    fn diff(&self, id: i64) -> Result<Vec<MergeRequestDiff>> {
+        // https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#list-pull-requests-files
+        let url = format!(
+            "{}/repos/{}/pulls/{}/files?per_page=100",
+            self.rest_api_basepath, self.path, id
+        );
+        query::paged_unsorted(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            |value| GithubMergeRequestDiffFields::from(value).into(),
+        )
+    }
+
+    fn head_ref(&self, id: i64) -> String {
+        // https://docs.github.com/en/pull-requests/collaborating-with-pull-requests/reviewing-changes-in-pull-requests/checking-out-pull-requests-locally
+        format!("refs/pull/{}/head", id)
+    }
+
+    fn rebase(&self, id: i64) -> Result<()> {
+        // https://docs.github.com/en/rest/pulls/pulls#update-a-pull-request-branch
+        // Github merges the base branch into the head branch, there is no API
+        // to rebase it. Responds with a 202 Accepted.
+        let url = format!(
+            "{}/repos/{}/pulls/{}/update-branch",
+            self.rest_api_basepath, self.path, id
+        );
+        query::send_raw::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+
     fn get(&self, id: i64) -> Result<MergeRequestResponse> {
         let url = format!(
             "{}/repos/{}/pulls/{}",
@@ -338,6 +699,142 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
         )
     }
 
+    fn reopen(&self, id: i64) -> Result<MergeRequestResponse> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let mut body = Body::new();
+        body.add("state", "open");
+        query::send::<_, &str, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            |value| GithubMergeRequestFields::from(value).into(),
+            http::Method::PATCH,
+        )
+    }
+
+    fn update(&self, args: MergeRequestUpdateBodyArgs) -> Result<MergeRequestResponse> {
+        // https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#update-a-pull-request
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, args.id
+        );
+        // Same as when opening, assignees, labels and milestones are set using
+        // the issues API.
+        let mut body = Body::new();
+        if let Some(assignee) = &args.assignee {
+            body.add(
+                "assignees",
+                serde_json::Value::from(vec![assignee.username.as_str()]),
+            );
+        }
+        if let Some(labels) = &args.labels {
+            body.add("labels", serde_json::Value::from(labels.clone()));
+        }
+        if let Some(milestone) = &args.milestone {
+            body.add(
+                "milestone",
+                serde_json::Value::from(self.milestone_number(milestone)?),
+            );
+        }
+        if !body.is_empty() {
+            let issues_url = format!(
+                "{}/repos/{}/issues/{}",
+                self.rest_api_basepath, self.path, args.id
+            );
+            query::send_raw(
+                &self.runner,
+                &issues_url,
+                Some(&body),
+                self.request_headers(),
+                ApiOperation::MergeRequest,
+                http::Method::PATCH,
+            )?;
+        }
+        if let Some(reviewers) = &args.reviewers {
+            // Reviewers replace the ones already requested, same as in Gitlab.
+            // Github can only add and remove review requests, so pending
+            // requests not given are removed first.
+            // https://docs.github.com/en/rest/pulls/review-requests?apiVersion=2022-11-28
+            let requested_reviewers_url = format!("{}/requested_reviewers", url);
+            let reviewers: Vec<&str> = reviewers
+                .iter()
+                .map(|reviewer| reviewer.username.as_str())
+                .collect();
+            let requested = query::get_json::<_, ()>(
+                &self.runner,
+                &requested_reviewers_url,
+                None,
+                self.request_headers(),
+                ApiOperation::MergeRequest,
+            )?;
+            let removed: Vec<&str> = requested["users"]
+                .as_array()
+                .map(|users| {
+                    users
+                        .iter()
+                        .filter_map(|user| user["login"].as_str())
+                        .filter(|login| !reviewers.contains(login))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if !removed.is_empty() {
+                let mut body = Body::new();
+                body.add("reviewers", removed);
+                query::send_raw(
+                    &self.runner,
+                    &requested_reviewers_url,
+                    Some(&body),
+                    self.request_headers(),
+                    ApiOperation::MergeRequest,
+                    http::Method::DELETE,
+                )?;
+            }
+            if !reviewers.is_empty() {
+                let mut body = Body::new();
+                body.add("reviewers", reviewers);
+                let response = query::send_raw(
+                    &self.runner,
+                    &requested_reviewers_url,
+                    Some(&body),
+                    self.request_headers(),
+                    ApiOperation::MergeRequest,
+                    http::Method::POST,
+                )?;
+                // Consider 422 failure - Reviewer not a collaborator
+                if response.status != 201 {
+                    return Err(query::query_error(&requested_reviewers_url, &response).into());
+                }
+            }
+        }
+        if let Some(draft) = args.draft {
+            self.set_draft(args.id, draft)?;
+        }
+        let mut body = Body::new();
+        if let Some(title) = args.title {
+            body.add("title", title);
+        }
+        if let Some(description) = args.description {
+            body.add("body", description);
+        }
+        if let Some(target_branch) = args.target_branch {
+            body.add("base", target_branch);
+        }
+        query::send(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            |value| GithubMergeRequestFields::from(value).into(),
+            http::Method::PATCH,
+        )
+    }
+
     fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
         let url = self.url_list_merge_requests(&args) + "&page=1";
         let headers = self.request_headers();
@@ -374,6 +871,70 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Github<R> {
         Ok(())
     }
 
+    // Pull request comments are issue comments, identified by their id alone.
+    fn update(&self, args: CommentMergeRequestUpdateBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}",
+            self.rest_api_basepath, self.path, args.comment_id
+        );
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PATCH,
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, _id: i64, comment_id: i64) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}",
+            self.rest_api_basepath, self.path, comment_id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    fn react(&self, _id: i64, comment_id: i64, reaction: Reaction) -> Result<()> {
+        // https://docs.github.com/en/rest/reactions/reactions#create-reaction-for-an-issue-comment
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}/reactions",
+            self.rest_api_basepath, self.path, comment_id
+        );
+        let content = match reaction {
+            Reaction::ThumbsUp => "+1",
+            Reaction::ThumbsDown => "-1",
+            Reaction::Laugh => "laugh",
+            Reaction::Confused => "confused",
+            Reaction::Heart => "heart",
+            Reaction::Hooray => "hooray",
+            Reaction::Rocket => "rocket",
+            Reaction::Eyes => "eyes",
+        };
+        let mut body = Body::new();
+        body.add("content", content);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
     fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
         let url = format!(
             "{}/repos/{}/issues/{}/comments",
@@ -414,20 +975,520 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Github<R> {
     }
 }
 
-pub struct GithubMergeRequestFields {
-    fields: MergeRequestResponse,
-}
-
-impl From<&serde_json::Value> for GithubMergeRequestFields {
-    fn from(merge_request_data: &serde_json::Value) -> Self {
-        GithubMergeRequestFields {
-            fields: MergeRequestResponse::builder()
-                .id(merge_request_data["number"].as_i64().unwrap())
-                .web_url(merge_request_data["html_url"].as_str().unwrap().to_string())
-                .source_branch(
-                    merge_request_data["head"]["ref"]
-                        .as_str()
-                        .unwrap_or_default()
+impl<R: HttpRunner<Response = HttpResponse>> ReviewMergeRequest for Github<R> {
+    fn review(&self, args: MergeRequestReviewBodyArgs) -> Result<()> {
+        // https://docs.github.com/en/rest/pulls/reviews#create-a-review-for-a-pull-request
+        let url = format!(
+            "{}/repos/{}/pulls/{}/reviews",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let event = match args.verdict {
+            ReviewVerdict::Approve => "APPROVE",
+            ReviewVerdict::RequestChanges => "REQUEST_CHANGES",
+            ReviewVerdict::Comment => "COMMENT",
+        };
+        let comments = args
+            .comments
+            .iter()
+            .map(|comment| {
+                let side = match comment.side {
+                    ReviewSide::Old => "LEFT",
+                    ReviewSide::New => "RIGHT",
+                };
+                serde_json::json!({
+                    "path": comment.path,
+                    "line": comment.line,
+                    "side": side,
+                    "body": comment.body,
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut body: Body<serde_json::Value> = Body::new();
+        body.add("event", serde_json::Value::from(event));
+        if let Some(summary) = args.body {
+            body.add("body", serde_json::Value::from(summary));
+        }
+        if !comments.is_empty() {
+            body.add("comments", serde_json::Value::from(comments));
+        }
+        let response = query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        // A 422 is not an error for POST requests, but Github responds with it
+        // when the review is rejected, ex. a line is not part of the diff.
+        if response.status != 200 {
+            return Err(query::query_error(&url, &response).into());
+        }
+        Ok(())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestCommit for Github<R> {
+    fn list(&self, args: MergeRequestCommitListBodyArgs) -> Result<Vec<Commit>> {
+        // https://docs.github.com/en/rest/pulls/pulls#list-commits-on-a-pull-request
+        let url = format!(
+            "{}/repos/{}/pulls/{}/commits",
+            self.rest_api_basepath, self.path, args.id
+        );
+        query::paged(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.request_headers(),
+            None,
+            ApiOperation::MergeRequest,
+            |value| {
+                let mut commit: Commit = GithubCommitFields::from(value).into();
+                if args.ci_status {
+                    commit.ci_status = self
+                        .commit_ci_status(&commit.sha)
+                        .unwrap_or_else(|_| "unknown".to_string());
+                }
+                commit
+            },
+        )
+    }
+
+    fn num_pages(&self, args: MergeRequestCommitListBodyArgs) -> Result<Option<u32>> {
+        let url = self.resource_commits_metadata_url(args);
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )
+    }
+
+    fn num_resources(
+        &self,
+        args: MergeRequestCommitListBodyArgs,
+    ) -> Result<Option<NumberDeltaErr>> {
+        let url = self.resource_commits_metadata_url(args);
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    fn resource_commits_metadata_url(&self, args: MergeRequestCommitListBodyArgs) -> String {
+        format!(
+            "{}/repos/{}/pulls/{}/commits?page=1",
+            self.rest_api_basepath, self.path, args.id
+        )
+    }
+
+    /// Combined status of the check runs on a commit.
+    /// https://docs.github.com/en/rest/checks/runs#list-check-runs-for-a-git-reference
+    fn commit_ci_status(&self, sha: &str) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/commits/{}/check-runs?per_page=100",
+            self.rest_api_basepath, self.path, sha
+        );
+        let check_runs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let statuses: Vec<CiStatus> = check_runs["check_runs"]
+            .as_array()
+            .map(|check_runs| {
+                check_runs
+                    .iter()
+                    .map(|check_run| {
+                        if check_run["status"].as_str() != Some("completed") {
+                            return CiStatus::Pending;
+                        }
+                        match check_run["conclusion"].as_str().unwrap_or_default() {
+                            "success" | "neutral" | "skipped" => CiStatus::Success,
+                            _ => CiStatus::Failed,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(combined_status(&statuses))
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestReadiness for Github<R> {
+    fn mergeability(&self, id: i64) -> Result<Mergeability> {
+        let pull_request = self.pull_request_json(id)?;
+        let head_sha = pull_request["head"]["sha"].as_str().unwrap_or_default();
+        let base_ref = pull_request["base"]["ref"].as_str().unwrap_or_default();
+        // mergeable is null while Github is still computing it.
+        let has_conflicts = pull_request["mergeable"].as_bool() == Some(false)
+            || pull_request["mergeable_state"].as_str() == Some("dirty");
+        // https://docs.github.com/en/rest/commits/commits#compare-two-commits
+        let compare_url = format!(
+            "{}/repos/{}/compare/{}...{}",
+            self.rest_api_basepath, self.path, base_ref, head_sha
+        );
+        let behind_by = query::get_json::<_, ()>(
+            &self.runner,
+            &compare_url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )
+        .ok()
+        .and_then(|comparison| comparison["behind_by"].as_u64());
+        Ok(Mergeability::builder()
+            .draft(pull_request["draft"].as_bool().unwrap_or_default())
+            .has_conflicts(has_conflicts)
+            .behind_by(behind_by)
+            .ci_status(self.commit_ci_status(head_sha)?)
+            .build()?)
+    }
+
+    fn approvals(&self, id: i64) -> Result<Approvals> {
+        // https://docs.github.com/en/rest/pulls/reviews#list-reviews-for-a-pull-request
+        let url = format!(
+            "{}/repos/{}/pulls/{}/reviews?per_page=100",
+            self.rest_api_basepath, self.path, id
+        );
+        // Reviews are listed in chronological order.
+        let reviews = query::paged_unsorted(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            |value| GithubReview {
+                reviewer: value["user"]["login"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                state: value["state"].as_str().unwrap_or_default().to_string(),
+            },
+        )?;
+        // Only the last approval or change request of each reviewer counts.
+        let mut verdicts: Vec<&GithubReview> = Vec::new();
+        for review in &reviews {
+            let state = review.state.as_str();
+            if state != "APPROVED" && state != "CHANGES_REQUESTED" && state != "DISMISSED" {
+                continue;
+            }
+            verdicts.retain(|verdict| verdict.reviewer != review.reviewer);
+            verdicts.push(review);
+        }
+        let approved = verdicts
+            .iter()
+            .filter(|verdict| verdict.state == "APPROVED")
+            .count() as u32;
+        let pull_request = self.pull_request_json(id)?;
+        let base_ref = pull_request["base"]["ref"].as_str().unwrap_or_default();
+        // Reading the branch protection requires admin permissions, so the
+        // number of required approvals is unknown otherwise.
+        // https://docs.github.com/en/rest/branches/branch-protection#get-pull-request-review-protection
+        let protection_url = format!(
+            "{}/repos/{}/branches/{}/protection/required_pull_request_reviews",
+            self.rest_api_basepath, self.path, base_ref
+        );
+        let required = query::get_json::<_, ()>(
+            &self.runner,
+            &protection_url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )
+        .ok()
+        .and_then(|protection| protection["required_approving_review_count"].as_u64())
+        .map(|required| required as u32);
+        Ok(Approvals::builder()
+            .approved(approved)
+            .required(required)
+            .build()?)
+    }
+}
+
+/// Review of a pull request, as needed to count approvals.
+#[derive(Clone)]
+struct GithubReview {
+    reviewer: String,
+    state: String,
+}
+
+impl From<GithubReview> for DisplayBody {
+    fn from(review: GithubReview) -> DisplayBody {
+        DisplayBody::new(vec![
+            Column::new("Reviewer", review.reviewer),
+            Column::new("State", review.state),
+        ])
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    /// Appends the remaining pages of comments of a review thread to its
+    /// first page.
+    fn page_thread_comments(&self, thread: &mut serde_json::Value) -> Result<()> {
+        let query = "query($id: ID!, $cursor: String) { node(id: $id) { \
+            ... on PullRequestReviewThread { comments(first: 100, after: $cursor) { \
+            pageInfo { hasNextPage endCursor } \
+            nodes { databaseId body author { login } createdAt } } } } }";
+        let mut page_info = thread["comments"]["pageInfo"].clone();
+        while page_info["hasNextPage"].as_bool().unwrap_or_default() {
+            let variables = serde_json::json!({
+                "id": thread["id"], "cursor": page_info["endCursor"]
+            });
+            let data = self.graphql(query.to_string(), variables)?;
+            let comments = &data["node"]["comments"];
+            let nodes = comments["nodes"].as_array().cloned().unwrap_or_default();
+            if let Some(thread_nodes) = thread["comments"]["nodes"].as_array_mut() {
+                thread_nodes.extend(nodes);
+            }
+            page_info = comments["pageInfo"].clone();
+        }
+        Ok(())
+    }
+
+    fn pull_request_json(&self, id: i64) -> Result<serde_json::Value> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )
+    }
+}
+
+// Review threads are only exposed through the GraphQL API.
+// https://docs.github.com/en/graphql/reference/objects#pullrequestreviewthread
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestThread for Github<R> {
+    fn list(&self, id: i64) -> Result<Vec<Thread>> {
+        // Connections return at most 100 nodes, the rest are paged with the
+        // end cursor of the previous page.
+        // https://docs.github.com/en/graphql/guides/using-pagination-in-the-graphql-api
+        let query = "query($owner: String!, $name: String!, $number: Int!, $cursor: String) { \
+            repository(owner: $owner, name: $name) { pullRequest(number: $number) { \
+            reviewThreads(first: 100, after: $cursor) { \
+            pageInfo { hasNextPage endCursor } nodes { id isResolved path line originalLine \
+            comments(first: 100) { pageInfo { hasNextPage endCursor } \
+            nodes { databaseId body author { login } createdAt } } \
+            } } } } }";
+        let (owner, name) = self.path.split_once('/').unwrap_or_default();
+        let mut threads = Vec::new();
+        let mut cursor = serde_json::Value::Null;
+        loop {
+            let variables = serde_json::json!({
+                "owner": owner, "name": name, "number": id, "cursor": cursor
+            });
+            let data = self.graphql(query.to_string(), variables)?;
+            let review_threads = &data["repository"]["pullRequest"]["reviewThreads"];
+            for thread in review_threads["nodes"].as_array().into_iter().flatten() {
+                let mut thread = thread.clone();
+                self.page_thread_comments(&mut thread)?;
+                threads.push(GithubThreadFields::from(&thread).into());
+            }
+            if !review_threads["pageInfo"]["hasNextPage"]
+                .as_bool()
+                .unwrap_or_default()
+            {
+                return Ok(threads);
+            }
+            cursor = review_threads["pageInfo"]["endCursor"].clone();
+        }
+    }
+
+    fn reply(&self, id: i64, thread_id: &str, body: String) -> Result<()> {
+        let query = "mutation($id: ID!, $body: String!) { addPullRequestReviewThreadReply(\
+            input: {pullRequestReviewThreadId: $id, body: $body}) { clientMutationId } }"
+            .to_string();
+        let variables = serde_json::json!({ "id": thread_id, "body": body });
+        self.graphql(query, variables).map_err(|err| {
+            error::gen(format!(
+                "Failed to reply to thread {} in pull request {}: {}",
+                thread_id, id, err
+            ))
+        })?;
+        Ok(())
+    }
+
+    fn resolve(&self, id: i64, thread_id: &str, resolved: bool) -> Result<()> {
+        let mutation = if resolved {
+            "resolveReviewThread"
+        } else {
+            "unresolveReviewThread"
+        };
+        let query = format!(
+            "mutation($id: ID!) {{ {}(input: {{threadId: $id}}) {{ clientMutationId }} }}",
+            mutation
+        );
+        let variables = serde_json::json!({ "id": thread_id });
+        self.graphql(query, variables).map_err(|err| {
+            error::gen(format!(
+                "Failed to update thread {} in pull request {}: {}",
+                thread_id, id, err
+            ))
+        })?;
+        Ok(())
+    }
+}
+
+pub struct GithubCommitFields {
+    commit: Commit,
+}
+
+impl From<&serde_json::Value> for GithubCommitFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let message = data["commit"]["message"].as_str().unwrap_or_default();
+        GithubCommitFields {
+            commit: Commit::builder()
+                .sha(data["sha"].as_str().unwrap_or_default().to_string())
+                .author(
+                    data["commit"]["author"]["name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .title(message.lines().next().unwrap_or_default().to_string())
+                .date(
+                    data["commit"]["author"]["date"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubCommitFields> for Commit {
+    fn from(fields: GithubCommitFields) -> Self {
+        fields.commit
+    }
+}
+
+pub struct GithubThreadFields {
+    thread: Thread,
+}
+
+impl From<&serde_json::Value> for GithubThreadFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let empty = vec![];
+        let notes: Vec<Comment> = data["comments"]["nodes"]
+            .as_array()
+            .unwrap_or(&empty)
+            .iter()
+            .map(|note| {
+                Comment::builder()
+                    .id(note["databaseId"].as_i64().unwrap_or_default())
+                    .body(note["body"].as_str().unwrap_or_default().to_string())
+                    .author(
+                        note["author"]["login"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string(),
+                    )
+                    .created_at(note["createdAt"].as_str().unwrap_or_default().to_string())
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let created_at = notes
+            .first()
+            .map(|note| note.created_at.clone())
+            .unwrap_or_default();
+        // Outdated threads no longer have a line in the current diff.
+        let line = data["line"].as_i64().or(data["originalLine"].as_i64());
+        GithubThreadFields {
+            thread: Thread::builder()
+                .id(data["id"].as_str().unwrap_or_default().to_string())
+                .resolvable(true)
+                .resolved(data["isResolved"].as_bool().unwrap_or_default())
+                .path(data["path"].as_str().map(|path| path.to_string()))
+                .line(line)
+                .notes(notes)
+                .created_at(created_at)
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubThreadFields> for Thread {
+    fn from(fields: GithubThreadFields) -> Self {
+        fields.thread
+    }
+}
+
+pub struct GithubMergeRequestDiffFields {
+    diff: MergeRequestDiff,
+}
+
+impl From<&serde_json::Value> for GithubMergeRequestDiffFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let new_path = data["filename"].as_str().unwrap_or_default().to_string();
+        // Only renamed files have a previous filename.
+        let old_path = data["previous_filename"]
+            .as_str()
+            .map(|previous| previous.to_string())
+            .unwrap_or_else(|| new_path.clone());
+        GithubMergeRequestDiffFields {
+            diff: MergeRequestDiff::builder()
+                .old_path(old_path)
+                .new_path(new_path)
+                .status(data["status"].as_str().unwrap_or_default().to_string())
+                .additions(data["additions"].as_u64().unwrap_or_default() as usize)
+                .deletions(data["deletions"].as_u64().unwrap_or_default() as usize)
+                .diff(data["patch"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubMergeRequestDiffFields> for MergeRequestDiff {
+    fn from(fields: GithubMergeRequestDiffFields) -> Self {
+        fields.diff
+    }
+}
+
+pub struct GithubMergeRequestFields {
+    fields: MergeRequestResponse,
+}
+
+impl From<&serde_json::Value> for GithubMergeRequestFields {
+    fn from(merge_request_data: &serde_json::Value) -> Self {
+        // Github has no merged state. A merged pull request is a closed one
+        // with a merge date, which is found under the pull_request key when
+        // listing through the issues API.
+        let merged = !merge_request_data["merged_at"].is_null()
+            || !merge_request_data["pull_request"]["merged_at"].is_null();
+        let state = match merge_request_data["state"].as_str().unwrap_or_default() {
+            "open" => "opened",
+            "closed" if merged => "merged",
+            state => state,
+        };
+        GithubMergeRequestFields {
+            fields: MergeRequestResponse::builder()
+                .id(merge_request_data["number"].as_i64().unwrap())
+                .web_url(merge_request_data["html_url"].as_str().unwrap().to_string())
+                .source_branch(
+                    merge_request_data["head"]["ref"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .target_branch(
+                    merge_request_data["base"]["ref"]
+                        .as_str()
+                        .unwrap_or_default()
                         .to_string(),
                 )
                 .sha(
@@ -460,6 +1521,24 @@ impl From<&serde_json::Value> for GithubMergeRequestFields {
                         .unwrap_or_default()
                         .to_string(),
                 )
+                .state(state.to_string())
+                .labels(
+                    merge_request_data["labels"]
+                        .as_array()
+                        .map(|labels| {
+                            labels
+                                .iter()
+                                .filter_map(|label| label["name"].as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .milestone(
+                    merge_request_data["milestone"]["title"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
                 .pull_request(
                     merge_request_data["pull_request"]["html_url"]
                         .as_str()
@@ -525,6 +1604,7 @@ impl From<GithubMergeRequestCommentFields> for Comment {
 mod test {
 
     use crate::{
+        cmds::merge_request::{review::ReviewComment, MergeMethod},
         cmds::project::{Member, MrMemberType},
         http::{self, Headers},
         remote::ListBodyArgs,
@@ -558,9 +1638,16 @@ mod test {
             .id(45678)
             .build()
             .unwrap();
+        let second_reviewer = Member::builder()
+            .name("jim".to_string())
+            .username("jim".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .id(90123)
+            .build()
+            .unwrap();
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer, second_reviewer])
             .build()
             .unwrap();
         let response = github.open(mr_args).unwrap();
@@ -579,6 +1666,10 @@ mod test {
         assert_eq!(http::Method::PATCH, actual_method[1]);
         // Reviewer call
         assert_eq!(http::Method::POST, actual_method[2]);
+        assert!(client
+            .request_body
+            .borrow()
+            .contains("\"reviewers\":[\"hfinn\",\"jim\"]"));
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
@@ -601,7 +1692,7 @@ mod test {
         let reviewer = Member::default(); // Default member is empty/placeholder
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer])
             .build()
             .unwrap();
         let response = github.open(mr_args).unwrap();
@@ -640,7 +1731,7 @@ mod test {
             .unwrap();
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer])
             .build()
             .unwrap();
         assert!(github.open(mr_args).is_ok());
@@ -671,7 +1762,7 @@ mod test {
         let reviewer = Member::default();
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer])
             .build()
             .unwrap();
         assert!(github.open(mr_args).is_ok());
@@ -1053,7 +2144,8 @@ mod test {
             None,
         );
         let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        github.get(23).unwrap();
+        let merge_request = github.get(23).unwrap();
+        assert_eq!("main", merge_request.target_branch);
         assert_eq!(
             "https://api.github.com/repos/jordilin/githapi/pulls/23",
             *client.url(),
@@ -1072,7 +2164,8 @@ mod test {
             None,
         );
         let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        github.merge(23).unwrap();
+        let args = MergeRequestMergeBodyArgs::builder().id(23).build().unwrap();
+        github.merge(args).unwrap();
         assert_eq!(
             "https://api.github.com/repos/jordilin/githapi/pulls/23/merge",
             *client.url(),
@@ -1086,56 +2179,1050 @@ mod test {
     }
 
     #[test]
-    fn test_list_pull_request_comments() {
+    fn test_github_pull_request_diff() {
         let contracts = ResponseContracts::new(ContractType::Github).add_body(
             200,
-            Some(format!(
-                "[{}]",
-                get_contract(ContractType::Github, "comment.json")
-            )),
+            Some(
+                r#"[{"filename": "src/main.rs", "status": "modified", "additions": 2,
+                "deletions": 1, "patch": "@@ -1 +1,2 @@\n-a\n+b\n+c"},
+                {"filename": "new.rs", "previous_filename": "old.rs", "status": "renamed",
+                "additions": 0, "deletions": 0}]"#,
+            ),
             None,
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
-        let args = CommentMergeRequestListBodyArgs::builder()
-            .id(23)
-            .list_args(None)
-            .build()
-            .unwrap();
-        github.list(args).unwrap();
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let diffs = github.diff(23).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/issues/23/comments",
-            *client.url(),
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/files?per_page=100",
+            *client.url()
         );
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
         );
+        assert_eq!(2, diffs.len());
+        assert_eq!("src/main.rs", diffs[0].old_path);
+        assert_eq!(2, diffs[0].additions);
+        assert_eq!("old.rs", diffs[1].old_path);
+        assert_eq!("new.rs", diffs[1].new_path);
+        assert!(diffs[1].diff.is_empty());
     }
 
     #[test]
-    fn test_pull_request_comment_num_pages() {
-        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="last""#;
-        let mut headers = Headers::new();
-        headers.set("link".to_string(), link_header.to_string());
-        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+    fn test_github_merge_pull_request_squash_with_message_and_sha() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
             200,
+            "merge_request.json",
             None,
-            Some(headers),
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
-        let args = CommentMergeRequestListBodyArgs::builder()
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
             .id(23)
-            .list_args(None)
+            .merge_method(Some(MergeMethod::Squash))
+            .squash_message(Some("New feature\n\nDetails".to_string()))
+            .sha(Some("6dcb09b".to_string()))
             .build()
             .unwrap();
-        assert_eq!(Some(2), github.num_pages(args).unwrap());
+        github.merge(args).unwrap();
+        let body = client.request_body.borrow();
+        assert!(body.contains("\"merge_method\":\"squash\""));
+        assert!(body.contains("\"commit_title\":\"New feature\""));
+        assert!(body.contains("\"commit_message\":\"Details\""));
+        assert!(body.contains("\"sha\":\"6dcb09b\""));
+    }
+
+    #[test]
+    fn test_github_merge_pull_request_delete_branch() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(204, Some(""), None)
+            .add_body(200, Some(r#"{"merged": true}"#), None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .delete_branch(true)
+            .build()
+            .unwrap();
+        github.merge(args).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=1",
-            *client.url(),
+            "https://api.github.com/repos/jordilin/githapi/git/refs/heads/feature",
+            *client.url()
         );
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            vec![http::Method::GET, http::Method::PUT, http::Method::DELETE],
+            *client.http_method.borrow()
         );
     }
+
+    #[test]
+    fn test_github_merge_pull_request_delete_branch_in_fork_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(r#"{"head": {"ref": "feature", "repo": {"full_name": "someone/githapi"}}}"#),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .delete_branch(true)
+            .build()
+            .unwrap();
+        let err = github.merge(args).unwrap_err();
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::OperationNotSupported(_)) => (),
+            _ => panic!("Expected OperationNotSupported"),
+        }
+        // The pull request is not merged.
+        assert_eq!(vec![http::Method::GET], *client.http_method.borrow());
+    }
+
+    #[test]
+    fn test_github_auto_merge_pull_request_delete_branch_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .delete_branch(true)
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let err = github.merge(args).unwrap_err();
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::OperationNotSupported(_)) => (),
+            _ => panic!("Expected OperationNotSupported"),
+        }
+    }
+
+    #[test]
+    fn test_github_auto_merge_pull_request_uses_graphql() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"data": {"enablePullRequestAutoMerge": {"clientMutationId": null}}}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .merge_method(Some(MergeMethod::Rebase))
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let response = github.merge(args).unwrap();
+        assert_eq!(
+            "https://github.com/jordilin/githapi/pull/23",
+            response.web_url
+        );
+        assert_eq!("https://api.github.com/graphql", *client.url());
+        let body = client.request_body.borrow();
+        assert!(body.contains("enablePullRequestAutoMerge"));
+        assert!(body.contains("\"method\":\"REBASE\""));
+    }
+
+    #[test]
+    fn test_github_auto_merge_graphql_errors_are_reported() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(
+                    r#"{"errors": [{"message": "Auto merge is not allowed for this repository"}]}"#,
+                ),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let err = github.merge(args).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Auto merge is not allowed for this repository"));
+    }
+
+    #[test]
+    fn test_list_pull_request_comments() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Github, "comment.json")
+            )),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(23)
+            .list_args(None)
+            .build()
+            .unwrap();
+        github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23/comments",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_pull_request_comment_num_pages() {
+        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="last""#;
+        let mut headers = Headers::new();
+        headers.set("link".to_string(), link_header.to_string());
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(23)
+            .list_args(None)
+            .build()
+            .unwrap();
+        assert_eq!(Some(2), github.num_pages(args).unwrap());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=1",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_update_pull_request() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(200, Some(r#"{"data": {}}"#), None)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(r#"{"users": [{"login": "tom"}, {"login": "hfinn"}], "teams": []}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let member = |username: &str| {
+            Member::builder()
+                .username(username.to_string())
+                .mr_member_type(MrMemberType::Filled)
+                .build()
+                .unwrap()
+        };
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(23)
+            .title(Some("Fix typo".to_string()))
+            .assignee(Some(member("tsawyer")))
+            .reviewers(Some(vec![member("hfinn"), member("jim")]))
+            .draft(Some(true))
+            .build()
+            .unwrap();
+        github.update(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
+            *client.url()
+        );
+        let methods = client.http_method.borrow();
+        assert_eq!(
+            vec![
+                http::Method::PATCH,
+                http::Method::GET,
+                http::Method::DELETE,
+                http::Method::POST,
+                http::Method::GET,
+                http::Method::POST,
+                http::Method::PATCH
+            ],
+            *methods
+        );
+        assert!(client.request_body.borrow().contains("Fix typo"));
+    }
+
+    #[test]
+    fn test_update_pull_request_reviewer_not_collaborator_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                422,
+                Some(r#"{"message": "Reviews may only be requested from collaborators."}"#),
+                None,
+            )
+            .add_body(200, Some(r#"{"users": [], "teams": []}"#), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(23)
+            .reviewers(Some(vec![Member::builder()
+                .username("tsawyer".to_string())
+                .build()
+                .unwrap()]))
+            .build()
+            .unwrap();
+        let err = github.update(args).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("only be requested from collaborators"));
+        // Nothing else is updated.
+        assert_eq!(
+            vec![http::Method::GET, http::Method::POST],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_update_pull_request_reviewers_replaces_requested_reviewers() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(r#"{"users": [{"login": "tom"}, {"login": "hfinn"}], "teams": []}"#),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(23)
+            .reviewers(Some(vec![]))
+            .build()
+            .unwrap();
+        github.update(args).unwrap();
+        // Nobody to request, all pending requests are removed.
+        assert_eq!(
+            vec![http::Method::GET, http::Method::DELETE, http::Method::PATCH],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_update_pull_request_draft_state_unchanged_skips_graphql() {
+        // merge_request.json is not a draft.
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(23)
+            .draft(Some(false))
+            .build()
+            .unwrap();
+        github.update(args).unwrap();
+        assert_eq!(
+            vec![http::Method::GET, http::Method::PATCH],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_update_pull_request_graphql_error() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"errors": [{"message": "Resource not accessible by integration"}]}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(23)
+            .draft(Some(true))
+            .build()
+            .unwrap();
+        let err = github.update(args).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Resource not accessible by integration"));
+        assert_eq!("https://api.github.com/graphql", *client.url());
+        assert!(client
+            .request_body
+            .borrow()
+            .contains("convertPullRequestToDraft"));
+    }
+
+    #[test]
+    fn test_reopen_pull_request() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let merge_request = github.reopen(23).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
+            *client.url()
+        );
+        assert_eq!(http::Method::PATCH, client.http_method.borrow()[0]);
+        assert!(client.request_body.borrow().contains("\"state\":\"open\""));
+        assert_eq!("opened", merge_request.state);
+    }
+
+    #[test]
+    fn test_list_all_pull_requests_maps_merged_state() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(
+                r#"[{"number": 1, "html_url": "https://github.com/jordilin/githapi/pull/1", "state": "closed", "merged_at": "2024-01-01T00:00:00Z", "created_at": "2023-12-01T00:00:00Z"},
+                   {"number": 2, "html_url": "https://github.com/jordilin/githapi/pull/2", "state": "closed", "merged_at": null, "created_at": "2023-12-02T00:00:00Z"}]"#,
+            ),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::All)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls?state=all",
+            *client.url()
+        );
+        assert_eq!("merged", merge_requests[0].state);
+        assert_eq!("closed", merge_requests[1].state);
+    }
+
+    #[test]
+    fn test_open_pull_request_with_labels_and_milestone() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(r#"[{"number": 1, "title": "v0.9"}, {"number": 2, "title": "v1.0"}]"#),
+                None,
+            )
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .target_branch("main".to_string())
+            .labels(vec!["needs-review".to_string()])
+            .milestone(Some("v1.0".to_string()))
+            .build()
+            .unwrap();
+        github.open(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23",
+            *client.url()
+        );
+        assert_eq!(
+            vec![http::Method::POST, http::Method::GET, http::Method::PATCH],
+            *client.http_method.borrow()
+        );
+        let body = client.request_body.borrow();
+        assert!(body.contains("\"labels\":[\"needs-review\"]"));
+        assert!(body.contains("\"milestone\":2"));
+        assert!(!body.contains("assignees"));
+    }
+
+    #[test]
+    fn test_open_pull_request_milestone_in_second_page() {
+        let first_page = format!(
+            "[{}]",
+            (1..=100)
+                .map(|number| format!(r#"{{"number": {}, "title": "v0.{}"}}"#, number, number))
+                .collect::<Vec<String>>()
+                .join(",")
+        );
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(200, Some(r#"[{"number": 101, "title": "v1.0"}]"#), None)
+            .add_body(200, Some(first_page), None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .target_branch("main".to_string())
+            .milestone(Some("v1.0".to_string()))
+            .build()
+            .unwrap();
+        github.open(args).unwrap();
+        assert_eq!(
+            vec![
+                http::Method::POST,
+                http::Method::GET,
+                http::Method::GET,
+                http::Method::PATCH
+            ],
+            *client.http_method.borrow()
+        );
+        assert!(client.request_body.borrow().contains("\"milestone\":101"));
+    }
+
+    #[test]
+    fn test_open_pull_request_milestone_not_found_is_error() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some(r#"[{"number": 1, "title": "v0.9"}]"#), None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .target_branch("main".to_string())
+            .milestone(Some("v1.0".to_string()))
+            .build()
+            .unwrap();
+        let err = github.open(args).unwrap_err();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/milestones?state=all&per_page=100&page=1",
+            *client.url()
+        );
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("Milestone v1.0 not found", msg)
+            }
+            _ => panic!("Expected PreconditionNotMet error"),
+        }
+    }
+
+    #[test]
+    fn test_list_pull_requests_by_labels_uses_issues_api() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .labels(vec!["needs review".to_string(), "c++".to_string()])
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues?state=open&labels=needs%20review,c%2B%2B",
+            *client.url()
+        );
+        // Issues that are not pull requests are filtered out.
+        assert_eq!(1, merge_requests.len());
+    }
+
+    #[test]
+    fn test_list_pull_requests_by_labels_and_source_branch_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .labels(vec!["needs review".to_string()])
+            .source_branch(Some("feature".to_string()))
+            .build()
+            .unwrap();
+        let err = github.list(args).unwrap_err();
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::OperationNotSupported(_)) => (),
+            _ => panic!("Expected OperationNotSupported error"),
+        }
+        assert!(client.http_method.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_list_pull_requests_by_reviewer_uses_search_api() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(
+                r#"{"total_count": 1, "items": [{"number": 23, "title": "New feature",
+                "state": "open", "html_url": "https://github.com/jordilin/githapi/pull/23",
+                "created_at": "2024-03-01T00:00:00Z", "updated_at": "2024-03-01T00:00:00Z",
+                "user": {"login": "tsawyer"}, "labels": [],
+                "pull_request": {"url": "https://api.github.com/repos/jordilin/githapi/pulls/23"}}]}"#,
+            ),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .reviewer(Some(
+                Member::builder()
+                    .username("hfinn".to_string())
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/search/issues?q=repo:jordilin/githapi+is:pr+is:open+review-requested:hfinn",
+            *client.url()
+        );
+        assert_eq!(1, merge_requests.len());
+        assert_eq!(23, merge_requests[0].id);
+    }
+
+    #[test]
+    fn test_review_pull_request_submits_single_review() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some("{}"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ReviewMergeRequest);
+        let comments = vec![
+            ReviewComment::builder()
+                .path("src/main.rs".to_string())
+                .line(42)
+                .body("Prefer expect".to_string())
+                .build()
+                .unwrap(),
+            ReviewComment::builder()
+                .path("src/old.rs".to_string())
+                .line(7)
+                .side(ReviewSide::Old)
+                .body("Why was this removed?".to_string())
+                .build()
+                .unwrap(),
+        ];
+        let args = MergeRequestReviewBodyArgs::builder()
+            .id(23)
+            .comments(comments)
+            .verdict(ReviewVerdict::RequestChanges)
+            .body(Some("A few nits".to_string()))
+            .build()
+            .unwrap();
+        github.review(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/reviews",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::POST], *client.http_method.borrow());
+        let body: serde_json::Value = serde_json::from_str(&client.request_body.borrow()).unwrap();
+        assert_eq!("REQUEST_CHANGES", body["event"]);
+        assert_eq!("A few nits", body["body"]);
+        assert_eq!("RIGHT", body["comments"][0]["side"]);
+        assert_eq!(42, body["comments"][0]["line"]);
+        assert_eq!("LEFT", body["comments"][1]["side"]);
+        assert_eq!("src/old.rs", body["comments"][1]["path"]);
+    }
+
+    #[test]
+    fn test_review_pull_request_rejected_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            422,
+            Some(
+                r#"{"message": "Unprocessable Entity", "errors": ["Line could not be resolved"]}"#,
+            ),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn ReviewMergeRequest);
+        let comments = vec![ReviewComment::builder()
+            .path("src/main.rs".to_string())
+            .line(4242)
+            .body("Not part of the diff".to_string())
+            .build()
+            .unwrap()];
+        let args = MergeRequestReviewBodyArgs::builder()
+            .id(23)
+            .comments(comments)
+            .verdict(ReviewVerdict::Comment)
+            .build()
+            .unwrap();
+        let err = github.review(args).unwrap_err();
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::RemoteServerError(msg)) => {
+                assert!(msg.contains("Line could not be resolved"))
+            }
+            _ => panic!("Expected RemoteServerError"),
+        }
+    }
+
+    #[test]
+    fn test_list_pull_request_review_threads() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(
+                r#"{"data": {"repository": {"pullRequest": {"reviewThreads": {"nodes": [
+                    {"id": "PRRT_1", "isResolved": false, "path": "src/main.rs",
+                    "line": null, "originalLine": 7, "comments": {"nodes": [
+                    {"databaseId": 10, "body": "Typo", "author": {"login": "tom"},
+                    "createdAt": "2024-01-01T00:00:00Z"}]}},
+                    {"id": "PRRT_2", "isResolved": true, "path": "README.md",
+                    "line": 3, "originalLine": 3, "comments": {"nodes": []}}]}}}}}"#,
+            ),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestThread);
+        let threads = github.list(23).unwrap();
+        assert_eq!("https://api.github.com/graphql", *client.url());
+        let body: serde_json::Value = serde_json::from_str(&client.request_body.borrow()).unwrap();
+        assert_eq!("jordilin", body["variables"]["owner"]);
+        assert_eq!("githapi", body["variables"]["name"]);
+        assert_eq!(23, body["variables"]["number"]);
+        assert_eq!(2, threads.len());
+        assert_eq!("PRRT_1", threads[0].id);
+        assert!(threads[0].is_unresolved());
+        // Outdated threads fall back to the original line.
+        assert_eq!(Some(7), threads[0].line);
+        assert_eq!("tom", threads[0].notes[0].author);
+        assert!(threads[1].resolved);
+    }
+
+    #[test]
+    fn test_list_pull_request_review_threads_follows_end_cursor() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(
+                    r#"{"data": {"repository": {"pullRequest": {"reviewThreads": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": "Y3Vyc29yOjI="},
+                    "nodes": [{"id": "PRRT_2", "isResolved": true, "path": "README.md",
+                    "line": 3, "originalLine": 3, "comments": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": null}, "nodes": []}}]}}}}}"#,
+                ),
+                None,
+            )
+            .add_body(
+                200,
+                Some(
+                    r#"{"data": {"node": {"comments": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": "Y29tbWVudDoy"},
+                    "nodes": [{"databaseId": 11, "body": "Done", "author": {"login": "hfinn"},
+                    "createdAt": "2024-01-02T00:00:00Z"}]}}}}"#,
+                ),
+                None,
+            )
+            .add_body(
+                200,
+                Some(
+                    r#"{"data": {"repository": {"pullRequest": {"reviewThreads": {
+                    "pageInfo": {"hasNextPage": true, "endCursor": "Y3Vyc29yOjE="},
+                    "nodes": [{"id": "PRRT_1", "isResolved": false, "path": "src/main.rs",
+                    "line": 7, "originalLine": 7, "comments": {
+                    "pageInfo": {"hasNextPage": true, "endCursor": "Y29tbWVudDox"},
+                    "nodes": [{"databaseId": 10, "body": "Typo", "author": {"login": "tom"},
+                    "createdAt": "2024-01-01T00:00:00Z"}]}}]}}}}}"#,
+                ),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestThread);
+        let threads = github.list(23).unwrap();
+        assert_eq!(3, client.http_method.borrow().len());
+        let body: serde_json::Value = serde_json::from_str(&client.request_body.borrow()).unwrap();
+        assert_eq!("Y3Vyc29yOjE=", body["variables"]["cursor"]);
+        assert_eq!(2, threads.len());
+        assert_eq!("PRRT_1", threads[0].id);
+        assert_eq!(2, threads[0].notes.len());
+        assert_eq!("hfinn", threads[0].notes[1].author);
+        assert_eq!("PRRT_2", threads[1].id);
+    }
+
+    #[test]
+    fn test_unresolve_pull_request_review_thread() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(r#"{"data": {"unresolveReviewThread": {"clientMutationId": null}}}"#),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestThread);
+        github.resolve(23, "PRRT_1", false).unwrap();
+        let body = client.request_body.borrow();
+        assert!(body.contains("unresolveReviewThread"));
+        assert!(body.contains("PRRT_1"));
+    }
+
+    #[test]
+    fn test_reply_pull_request_review_thread_graphql_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(r#"{"errors": [{"message": "Could not resolve to a node"}]}"#),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequestThread);
+        let err = github.reply(23, "PRRT_1", "Done".to_string()).unwrap_err();
+        assert!(err.to_string().contains("Could not resolve to a node"));
+    }
+
+    #[test]
+    fn test_edit_pull_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(200, "comment.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestUpdateBodyArgs::builder()
+            .id(23)
+            .comment_id(1234)
+            .comment("LGTM!".to_string())
+            .build()
+            .unwrap();
+        github.update(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/comments/1234",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::PATCH], *client.http_method.borrow());
+        assert!(client.request_body.borrow().contains("LGTM!"));
+    }
+
+    #[test]
+    fn test_delete_pull_request_comment() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(204, Some(""), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        github.delete(23, 1234).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/comments/1234",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::DELETE], *client.http_method.borrow());
+    }
+
+    #[test]
+    fn test_react_to_pull_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(201, Some("{}"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        github.react(23, 1234, Reaction::ThumbsUp).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/comments/1234/reactions",
+            *client.url()
+        );
+        assert_eq!(r#"{"content":"+1"}"#, *client.request_body.borrow());
+    }
+
+    #[test]
+    fn test_list_pull_request_commits() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(
+                r#"[{"sha": "6dcb09b5", "commit": {"author": {"name": "Monalisa Octocat", "date": "2011-04-14T16:00:49Z"}, "message": "Fix all the bugs\n\nLong description"}}]"#
+                    .to_string(),
+            ),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestCommit);
+        let args = MergeRequestCommitListBodyArgs::builder()
+            .id(23)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let commits = github.list(args).unwrap();
+        assert_eq!(1, commits.len());
+        assert_eq!("6dcb09b5", commits[0].sha);
+        assert_eq!("Monalisa Octocat", commits[0].author);
+        assert_eq!("Fix all the bugs", commits[0].title);
+        assert_eq!("2011-04-14T16:00:49Z", commits[0].date);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/commits",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_list_pull_request_commits_with_check_runs() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(
+                    r#"{"total_count": 2, "check_runs": [{"status": "completed", "conclusion": "success"}, {"status": "in_progress", "conclusion": null}]}"#
+                        .to_string(),
+                ),
+                None,
+            )
+            .add_body(
+                200,
+                Some(
+                    r#"[{"sha": "6dcb09b5", "commit": {"author": {"name": "tom", "date": "2011-04-14T16:00:49Z"}, "message": "Fix"}}]"#
+                        .to_string(),
+                ),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestCommit);
+        let args = MergeRequestCommitListBodyArgs::builder()
+            .id(23)
+            .list_args(None)
+            .ci_status(true)
+            .build()
+            .unwrap();
+        let commits = github.list(args).unwrap();
+        assert_eq!("pending", commits[0].ci_status);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/commits/6dcb09b5/check-runs?per_page=100",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_list_pull_requests_by_source_branch() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(
+                r#"[{"number": 23, "state": "closed", "merged_at": "2024-03-02T00:00:00Z",
+                "html_url": "https://github.com/jordilin/githapi/pull/23",
+                "created_at": "2024-03-01T00:00:00Z", "head": {"ref": "stack/c++#1"}}]"#,
+            ),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::All)
+            .list_args(None)
+            .source_branch(Some("stack/c++#1".to_string()))
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls?state=all&head=jordilin:stack%2Fc%2B%2B%231",
+            *client.url()
+        );
+        assert_eq!("merged", merge_requests[0].state);
+    }
+
+    #[test]
+    fn test_pull_request_mergeability() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(
+                    r#"{"total_count": 1, "check_runs": [{"status": "completed", "conclusion": "failure"}]}"#
+                        .to_string(),
+                ),
+                None,
+            )
+            .add_body(200, Some(r#"{"ahead_by": 2, "behind_by": 4}"#.to_string()), None)
+            .add_body(
+                200,
+                Some(
+                    r#"{"number": 23, "draft": false, "mergeable": false, "mergeable_state": "dirty", "head": {"sha": "6dcb09b5"}, "base": {"ref": "main"}}"#
+                        .to_string(),
+                ),
+                None,
+            );
+        let (client, github) =
+            setup_client!(contracts, default_github(), dyn MergeRequestReadiness);
+        let mergeability = github.mergeability(23).unwrap();
+        assert!(!mergeability.draft);
+        assert!(mergeability.has_conflicts);
+        assert_eq!(Some(4), mergeability.behind_by);
+        assert_eq!("failed", mergeability.ci_status);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/commits/6dcb09b5/check-runs?per_page=100",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_pull_request_approvals_latest_review_per_user() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"required_approving_review_count": 2}"#.to_string()),
+                None,
+            )
+            .add_body(
+                200,
+                Some(r#"{"number": 23, "base": {"ref": "main"}}"#.to_string()),
+                None,
+            )
+            .add_body(
+                200,
+                Some(
+                    r#"[{"user": {"login": "tom"}, "state": "APPROVED"},
+                    {"user": {"login": "jerry"}, "state": "APPROVED"},
+                    {"user": {"login": "jerry"}, "state": "COMMENTED"},
+                    {"user": {"login": "tom"}, "state": "CHANGES_REQUESTED"},
+                    {"user": {"login": "spike"}, "state": "APPROVED"}]"#
+                        .to_string(),
+                ),
+                None,
+            );
+        let (client, github) =
+            setup_client!(contracts, default_github(), dyn MergeRequestReadiness);
+        let approvals = github.approvals(23).unwrap();
+        assert_eq!(2, approvals.approved);
+        assert_eq!(Some(2), approvals.required);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/branches/main/protection/required_pull_request_reviews",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_pull_request_approvals_reads_every_page_of_reviews() {
+        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/pulls/23/reviews?per_page=100&page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/pulls/23/reviews?per_page=100&page=2>; rel="last""#;
+        let mut headers = Headers::new();
+        headers.set("link".to_string(), link_header.to_string());
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(404, Some(r#"{"message": "Not Found"}"#.to_string()), None)
+            .add_body(
+                200,
+                Some(r#"{"number": 23, "base": {"ref": "main"}}"#.to_string()),
+                None,
+            )
+            .add_body(
+                200,
+                Some(
+                    r#"[{"user": {"login": "tom"}, "state": "CHANGES_REQUESTED"},
+                    {"user": {"login": "jerry"}, "state": "APPROVED"}]"#
+                        .to_string(),
+                ),
+                None,
+            )
+            .add_body(
+                200,
+                Some(r#"[{"user": {"login": "tom"}, "state": "APPROVED"}]"#.to_string()),
+                Some(headers),
+            );
+        let (client, github) =
+            setup_client!(contracts, default_github(), dyn MergeRequestReadiness);
+        let approvals = github.approvals(23).unwrap();
+        // The change request on the second page supersedes the approval.
+        assert_eq!(1, approvals.approved);
+        assert_eq!(4, client.http_method.borrow().len());
+    }
+
+    #[test]
+    fn test_pull_request_approvals_without_branch_protection_access() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(404, Some(r#"{"message": "Not Found"}"#.to_string()), None)
+            .add_body(
+                200,
+                Some(r#"{"number": 23, "base": {"ref": "main"}}"#.to_string()),
+                None,
+            )
+            .add_body(
+                200,
+                Some(r#"[{"user": {"login": "tom"}, "state": "APPROVED"}]"#.to_string()),
+                None,
+            );
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequestReadiness);
+        let approvals = github.approvals(23).unwrap();
+        assert_eq!(1, approvals.approved);
+        assert_eq!(None, approvals.required);
+    }
+
+    #[test]
+    fn test_pull_request_head_ref() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        assert_eq!("refs/pull/23/head", github.head_ref(23));
+    }
+
+    #[test]
+    fn test_update_pull_request_branch() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            202,
+            Some(r#"{"message": "Updating pull request branch."}"#.to_string()),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        github.rebase(23).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/update-branch",
+            *client.url()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_update_pull_request_branch_error_status_code() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            422,
+            Some(r#"{"message": "merge conflict between base and head"}"#.to_string()),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        assert!(github.rebase(23).is_err());
+    }
 }
@@ -1,8 +1,9 @@
 use super::Gitlab;
-use crate::api_traits::{ApiOperation, CicdJob, CicdRunner};
+use crate::api_traits::{ApiOperation, CicdJob, CicdJobArtifact, CicdRunner};
 use crate::cmds::cicd::{
-    Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, Runner, RunnerListBodyArgs,
-    RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse, RunnerStatus, YamlBytes,
+    Job, JobArtifact, JobArtifactBodyArgs, JobListBodyArgs, JobLogBodyArgs, LintResponse, Pipeline,
+    PipelineBodyArgs, PipelineCreateBodyArgs, Runner, RunnerListBodyArgs, RunnerMetadata,
+    RunnerPostDataCliArgs, RunnerRegistrationResponse, RunnerStatus, YamlBytes,
 };
 use crate::http::{self, Body, Headers};
 use crate::remote::{query, URLQueryParamBuilder};
@@ -11,10 +12,15 @@ use crate::{
     io::{HttpResponse, HttpRunner},
 };
 use crate::{time, Result};
+use std::io::Write;
 
 impl<R: HttpRunner<Response = HttpResponse>> Cicd for Gitlab<R> {
     fn list(&self, args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
-        let url = format!("{}/pipelines", self.rest_api_basepath());
+        let mut url = URLQueryParamBuilder::new(&format!("{}/pipelines", self.rest_api_basepath()));
+        if let Some(ref_name) = &args.ref_name {
+            url.add_param("ref", &http::encode_query_value(ref_name));
+        }
+        let url = url.build();
         query::paged(
             &self.runner,
             &url,
@@ -26,8 +32,17 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Gitlab<R> {
         )
     }
 
-    fn get_pipeline(&self, _id: i64) -> Result<Pipeline> {
-        todo!();
+    // https://docs.gitlab.com/ee/api/pipelines.html#get-a-single-pipeline
+    fn get_pipeline(&self, id: i64) -> Result<Pipeline> {
+        let url = format!("{}/pipelines/{}", self.rest_api_basepath(), id);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            |value| GitlabPipelineFields::from(value).into(),
+        )
     }
 
     fn num_pages(&self) -> Result<Option<u32>> {
@@ -55,6 +70,59 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Gitlab<R> {
             http::Method::POST,
         )
     }
+
+    // https://docs.gitlab.com/ee/api/pipelines.html#create-a-new-pipeline
+    fn create(&self, args: PipelineCreateBodyArgs) -> Result<Option<Pipeline>> {
+        let url = format!("{}/pipeline", self.rest_api_basepath());
+        let mut body = Body::new();
+        body.add("ref", serde_json::Value::String(args.ref_name));
+        if !args.variables.is_empty() {
+            let variables = args
+                .variables
+                .into_iter()
+                .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+                .collect();
+            body.add("variables", serde_json::Value::Array(variables));
+        }
+        let pipeline = query::send(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Pipeline,
+            |value| GitlabPipelineFields::from(value).into(),
+            http::Method::POST,
+        )?;
+        Ok(Some(pipeline))
+    }
+
+    // https://docs.gitlab.com/ee/api/pipelines.html#retry-jobs-in-a-pipeline
+    fn retry(&self, id: i64) -> Result<()> {
+        let url = format!("{}/pipelines/{}/retry", self.rest_api_basepath(), id);
+        query::send_raw::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.gitlab.com/ee/api/pipelines.html#cancel-a-pipelines-jobs
+    fn cancel(&self, id: i64) -> Result<()> {
+        let url = format!("{}/pipelines/{}/cancel", self.rest_api_basepath(), id);
+        query::send_raw::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
 }
 
 impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Gitlab<R> {
@@ -218,8 +286,9 @@ impl From<GitlabCicdJobFields> for Job {
 
 impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Gitlab<R> {
     // https://docs.gitlab.com/ee/api/jobs.html#list-project-jobs
+    // https://docs.gitlab.com/ee/api/jobs.html#list-pipeline-jobs
     fn list(&self, args: JobListBodyArgs) -> Result<Vec<Job>> {
-        let url = format!("{}/jobs", self.rest_api_basepath());
+        let url = self.list_jobs_url(&args, false);
         query::paged(
             &self.runner,
             &url,
@@ -231,21 +300,142 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Gitlab<R> {
         )
     }
 
-    fn num_pages(&self, _args: JobListBodyArgs) -> Result<Option<u32>> {
-        let url = format!("{}/jobs?page=1", self.rest_api_basepath());
+    // https://docs.gitlab.com/ee/api/jobs.html#get-a-single-job
+    fn get(&self, id: i64) -> Result<Job> {
+        let url = format!("{}/jobs/{}", self.rest_api_basepath(), id);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            |value| GitlabCicdJobFields::from(value).into(),
+        )
+    }
+
+    // https://docs.gitlab.com/ee/api/jobs.html#get-a-log-file
+    fn log(&self, args: JobLogBodyArgs) -> Result<String> {
+        let url = format!("{}/jobs/{}/trace", self.rest_api_basepath(), args.id);
+        let response = query::get_raw_with_backoff(
+            &self.runner,
+            &url,
+            self.headers(),
+            ApiOperation::Pipeline,
+            args.backoff_max_retries,
+            args.backoff_retry_after,
+        )?;
+        Ok(response.body)
+    }
+
+    fn num_pages(&self, args: JobListBodyArgs) -> Result<Option<u32>> {
+        let url = self.list_jobs_url(&args, true);
         query::num_pages(&self.runner, &url, self.headers(), ApiOperation::Pipeline)
     }
 
     fn num_resources(
         &self,
-        _args: JobListBodyArgs,
+        args: JobListBodyArgs,
     ) -> Result<Option<crate::api_traits::NumberDeltaErr>> {
-        let url = format!("{}/jobs?page=1", self.rest_api_basepath());
+        let url = self.list_jobs_url(&args, true);
         query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Pipeline)
     }
 }
 
+impl<R: HttpRunner<Response = HttpResponse>> CicdJobArtifact for Gitlab<R> {
+    // Artifacts are part of the job's metadata.
+    // https://docs.gitlab.com/ee/api/jobs.html#get-a-single-job
+    fn list(&self, job_id: i64) -> Result<Vec<JobArtifact>> {
+        let url = format!("{}/jobs/{}", self.rest_api_basepath(), job_id);
+        let job = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        Ok(job["artifacts"]
+            .as_array()
+            .map(|artifacts| {
+                artifacts
+                    .iter()
+                    .map(|value| GitlabJobArtifactFields::from(value).into())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    // https://docs.gitlab.com/ee/api/job_artifacts.html#get-job-artifacts
+    // https://docs.gitlab.com/ee/api/job_artifacts.html#download-a-single-artifact-file-by-job-id
+    fn download(&self, args: JobArtifactBodyArgs, writer: &mut dyn Write) -> Result<()> {
+        let url = match args.path {
+            Some(path) => format!(
+                "{}/jobs/{}/artifacts/{}",
+                self.rest_api_basepath(),
+                args.job_id,
+                path.trim_start_matches('/')
+            ),
+            None => format!(
+                "{}/jobs/{}/artifacts",
+                self.rest_api_basepath(),
+                args.job_id
+            ),
+        };
+        query::download(
+            &self.runner,
+            &url,
+            self.headers(),
+            ApiOperation::Pipeline,
+            writer,
+        )?;
+        Ok(())
+    }
+
+    fn downloads_archive(&self, path: Option<&str>) -> bool {
+        // A path selects a single file in the archive.
+        path.is_none()
+    }
+}
+
+pub struct GitlabJobArtifactFields {
+    artifact: JobArtifact,
+}
+
+impl From<&serde_json::Value> for GitlabJobArtifactFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabJobArtifactFields {
+            artifact: JobArtifact::builder()
+                .name(data["filename"].as_str().unwrap_or_default().to_string())
+                .file_type(data["file_type"].as_str().unwrap_or_default().to_string())
+                .size(data["size"].as_u64().unwrap_or_default())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabJobArtifactFields> for JobArtifact {
+    fn from(fields: GitlabJobArtifactFields) -> Self {
+        fields.artifact
+    }
+}
+
 impl<R> Gitlab<R> {
+    fn list_jobs_url(&self, args: &JobListBodyArgs, num_pages: bool) -> String {
+        let base_url = match args.pipeline_id {
+            Some(id) => format!("{}/pipelines/{}/jobs", self.rest_api_basepath(), id),
+            None => format!("{}/jobs", self.rest_api_basepath()),
+        };
+        let mut url = URLQueryParamBuilder::new(&base_url);
+        // Statuses are filtered server-side.
+        for status in &args.status {
+            url.add_param("scope[]", &status.to_string());
+        }
+        if num_pages {
+            url.add_param("page", "1");
+        }
+        url.build()
+    }
+
     fn list_runners_url(&self, args: &RunnerListBodyArgs, num_pages: bool) -> String {
         let base_url = if args.all {
             format!("{}/all", self.base_runner_url)
@@ -440,8 +630,7 @@ impl From<GitlabLintResponseFields> for LintResponse {
 
 #[cfg(test)]
 mod test {
-
-    use crate::cmds::cicd::{RunnerStatus, RunnerType};
+    use crate::cmds::cicd::{JobStatus, RunnerStatus, RunnerType};
     use crate::remote::ListBodyArgs;
     use crate::setup_client;
     use crate::test::utils::{default_gitlab, ContractType, ResponseContracts};
@@ -467,6 +656,41 @@ mod test {
         assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_list_pipelines_by_ref() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_pipelines.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        let args = PipelineBodyArgs::builder()
+            .from_to_page(None)
+            .ref_name(Some("fix/a&b".to_string()))
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines?ref=fix%2Fa%26b",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_get_pipeline() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "pipeline.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        let pipeline = gitlab.get_pipeline(1191917540).unwrap();
+        assert_eq!("created", pipeline.status);
+        assert!(!pipeline.is_finished());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/1191917540",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
     #[test]
     fn test_list_pipelines_with_stream_ok() {
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
@@ -829,6 +1053,220 @@ mod test {
         assert!(response.errors.len() > 0);
     }
 
+    #[test]
+    fn test_create_pipeline_with_variables() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "pipeline.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        let args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .variables(vec![("DEPLOY".to_string(), "true".to_string())])
+            .build()
+            .unwrap();
+        let pipeline = gitlab.create(args).unwrap().unwrap();
+        assert_eq!("created", pipeline.status);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipeline",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+        let body = client.request_body.borrow();
+        assert!(body.contains(r#""ref":"main""#));
+        assert!(body.contains(r#"{"key":"DEPLOY","value":"true"}"#));
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_pipeline_no_variables_not_in_body() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "pipeline.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        let args = PipelineCreateBodyArgs::builder()
+            .ref_name("main".to_string())
+            .build()
+            .unwrap();
+        gitlab.create(args).unwrap();
+        assert!(!client.request_body.borrow().contains("variables"));
+    }
+
+    #[test]
+    fn test_retry_pipeline() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "pipeline.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        gitlab.retry(1191917540).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/1191917540/retry",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_cancel_pipeline() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "pipeline.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        gitlab.cancel(1191917540).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/1191917540/cancel",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_cancel_pipeline_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(404, None, None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        assert!(gitlab.cancel(1191917540).is_err());
+    }
+
+    #[test]
+    fn test_get_gitlab_job() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "job.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        let job = gitlab.get(7).unwrap();
+        assert!(job.is_finished());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7",
+            *client.url()
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_gitlab_job_log() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some("Running with gitlab-runner 16.11.1\nJob succeeded\n".to_string()),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        let args = JobLogBodyArgs::builder().id(7).build().unwrap();
+        let log = gitlab.log(args).unwrap();
+        assert_eq!("Running with gitlab-runner 16.11.1\nJob succeeded\n", log);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7/trace",
+            *client.url()
+        );
+        assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
+    }
+
+    #[test]
+    fn test_get_gitlab_job_log_not_found_is_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(404, None, None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        let args = JobLogBodyArgs::builder().id(7).build().unwrap();
+        assert!(gitlab.log(args).is_err());
+    }
+
+    #[test]
+    fn test_list_gitlab_job_artifacts() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "job.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobArtifact);
+        let artifacts = gitlab.list(7).unwrap();
+        assert_eq!(4, artifacts.len());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_download_gitlab_job_artifacts_archive() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some("PK\u{3}\u{4}".to_string()),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobArtifact);
+        let args = JobArtifactBodyArgs::builder().job_id(7).build().unwrap();
+        let mut buf = Vec::new();
+        gitlab.download(args, &mut buf).unwrap();
+        assert_eq!(b"PK\x03\x04".to_vec(), buf);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7/artifacts",
+            *client.url()
+        );
+        assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_download_gitlab_job_single_artifact_file() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some("<testsuites/>".to_string()),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobArtifact);
+        let args = JobArtifactBodyArgs::builder()
+            .job_id(7)
+            .path(Some("target/junit.xml".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        gitlab.download(args, &mut buf).unwrap();
+        assert_eq!("<testsuites/>", String::from_utf8(buf).unwrap());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7/artifacts/target/junit.xml",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_download_gitlab_job_artifacts_not_found_is_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(404, None, None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobArtifact);
+        let args = JobArtifactBodyArgs::builder().job_id(7).build().unwrap();
+        let mut buf = Vec::new();
+        assert!(gitlab.download(args, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_gitlab_pipeline_jobs_filtered_by_status_server_side() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_project_jobs.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        let args = JobListBodyArgs::builder()
+            .list_args(None)
+            .pipeline_id(Some(123))
+            .status(vec![JobStatus::Failed, JobStatus::Manual])
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/123/jobs?scope[]=failed&scope[]=manual",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_gitlab_pipeline_jobs_num_pages() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(200, None, None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        let args = JobListBodyArgs::builder()
+            .list_args(None)
+            .pipeline_id(Some(123))
+            .build()
+            .unwrap();
+        gitlab.num_pages(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/123/jobs?page=1",
+            *client.url()
+        );
+    }
+
     #[test]
     fn test_gitlab_project_pipeline_jobs() {
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
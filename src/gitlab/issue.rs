@@ -0,0 +1,312 @@
+use crate::api_traits::{ApiOperation, CommentIssue, NumberDeltaErr, RemoteIssue};
+use crate::cmds::issue::{
+    CommentIssueBodyArgs, CommentIssueListBodyArgs, Issue, IssueBodyArgs, IssueListBodyArgs,
+};
+use crate::cmds::merge_request::Comment;
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::query;
+use crate::Result;
+
+use super::merge_request::GitlabMergeRequestCommentFields;
+use super::Gitlab;
+
+// https://docs.gitlab.com/ee/api/issues.html
+
+impl<R> Gitlab<R> {
+    fn list_issues_url(&self, args: &IssueListBodyArgs, num_pages: bool) -> String {
+        let mut url = format!("{}/issues?state={}", self.rest_api_basepath(), args.state);
+        if num_pages {
+            url.push_str("&page=1");
+        }
+        url
+    }
+
+    fn resource_issue_comments_metadata_url(&self, args: CommentIssueListBodyArgs) -> String {
+        format!(
+            "{}/issues/{}/notes?page=1",
+            self.rest_api_basepath(),
+            args.id
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> RemoteIssue for Gitlab<R> {
+    fn create(&self, args: IssueBodyArgs) -> Result<Issue> {
+        // POST /projects/:id/issues
+        let url = format!("{}/issues", self.rest_api_basepath());
+        let mut body = Body::new();
+        body.add("title", args.title);
+        body.add("description", args.description);
+        query::send::<_, String, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Issue,
+            |value| GitlabIssueFields::from(value).into(),
+            http::Method::POST,
+        )
+    }
+
+    fn list(&self, args: IssueListBodyArgs) -> Result<Vec<Issue>> {
+        let url = self.list_issues_url(&args, false);
+        query::paged(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.headers(),
+            None,
+            ApiOperation::Issue,
+            |value| GitlabIssueFields::from(value).into(),
+        )
+    }
+
+    fn get(&self, id: i64) -> Result<Issue> {
+        // GET /projects/:id/issues/:issue_iid
+        let url = format!("{}/issues/{}", self.rest_api_basepath(), id);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Issue,
+            |value| GitlabIssueFields::from(value).into(),
+        )
+    }
+
+    fn close(&self, id: i64) -> Result<Issue> {
+        // PUT /projects/:id/issues/:issue_iid
+        let url = format!("{}/issues/{}", self.rest_api_basepath(), id);
+        let mut body = Body::new();
+        body.add("state_event", "close");
+        query::send::<_, &str, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Issue,
+            |value| GitlabIssueFields::from(value).into(),
+            http::Method::PUT,
+        )
+    }
+
+    fn num_pages(&self, args: IssueListBodyArgs) -> Result<Option<u32>> {
+        let url = self.list_issues_url(&args, true);
+        query::num_pages(&self.runner, &url, self.headers(), ApiOperation::Issue)
+    }
+
+    fn num_resources(&self, args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.list_issues_url(&args, true);
+        query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Issue)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CommentIssue for Gitlab<R> {
+    fn create(&self, args: CommentIssueBodyArgs) -> Result<()> {
+        let url = format!("{}/issues/{}/notes", self.rest_api_basepath(), args.id);
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    fn list(&self, args: CommentIssueListBodyArgs) -> Result<Vec<Comment>> {
+        let url = format!("{}/issues/{}/notes", self.rest_api_basepath(), args.id);
+        query::paged(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.headers(),
+            None,
+            ApiOperation::Issue,
+            |value| GitlabMergeRequestCommentFields::from(value).into(),
+        )
+    }
+
+    fn num_pages(&self, args: CommentIssueListBodyArgs) -> Result<Option<u32>> {
+        let url = self.resource_issue_comments_metadata_url(args);
+        query::num_pages(&self.runner, &url, self.headers(), ApiOperation::Issue)
+    }
+
+    fn num_resources(&self, args: CommentIssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.resource_issue_comments_metadata_url(args);
+        query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Issue)
+    }
+}
+
+pub struct GitlabIssueFields {
+    issue: Issue,
+}
+
+impl From<&serde_json::Value> for GitlabIssueFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabIssueFields {
+            issue: Issue::builder()
+                .id(data["iid"].as_i64().unwrap_or_default())
+                .title(data["title"].as_str().unwrap_or_default().to_string())
+                .state(data["state"].as_str().unwrap_or_default().to_string())
+                .author(
+                    data["author"]["username"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .web_url(data["web_url"].as_str().unwrap_or_default().to_string())
+                .description(data["description"].as_str().unwrap_or_default().to_string())
+                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
+                .updated_at(data["updated_at"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabIssueFields> for Issue {
+    fn from(fields: GitlabIssueFields) -> Self {
+        fields.issue
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cmds::issue::IssueState;
+    use crate::http::Headers;
+    use crate::setup_client;
+    use crate::test::utils::{default_gitlab, get_contract, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_issues() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Gitlab, "issue.json")
+            )),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteIssue);
+        let args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let issues = gitlab.list(args).unwrap();
+        assert_eq!(1, issues.len());
+        assert_eq!(12, issues[0].id);
+        assert_eq!("jordilin", issues[0].author);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues?state=opened",
+            *client.url()
+        );
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "issue.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteIssue);
+        let issue = gitlab.get(12).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues/12",
+            *client.url()
+        );
+        assert_eq!("Crash on startup", issue.title);
+        assert_eq!("opened", issue.state);
+        assert_eq!(
+            "https://gitlab.com/jordilin/gitlapi/-/issues/12",
+            issue.web_url
+        );
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "issue.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteIssue);
+        let args = IssueBodyArgs::builder()
+            .title("Crash on startup".to_string())
+            .description("Application crashes".to_string())
+            .build()
+            .unwrap();
+        gitlab.create(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_close_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "issue.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteIssue);
+        gitlab.close(12).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues/12",
+            *client.url()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+        assert!(client.request_body.borrow().contains("close"));
+    }
+
+    #[test]
+    fn test_issues_num_pages() {
+        let link_header = "<https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues?state=opened&page=2>; rel=\"next\", <https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues?state=opened&page=3>; rel=\"last\"";
+        let mut headers = Headers::new();
+        headers.set("link", link_header);
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteIssue);
+        let args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        assert_eq!(Some(3), gitlab.num_pages(args).unwrap());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues?state=opened&page=1",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_list_issue_comments() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Gitlab, "comment.json")
+            )),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentIssue);
+        let args = CommentIssueListBodyArgs::builder()
+            .id(12)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let comments = gitlab.list(args).unwrap();
+        assert_eq!(1, comments.len());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues/12/notes",
+            *client.url()
+        );
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+}
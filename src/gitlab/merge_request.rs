@@ -1,8 +1,21 @@
-use crate::api_traits::{ApiOperation, CommentMergeRequest, NumberDeltaErr, RemoteProject};
+use crate::api_traits::{
+    ApiOperation, CommentMergeRequest, MergeRequestCommit, MergeRequestReadiness,
+    MergeRequestThread, NumberDeltaErr, RemoteProject, ReviewMergeRequest,
+};
 use crate::cli::browse::BrowseOptions;
+use crate::cmds::merge_request::commit::{
+    combined_status, CiStatus, Commit, MergeRequestCommitListBodyArgs,
+};
+use crate::cmds::merge_request::review::{
+    MergeRequestReviewBodyArgs, ReviewComment, ReviewSide, ReviewVerdict,
+};
+use crate::cmds::merge_request::status::{Approvals, Mergeability};
+use crate::cmds::merge_request::thread::Thread;
 use crate::cmds::merge_request::{
-    Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs, MergeRequestBodyArgs,
-    MergeRequestListBodyArgs, MergeRequestResponse,
+    count_diff_lines, Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs,
+    CommentMergeRequestUpdateBodyArgs, MergeMethod, MergeRequestBodyArgs, MergeRequestDiff,
+    MergeRequestListBodyArgs, MergeRequestMergeBodyArgs, MergeRequestResponse,
+    MergeRequestUpdateBodyArgs, Reaction,
 };
 use crate::cmds::project::MrMemberType;
 use crate::error::{self, GRError};
@@ -21,31 +34,45 @@ use super::Gitlab;
 
 impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
     fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
-        let mut body = Body::new();
-        body.add("source_branch", args.source_branch);
-        body.add("target_branch", args.target_branch);
-        body.add("title", args.title);
+        let mut body: Body<serde_json::Value> = Body::new();
+        body.add("source_branch", serde_json::Value::from(args.source_branch));
+        body.add("target_branch", serde_json::Value::from(args.target_branch));
+        body.add("title", serde_json::Value::from(args.title));
         match args.assignee.mr_member_type {
             MrMemberType::Filled => {
-                body.add("assignee_id", args.assignee.id.to_string());
+                body.add("assignee_id", serde_json::Value::from(args.assignee.id));
             }
             MrMemberType::Empty => {}
         }
-        match args.reviewer.mr_member_type {
-            MrMemberType::Filled => {
-                // We support one reviewer for now. The CE edition of Gitlab
-                // only supports one, so we'll keep it simple.
-                body.add("reviewer_ids", args.reviewer.id.to_string());
-            }
-            MrMemberType::Empty => {}
+        let reviewer_ids: Vec<i64> = args
+            .reviewers
+            .iter()
+            .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+            .map(|reviewer| reviewer.id)
+            .collect();
+        if !reviewer_ids.is_empty() {
+            // Gitlab CE only considers the first reviewer.
+            body.add("reviewer_ids", serde_json::Value::from(reviewer_ids));
+        }
+        body.add("description", serde_json::Value::from(args.description));
+        body.add(
+            "remove_source_branch",
+            serde_json::Value::from(args.remove_source_branch),
+        );
+        if !args.labels.is_empty() {
+            body.add("labels", serde_json::Value::from(args.labels.join(",")));
+        }
+        if let Some(milestone) = &args.milestone {
+            body.add(
+                "milestone_id",
+                serde_json::Value::from(self.milestone_id(milestone)?),
+            );
         }
-        body.add("description", args.description);
-        body.add("remove_source_branch", args.remove_source_branch);
         // if target repo provided, add target_project_id in the payload
         if !args.target_repo.is_empty() {
             match self.get_project_data(None, Some(&args.target_repo)) {
                 Ok(CmdInfo::Project(project)) => {
-                    body.add("target_project_id", project.id.to_string());
+                    body.add("target_project_id", serde_json::Value::from(project.id));
                 }
                 Ok(_) => {
                     // Application error - any other CmdInfo variant is unexpected
@@ -138,13 +165,47 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
         )
     }
 
-    fn merge(&self, id: i64) -> Result<MergeRequestResponse> {
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
         // PUT /projects/:id/merge_requests/:merge_request_iid/merge
-        let url = format!("{}/merge_requests/{}/merge", self.rest_api_basepath(), id);
-        query::send::<_, (), _>(
+        // https://docs.gitlab.com/ee/api/merge_requests.html#merge-a-merge-request
+        if args.merge_method == Some(MergeMethod::Rebase) {
+            return Err(GRError::OperationNotSupported(
+                "Gitlab sets the merge method in the project settings. Only squash can be requested"
+                    .to_string(),
+            )
+            .into());
+        }
+        let url = format!(
+            "{}/merge_requests/{}/merge",
+            self.rest_api_basepath(),
+            args.id
+        );
+        let mut body: Body<serde_json::Value> = Body::new();
+        if args.squash() {
+            body.add("squash", serde_json::Value::from(true));
+        }
+        if let Some(squash_message) = args.squash_message {
+            body.add(
+                "squash_commit_message",
+                serde_json::Value::from(squash_message),
+            );
+        }
+        if args.delete_branch {
+            body.add("should_remove_source_branch", serde_json::Value::from(true));
+        }
+        if let Some(sha) = args.sha {
+            body.add("sha", serde_json::Value::from(sha));
+        }
+        if args.auto_merge {
+            body.add(
+                "merge_when_pipeline_succeeds",
+                serde_json::Value::from(true),
+            );
+        }
+        query::send(
             &self.runner,
             &url,
-            None,
+            Some(&body),
             self.headers(),
             ApiOperation::MergeRequest,
             |value| GitlabMergeRequestFields::from(value).into(),
@@ -152,6 +213,44 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
         )
     }
 
+    fn diff(&self, id: i64) -> Result<Vec<MergeRequestDiff>> {
+        // GET /projects/:id/merge_requests/:merge_request_iid/diffs
+        // https://docs.gitlab.com/ee/api/merge_requests.html#list-merge-request-diffs
+        let url = format!(
+            "{}/merge_requests/{}/diffs?per_page=100",
+            self.rest_api_basepath(),
+            id
+        );
+        query::paged_unsorted(
+            &self.runner,
+            &url,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            |value| GitlabMergeRequestDiffFields::from(value).into(),
+        )
+    }
+
+    fn head_ref(&self, id: i64) -> String {
+        // https://docs.gitlab.com/ee/user/project/merge_requests/merge_request_troubleshooting.html#check-out-merge-requests-locally-through-the-head-ref
+        format!("refs/merge-requests/{}/head", id)
+    }
+
+    fn rebase(&self, id: i64) -> Result<()> {
+        // PUT /projects/:id/merge_requests/:merge_request_iid/rebase
+        // https://docs.gitlab.com/ee/api/merge_requests.html#rebase-a-merge-request
+        // Responds with a 202 Accepted, the rebase runs in the background.
+        let url = format!("{}/merge_requests/{}/rebase", self.rest_api_basepath(), id);
+        query::send_raw::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+
     fn get(&self, id: i64) -> Result<MergeRequestResponse> {
         // GET /projects/:id/merge_requests/:merge_request_iid
         let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), id);
@@ -180,6 +279,69 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
         )
     }
 
+    fn reopen(&self, id: i64) -> Result<MergeRequestResponse> {
+        let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), id);
+        let mut body = Body::new();
+        body.add("state_event", "reopen");
+        query::send::<_, &str, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            |value| GitlabMergeRequestFields::from(value).into(),
+            http::Method::PUT,
+        )
+    }
+
+    fn update(&self, args: MergeRequestUpdateBodyArgs) -> Result<MergeRequestResponse> {
+        // PUT /projects/:id/merge_requests/:merge_request_iid
+        let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), args.id);
+        let mut title = args.title;
+        if let Some(draft) = args.draft {
+            let current_title = match title {
+                Some(title) => title,
+                None => self.get(args.id)?.title,
+            };
+            title = Some(draft_title(&current_title, draft));
+        }
+        let mut body = Body::new();
+        if let Some(title) = title {
+            body.add("title", serde_json::Value::from(title));
+        }
+        if let Some(description) = args.description {
+            body.add("description", serde_json::Value::from(description));
+        }
+        if let Some(target_branch) = args.target_branch {
+            body.add("target_branch", serde_json::Value::from(target_branch));
+        }
+        if let Some(assignee) = args.assignee {
+            body.add("assignee_id", serde_json::Value::from(assignee.id));
+        }
+        if let Some(reviewers) = args.reviewers {
+            let reviewer_ids: Vec<i64> = reviewers.iter().map(|reviewer| reviewer.id).collect();
+            body.add("reviewer_ids", serde_json::Value::from(reviewer_ids));
+        }
+        if let Some(labels) = args.labels {
+            body.add("labels", serde_json::Value::from(labels.join(",")));
+        }
+        if let Some(milestone) = args.milestone {
+            body.add(
+                "milestone_id",
+                serde_json::Value::from(self.milestone_id(&milestone)?),
+            );
+        }
+        query::send(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            |value| GitlabMergeRequestFields::from(value).into(),
+            http::Method::PUT,
+        )
+    }
+
     fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
         let url = self.list_merge_request_url(&args, true);
         let mut headers = Headers::new();
@@ -215,6 +377,26 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
     }
 }
 
+// Gitlab has no draft attribute when updating a merge request. Drafts are
+// flagged by prefixing the title instead.
+// https://docs.gitlab.com/ee/user/project/merge_requests/drafts.html
+const DRAFT_PREFIXES: [&str; 4] = ["draft:", "[draft]", "(draft)", "wip:"];
+
+fn draft_title(title: &str, draft: bool) -> String {
+    let mut title = title.trim_start();
+    while let Some(prefix) = DRAFT_PREFIXES
+        .iter()
+        .find(|prefix| title.to_lowercase().starts_with(*prefix))
+    {
+        title = title[prefix.len()..].trim_start();
+    }
+    if draft {
+        format!("Draft: {}", title)
+    } else {
+        title.to_string()
+    }
+}
+
 impl<R> Gitlab<R> {
     fn list_merge_request_url(&self, args: &MergeRequestListBodyArgs, num_pages: bool) -> String {
         let mut url = if let Some(assignee) = &args.assignee {
@@ -239,6 +421,20 @@ impl<R> Gitlab<R> {
                 args.state
             )
         };
+        if !args.labels.is_empty() {
+            let labels: Vec<String> = args
+                .labels
+                .iter()
+                .map(|label| http::encode_query_value(label))
+                .collect();
+            url.push_str(&format!("&labels={}", labels.join(",")));
+        }
+        if let Some(source_branch) = &args.source_branch {
+            url.push_str(&format!(
+                "&source_branch={}",
+                http::encode_query_value(source_branch)
+            ));
+        }
         if num_pages {
             url.push_str("&page=1");
         }
@@ -255,6 +451,28 @@ impl<R> Gitlab<R> {
     }
 }
 
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    /// Milestones are given by title, but merge requests reference them by id.
+    fn milestone_id(&self, title: &str) -> Result<i64> {
+        // GET /projects/:id/milestones?title=:title
+        let url = format!(
+            "{}/milestones?title={}",
+            self.rest_api_basepath(),
+            http::encode_query_value(title)
+        );
+        let milestones = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        milestones[0]["id"].as_i64().ok_or_else(|| {
+            GRError::PreconditionNotMet(format!("Milestone {} not found", title)).into()
+        })
+    }
+}
+
 impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Gitlab<R> {
     fn create(&self, args: CommentMergeRequestBodyArgs) -> Result<()> {
         let url = format!(
@@ -275,6 +493,75 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Gitlab<R> {
         Ok(())
     }
 
+    fn update(&self, args: CommentMergeRequestUpdateBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/notes/{}",
+            self.rest_api_basepath(),
+            args.id,
+            args.comment_id
+        );
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, id: i64, comment_id: i64) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/notes/{}",
+            self.rest_api_basepath(),
+            id,
+            comment_id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    fn react(&self, id: i64, comment_id: i64, reaction: Reaction) -> Result<()> {
+        // https://docs.gitlab.com/ee/api/emoji_reactions.html
+        let url = format!(
+            "{}/merge_requests/{}/notes/{}/award_emoji",
+            self.rest_api_basepath(),
+            id,
+            comment_id
+        );
+        let name = match reaction {
+            Reaction::ThumbsUp => "thumbsup",
+            Reaction::ThumbsDown => "thumbsdown",
+            Reaction::Laugh => "laughing",
+            Reaction::Confused => "confused",
+            Reaction::Heart => "heart",
+            Reaction::Hooray => "tada",
+            Reaction::Rocket => "rocket",
+            Reaction::Eyes => "eyes",
+        };
+        let mut body = Body::new();
+        body.add("name", name);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
     fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
         let url = format!(
             "{}/merge_requests/{}/notes",
@@ -317,93 +604,541 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Gitlab<R> {
     }
 }
 
-pub struct GitlabMergeRequestFields {
-    fields: MergeRequestResponse,
-}
-
-impl From<&serde_json::Value> for GitlabMergeRequestFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabMergeRequestFields {
-            fields: MergeRequestResponse::builder()
-                .id(data["iid"].as_i64().unwrap_or_default())
-                .web_url(data["web_url"].as_str().unwrap_or_default().to_string())
-                .source_branch(
-                    data["source_branch"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .sha(
-                    data["merge_commit_sha"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .author(
-                    data["author"]["username"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .updated_at(data["updated_at"].as_str().unwrap_or_default().to_string())
-                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
-                .title(data["title"].as_str().unwrap_or_default().to_string())
-                .description(data["description"].as_str().unwrap_or_default().to_string())
-                // If merge request is not merged, merged_at is an empty string.
-                .merged_at(data["merged_at"].as_str().unwrap_or_default().to_string())
-                // Documentation recommends gathering head_pipeline instead of
-                // pipeline key.
-                .pipeline_id(data["head_pipeline"]["id"].as_i64())
-                .pipeline_url(
-                    data["head_pipeline"]["web_url"]
-                        .as_str()
-                        .map(|s| s.to_string()),
-                )
-                .build()
-                .unwrap(),
+// Review comments are created as draft notes, so they are all published at
+// once when the review is submitted.
+// https://docs.gitlab.com/ee/api/draft_notes.html
+impl<R: HttpRunner<Response = HttpResponse>> ReviewMergeRequest for Gitlab<R> {
+    fn review(&self, args: MergeRequestReviewBodyArgs) -> Result<()> {
+        if args.verdict == ReviewVerdict::RequestChanges {
+            return Err(GRError::OperationNotSupported(
+                "Gitlab does not support requesting changes in a review".to_string(),
+            )
+            .into());
+        }
+        let url = format!(
+            "{}/merge_requests/{}/draft_notes",
+            self.rest_api_basepath(),
+            args.id
+        );
+        if !args.comments.is_empty() {
+            let diff_refs = self.diff_refs(args.id)?;
+            for comment in &args.comments {
+                let body = draft_note_body(comment, &diff_refs);
+                query::send_raw(
+                    &self.runner,
+                    &url,
+                    Some(&body),
+                    self.headers(),
+                    ApiOperation::MergeRequest,
+                    http::Method::POST,
+                )?;
+            }
+        }
+        if let Some(summary) = &args.body {
+            let mut body = Body::new();
+            body.add("note", summary.to_string());
+            query::send_raw(
+                &self.runner,
+                &url,
+                Some(&body),
+                self.headers(),
+                ApiOperation::MergeRequest,
+                http::Method::POST,
+            )?;
+        }
+        if !args.comments.is_empty() || args.body.is_some() {
+            query::send_raw::<_, ()>(
+                &self.runner,
+                &format!("{}/bulk_publish", url),
+                None,
+                self.headers(),
+                ApiOperation::MergeRequest,
+                http::Method::POST,
+            )?;
         }
+        if args.verdict == ReviewVerdict::Approve {
+            self.approve(args.id)?;
+        }
+        Ok(())
     }
 }
 
-impl From<GitlabMergeRequestFields> for MergeRequestResponse {
-    fn from(fields: GitlabMergeRequestFields) -> Self {
-        fields.fields
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    /// Commit SHAs the merge request diff is computed against. Required to
+    /// anchor comments to a line in the diff.
+    fn diff_refs(&self, id: i64) -> Result<serde_json::Value> {
+        let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), id);
+        let merge_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        Ok(merge_request["diff_refs"].clone())
     }
 }
 
-pub struct GitlabMergeRequestCommentFields {
-    comment: Comment,
-}
-
-impl From<&serde_json::Value> for GitlabMergeRequestCommentFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabMergeRequestCommentFields {
-            comment: Comment::builder()
-                .id(data["id"].as_i64().unwrap_or_default())
-                .body(data["body"].as_str().unwrap_or_default().to_string())
-                .author(
-                    data["author"]["username"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
-                .build()
-                .unwrap(),
-        }
-    }
+fn draft_note_body(
+    comment: &ReviewComment,
+    diff_refs: &serde_json::Value,
+) -> Body<serde_json::Value> {
+    let line_key = match comment.side {
+        ReviewSide::Old => "old_line",
+        ReviewSide::New => "new_line",
+    };
+    let mut body = Body::new();
+    body.add("note", serde_json::Value::from(comment.body.clone()));
+    body.add(
+        "position",
+        serde_json::json!({
+            "position_type": "text",
+            "base_sha": diff_refs["base_sha"],
+            "start_sha": diff_refs["start_sha"],
+            "head_sha": diff_refs["head_sha"],
+            "old_path": comment.path,
+            "new_path": comment.path,
+            line_key: comment.line,
+        }),
+    );
+    body
 }
 
-impl From<GitlabMergeRequestCommentFields> for Comment {
-    fn from(fields: GitlabMergeRequestCommentFields) -> Self {
-        fields.comment
+// https://docs.gitlab.com/ee/api/discussions.html#merge-requests
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestThread for Gitlab<R> {
+    fn list(&self, id: i64) -> Result<Vec<Thread>> {
+        let url = format!(
+            "{}/merge_requests/{}/discussions?per_page=100",
+            self.rest_api_basepath(),
+            id
+        );
+        let threads: Vec<Thread> = query::paged(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            None,
+            ApiOperation::MergeRequest,
+            |value| GitlabThreadFields::from(value).into(),
+        )?;
+        // Discussions made only of system notes, ex. pushed commits, are not
+        // conversations.
+        Ok(threads
+            .into_iter()
+            .filter(|thread| !thread.notes.is_empty())
+            .collect())
     }
-}
 
-#[cfg(test)]
-mod test {
-
-    use crate::cmds::merge_request::MergeRequestState;
+    fn reply(&self, id: i64, thread_id: &str, body: String) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/discussions/{}/notes",
+            self.rest_api_basepath(),
+            id,
+            thread_id
+        );
+        let mut request_body = Body::new();
+        request_body.add("body", body);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&request_body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    fn resolve(&self, id: i64, thread_id: &str, resolved: bool) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/discussions/{}?resolved={}",
+            self.rest_api_basepath(),
+            id,
+            thread_id,
+            resolved
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestCommit for Gitlab<R> {
+    fn list(&self, args: MergeRequestCommitListBodyArgs) -> Result<Vec<Commit>> {
+        let url = format!(
+            "{}/merge_requests/{}/commits",
+            self.rest_api_basepath(),
+            args.id
+        );
+        query::paged(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.headers(),
+            None,
+            ApiOperation::MergeRequest,
+            |value| {
+                let mut commit: Commit = GitlabCommitFields::from(value).into();
+                if args.ci_status {
+                    // Statuses are gathered while paging, so streamed pages
+                    // carry them too.
+                    commit.ci_status = self
+                        .commit_ci_status(&commit.sha)
+                        .unwrap_or_else(|_| "unknown".to_string());
+                }
+                commit
+            },
+        )
+    }
+
+    fn num_pages(&self, args: MergeRequestCommitListBodyArgs) -> Result<Option<u32>> {
+        let url = self.resource_commits_metadata_url(args);
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )
+    }
+
+    fn num_resources(
+        &self,
+        args: MergeRequestCommitListBodyArgs,
+    ) -> Result<Option<NumberDeltaErr>> {
+        let url = self.resource_commits_metadata_url(args);
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    fn resource_commits_metadata_url(&self, args: MergeRequestCommitListBodyArgs) -> String {
+        format!(
+            "{}/merge_requests/{}/commits?page=1",
+            self.rest_api_basepath(),
+            args.id
+        )
+    }
+
+    /// Combined status of the jobs reported on a commit.
+    /// https://docs.gitlab.com/ee/api/commits.html#list-the-statuses-of-a-commit
+    fn commit_ci_status(&self, sha: &str) -> Result<String> {
+        let url = format!(
+            "{}/repository/commits/{}/statuses?per_page=100",
+            self.rest_api_basepath(),
+            sha
+        );
+        let statuses = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let statuses: Vec<CiStatus> = statuses
+            .as_array()
+            .map(|statuses| {
+                statuses
+                    .iter()
+                    .map(|status| ci_status(status["status"].as_str().unwrap_or_default()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(combined_status(&statuses))
+    }
+}
+
+/// Normalizes the status of a Gitlab job or pipeline.
+fn ci_status(status: &str) -> CiStatus {
+    match status {
+        "success" | "skipped" | "manual" => CiStatus::Success,
+        "failed" | "canceled" => CiStatus::Failed,
+        _ => CiStatus::Pending,
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestReadiness for Gitlab<R> {
+    fn mergeability(&self, id: i64) -> Result<Mergeability> {
+        // https://docs.gitlab.com/ee/api/merge_requests.html#get-single-mr
+        let url = format!(
+            "{}/merge_requests/{}?include_diverged_commits_count=true",
+            self.rest_api_basepath(),
+            id
+        );
+        let merge_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let ci_status = match merge_request["head_pipeline"]["status"].as_str() {
+            Some(status) => combined_status(&[ci_status(status)]),
+            None => "".to_string(),
+        };
+        Ok(Mergeability::builder()
+            .draft(merge_request["draft"].as_bool().unwrap_or_default())
+            .has_conflicts(merge_request["has_conflicts"].as_bool().unwrap_or_default())
+            .behind_by(merge_request["diverged_commits_count"].as_u64())
+            .ci_status(ci_status)
+            .build()?)
+    }
+
+    fn approvals(&self, id: i64) -> Result<Approvals> {
+        // https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-configuration-1
+        let url = format!(
+            "{}/merge_requests/{}/approvals",
+            self.rest_api_basepath(),
+            id
+        );
+        let approvals = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let approved = approvals["approved_by"]
+            .as_array()
+            .map_or(0, |approved_by| approved_by.len() as u32);
+        Ok(Approvals::builder()
+            .approved(approved)
+            .required(
+                approvals["approvals_required"]
+                    .as_u64()
+                    .map(|required| required as u32),
+            )
+            .build()?)
+    }
+}
+
+pub struct GitlabMergeRequestFields {
+    fields: MergeRequestResponse,
+}
+
+impl From<&serde_json::Value> for GitlabMergeRequestFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabMergeRequestFields {
+            fields: MergeRequestResponse::builder()
+                .id(data["iid"].as_i64().unwrap_or_default())
+                .web_url(data["web_url"].as_str().unwrap_or_default().to_string())
+                .source_branch(
+                    data["source_branch"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .target_branch(
+                    data["target_branch"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .sha(
+                    data["merge_commit_sha"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .author(
+                    data["author"]["username"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .updated_at(data["updated_at"].as_str().unwrap_or_default().to_string())
+                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
+                .title(data["title"].as_str().unwrap_or_default().to_string())
+                .state(data["state"].as_str().unwrap_or_default().to_string())
+                .labels(
+                    data["labels"]
+                        .as_array()
+                        .map(|labels| {
+                            labels
+                                .iter()
+                                .filter_map(|label| label.as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .milestone(
+                    data["milestone"]["title"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .description(data["description"].as_str().unwrap_or_default().to_string())
+                // If merge request is not merged, merged_at is an empty string.
+                .merged_at(data["merged_at"].as_str().unwrap_or_default().to_string())
+                // Documentation recommends gathering head_pipeline instead of
+                // pipeline key.
+                .pipeline_id(data["head_pipeline"]["id"].as_i64())
+                .pipeline_url(
+                    data["head_pipeline"]["web_url"]
+                        .as_str()
+                        .map(|s| s.to_string()),
+                )
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabMergeRequestFields> for MergeRequestResponse {
+    fn from(fields: GitlabMergeRequestFields) -> Self {
+        fields.fields
+    }
+}
+
+pub struct GitlabMergeRequestCommentFields {
+    comment: Comment,
+}
+
+impl From<&serde_json::Value> for GitlabMergeRequestCommentFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabMergeRequestCommentFields {
+            comment: Comment::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .body(data["body"].as_str().unwrap_or_default().to_string())
+                .author(
+                    data["author"]["username"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabMergeRequestCommentFields> for Comment {
+    fn from(fields: GitlabMergeRequestCommentFields) -> Self {
+        fields.comment
+    }
+}
+
+pub struct GitlabCommitFields {
+    commit: Commit,
+}
+
+impl From<&serde_json::Value> for GitlabCommitFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabCommitFields {
+            commit: Commit::builder()
+                .sha(data["id"].as_str().unwrap_or_default().to_string())
+                .author(data["author_name"].as_str().unwrap_or_default().to_string())
+                .title(data["title"].as_str().unwrap_or_default().to_string())
+                .date(data["created_at"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabCommitFields> for Commit {
+    fn from(fields: GitlabCommitFields) -> Self {
+        fields.commit
+    }
+}
+
+pub struct GitlabThreadFields {
+    thread: Thread,
+}
+
+impl From<&serde_json::Value> for GitlabThreadFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let empty = vec![];
+        let notes = data["notes"].as_array().unwrap_or(&empty);
+        // Resolvable state and position are the same for all the notes in a
+        // discussion, take them from the first one.
+        let first = notes.first().cloned().unwrap_or_default();
+        let position = &first["position"];
+        let (path, line) = if position["new_line"].is_i64() {
+            (&position["new_path"], &position["new_line"])
+        } else {
+            (&position["old_path"], &position["old_line"])
+        };
+        GitlabThreadFields {
+            thread: Thread::builder()
+                .id(data["id"].as_str().unwrap_or_default().to_string())
+                .resolvable(first["resolvable"].as_bool().unwrap_or_default())
+                .resolved(first["resolved"].as_bool().unwrap_or_default())
+                .path(path.as_str().map(|path| path.to_string()))
+                .line(line.as_i64())
+                .notes(
+                    notes
+                        .iter()
+                        .filter(|note| !note["system"].as_bool().unwrap_or_default())
+                        .map(|note| GitlabMergeRequestCommentFields::from(note).into())
+                        .collect(),
+                )
+                .created_at(first["created_at"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabThreadFields> for Thread {
+    fn from(fields: GitlabThreadFields) -> Self {
+        fields.thread
+    }
+}
+
+pub struct GitlabMergeRequestDiffFields {
+    diff: MergeRequestDiff,
+}
+
+impl From<&serde_json::Value> for GitlabMergeRequestDiffFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let status = if data["new_file"].as_bool().unwrap_or_default() {
+            "added"
+        } else if data["deleted_file"].as_bool().unwrap_or_default() {
+            "removed"
+        } else if data["renamed_file"].as_bool().unwrap_or_default() {
+            "renamed"
+        } else {
+            "modified"
+        };
+        let diff = data["diff"].as_str().unwrap_or_default();
+        // Gitlab does not report the number of changed lines per file.
+        let (additions, deletions) = count_diff_lines(diff);
+        GitlabMergeRequestDiffFields {
+            diff: MergeRequestDiff::builder()
+                .old_path(data["old_path"].as_str().unwrap_or_default().to_string())
+                .new_path(data["new_path"].as_str().unwrap_or_default().to_string())
+                .status(status.to_string())
+                .additions(additions)
+                .deletions(deletions)
+                .diff(diff.to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabMergeRequestDiffFields> for MergeRequestDiff {
+    fn from(fields: GitlabMergeRequestDiffFields) -> Self {
+        fields.diff
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::cmds::merge_request::MergeRequestState;
     use crate::cmds::project::Member;
     use crate::remote::ListBodyArgs;
     use crate::setup_client;
@@ -525,9 +1260,16 @@ mod test {
             .id(5678)
             .build()
             .unwrap();
+        let second_reviewer = Member::builder()
+            .name("jim".to_string())
+            .username("jim".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .id(9012)
+            .build()
+            .unwrap();
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer, second_reviewer])
             .build()
             .unwrap();
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
@@ -548,22 +1290,88 @@ mod test {
             *client.api_operation.borrow()
         );
         let actual_body = client.request_body.borrow();
-        assert!(actual_body.contains("assignee_id"));
-        assert!(actual_body.contains("reviewer_ids"));
+        assert!(actual_body.contains("\"assignee_id\":1234"));
+        assert!(actual_body.contains("\"reviewer_ids\":[5678,9012]"));
     }
 
     #[test]
-    fn test_open_merge_request_with_no_assignee() {
-        let assignee = Member::default();
+    fn test_open_merge_request_with_labels_and_milestone() {
         let mr_args = MergeRequestBodyArgs::builder()
-            .assignee(assignee)
+            .labels(vec!["needs-review".to_string(), "bug".to_string()])
+            .milestone(Some("v1.0".to_string()))
             .build()
             .unwrap();
-        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
-            201,
-            "merge_request.json",
-            None,
-        );
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(201, "merge_request.json", None)
+            .add_body(200, Some(r#"[{"id": 12, "title": "v1.0"}]"#), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        gitlab.open(mr_args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests",
+            *client.url(),
+        );
+        let actual_body = client.request_body.borrow();
+        assert!(actual_body.contains("\"labels\":\"needs-review,bug\""));
+        assert!(actual_body.contains("\"milestone_id\":12"));
+    }
+
+    #[test]
+    fn test_open_merge_request_milestone_not_found_is_error() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .milestone(Some("v9.9 #1".to_string()))
+            .build()
+            .unwrap();
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let err = gitlab.open(mr_args).unwrap_err();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/milestones?title=v9.9%20%231",
+            *client.url(),
+        );
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("Milestone v9.9 #1 not found", msg)
+            }
+            _ => panic!("Expected PreconditionNotMet error"),
+        }
+    }
+
+    #[test]
+    fn test_list_merge_requests_by_labels() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(r#"[{"iid": 1, "labels": ["bug", "ui"], "milestone": {"title": "v1.0"}, "created_at": "2024-01-01T00:00:00Z"}]"#),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .labels(vec!["bug".to_string(), "ui & ux".to_string()])
+            .build()
+            .unwrap();
+        let merge_requests = gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened&labels=bug,ui%20%26%20ux",
+            *client.url()
+        );
+        assert_eq!(vec!["bug", "ui"], merge_requests[0].labels);
+        assert_eq!("v1.0", merge_requests[0].milestone);
+    }
+
+    #[test]
+    fn test_open_merge_request_with_no_assignee() {
+        let assignee = Member::default();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .assignee(assignee)
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            201,
+            "merge_request.json",
+            None,
+        );
         let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
         assert!(gitlab.open(mr_args).is_ok());
         assert_eq!(
@@ -789,7 +1597,8 @@ mod test {
         );
         let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
         let merge_request_id = 123456;
-        gitlab.get(merge_request_id).unwrap();
+        let merge_request = gitlab.get(merge_request_id).unwrap();
+        assert_eq!("main", merge_request.target_branch);
         assert_eq!(
             "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123456",
             *client.url()
@@ -808,8 +1617,8 @@ mod test {
             None,
         );
         let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
-        let merge_request_id = 33;
-        gitlab.merge(merge_request_id).unwrap();
+        let args = MergeRequestMergeBodyArgs::builder().id(33).build().unwrap();
+        gitlab.merge(args).unwrap();
         assert_eq!(
             "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/merge",
             *client.url()
@@ -820,6 +1629,79 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_merge_request_diff() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(
+                r#"[{"old_path": "README.md", "new_path": "README.md", "new_file": false,
+                "renamed_file": false, "deleted_file": false,
+                "diff": "@@ -1 +1,2 @@\n-# gitar\n+# gr\n+Git all remotes\n"},
+                {"old_path": "old.rs", "new_path": "new.rs", "new_file": false,
+                "renamed_file": true, "deleted_file": false, "diff": ""}]"#,
+            ),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let diffs = gitlab.diff(33).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/diffs?per_page=100",
+            *client.url()
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+        assert_eq!(2, diffs.len());
+        assert_eq!("modified", diffs[0].status);
+        assert_eq!(2, diffs[0].additions);
+        assert_eq!(1, diffs[0].deletions);
+        assert_eq!("renamed", diffs[1].status);
+        assert_eq!("old.rs", diffs[1].old_path);
+    }
+
+    #[test]
+    fn test_merge_merge_request_with_options() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .merge_method(Some(MergeMethod::Squash))
+            .squash_message(Some("New feature".to_string()))
+            .delete_branch(true)
+            .sha(Some("6dcb09b".to_string()))
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        gitlab.merge(args).unwrap();
+        let body = client.request_body.borrow();
+        assert!(body.contains("\"squash\":true"));
+        assert!(body.contains("\"squash_commit_message\":\"New feature\""));
+        assert!(body.contains("\"should_remove_source_branch\":true"));
+        assert!(body.contains("\"sha\":\"6dcb09b\""));
+        assert!(body.contains("\"merge_when_pipeline_succeeds\":true"));
+    }
+
+    #[test]
+    fn test_merge_merge_request_rebase_method_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .merge_method(Some(MergeMethod::Rebase))
+            .build()
+            .unwrap();
+        let err = gitlab.merge(args).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::OperationNotSupported(_)) => (),
+            _ => panic!("Expected OperationNotSupported"),
+        }
+    }
+
     #[test]
     fn test_close_merge_request() {
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
@@ -842,6 +1724,119 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_reopen_merge_request() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let merge_request = gitlab.reopen(33).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33",
+            *client.url()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+        assert!(client.request_body.borrow().contains("reopen"));
+        assert_eq!("opened", merge_request.state);
+    }
+
+    #[test]
+    fn test_list_all_merge_requests() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::All)
+            .list_args(None)
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=all",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_update_merge_request() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let reviewers = vec![
+            Member::builder()
+                .id(1)
+                .username("tom".to_string())
+                .build()
+                .unwrap(),
+            Member::builder()
+                .id(2)
+                .username("huck".to_string())
+                .build()
+                .unwrap(),
+        ];
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(33)
+            .title(Some("Fix typo".to_string()))
+            .target_branch(Some("develop".to_string()))
+            .reviewers(Some(reviewers))
+            .build()
+            .unwrap();
+        gitlab.update(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33",
+            *client.url()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+        let body = client.request_body.borrow();
+        assert!(body.contains("\"title\":\"Fix typo\""));
+        assert!(body.contains("\"target_branch\":\"develop\""));
+        assert!(body.contains("\"reviewer_ids\":[1,2]"));
+        assert!(!body.contains("description"));
+    }
+
+    #[test]
+    fn test_update_merge_request_mark_as_ready_gets_current_title() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(r#"{"iid": 33, "title": "Draft: New Feature"}"#),
+                None,
+            );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestUpdateBodyArgs::builder()
+            .id(33)
+            .draft(Some(false))
+            .build()
+            .unwrap();
+        gitlab.update(args).unwrap();
+        assert_eq!(http::Method::GET, client.http_method.borrow()[0]);
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[1]);
+        assert!(client
+            .request_body
+            .borrow()
+            .contains("\"title\":\"New Feature\""));
+    }
+
+    #[test]
+    fn test_draft_title() {
+        assert_eq!("Draft: New Feature", draft_title("New Feature", true));
+        assert_eq!(
+            "Draft: New Feature",
+            draft_title("DRAFT: New Feature", true)
+        );
+        assert_eq!(
+            "New Feature",
+            draft_title("[Draft] WIP: New Feature", false)
+        );
+        assert_eq!("New Feature", draft_title("New Feature", false));
+    }
+
     #[test]
     fn test_approve_merge_request_ok() {
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
@@ -932,4 +1927,366 @@ mod test {
             *client.api_operation.borrow()
         );
     }
+
+    #[test]
+    fn test_review_merge_request_publishes_draft_notes_and_approves() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(201, "merge_request.json", None)
+            .add_body(204, Some(""), None)
+            .add_body(201, Some("{}"), None)
+            .add_body(201, Some("{}"), None)
+            .add_body(
+                200,
+                Some(
+                    r#"{"diff_refs": {"base_sha": "aaa", "start_sha": "bbb", "head_sha": "ccc"}}"#,
+                ),
+                None,
+            );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ReviewMergeRequest);
+        let comment = ReviewComment::builder()
+            .path("src/main.rs".to_string())
+            .line(42)
+            .body("Prefer expect".to_string())
+            .build()
+            .unwrap();
+        let args = MergeRequestReviewBodyArgs::builder()
+            .id(33)
+            .comments(vec![comment])
+            .verdict(ReviewVerdict::Approve)
+            .body(Some("LGTM".to_string()))
+            .build()
+            .unwrap();
+        gitlab.review(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/approve",
+            *client.url()
+        );
+        assert_eq!(
+            vec![
+                http::Method::GET,
+                http::Method::POST,
+                http::Method::POST,
+                http::Method::POST,
+                http::Method::POST
+            ],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_review_merge_request_request_changes_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ReviewMergeRequest);
+        let args = MergeRequestReviewBodyArgs::builder()
+            .id(33)
+            .verdict(ReviewVerdict::RequestChanges)
+            .body(Some("Needs work".to_string()))
+            .build()
+            .unwrap();
+        match gitlab.review(args) {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::OperationNotSupported(_)) => (),
+                _ => panic!("Expected OperationNotSupported"),
+            },
+            Ok(_) => panic!("Expected error"),
+        }
+        assert!(client.http_method.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_draft_note_body_anchors_to_diff_side() {
+        let diff_refs =
+            serde_json::json!({"base_sha": "aaa", "start_sha": "bbb", "head_sha": "ccc"});
+        let comment = ReviewComment::builder()
+            .path("src/old.rs".to_string())
+            .line(7)
+            .side(ReviewSide::Old)
+            .body("Why was this removed?".to_string())
+            .build()
+            .unwrap();
+        let body = serde_json::to_value(draft_note_body(&comment, &diff_refs)).unwrap();
+        assert_eq!("Why was this removed?", body["note"]);
+        assert_eq!("text", body["position"]["position_type"]);
+        assert_eq!("aaa", body["position"]["base_sha"]);
+        assert_eq!("ccc", body["position"]["head_sha"]);
+        assert_eq!(7, body["position"]["old_line"]);
+        assert!(body["position"]["new_line"].is_null());
+    }
+
+    #[test]
+    fn test_list_merge_request_threads() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(
+                r#"[{"id": "6a9c1750", "individual_note": false, "notes": [
+                    {"id": 1, "body": "Typo", "author": {"username": "tom"},
+                    "created_at": "2024-01-01T00:00:00Z", "system": false,
+                    "resolvable": true, "resolved": false,
+                    "position": {"old_path": "src/main.rs", "new_path": "src/main.rs",
+                    "old_line": null, "new_line": 42}},
+                    {"id": 2, "body": "Fixed", "author": {"username": "huck"},
+                    "created_at": "2024-01-02T00:00:00Z", "system": false,
+                    "resolvable": true, "resolved": false}]},
+                {"id": "87805b7c", "individual_note": true, "notes": [
+                    {"id": 3, "body": "added 1 commit", "author": {"username": "tom"},
+                    "created_at": "2024-01-03T00:00:00Z", "system": true,
+                    "resolvable": false, "resolved": false}]}]"#,
+            ),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestThread);
+        let threads = gitlab.list(33).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions?per_page=100",
+            *client.url()
+        );
+        assert_eq!(1, threads.len());
+        let thread = &threads[0];
+        assert_eq!("6a9c1750", thread.id);
+        assert!(thread.is_unresolved());
+        assert_eq!(Some("src/main.rs".to_string()), thread.path);
+        assert_eq!(Some(42), thread.line);
+        assert_eq!(2, thread.notes.len());
+        assert_eq!("huck", thread.notes[1].author);
+    }
+
+    #[test]
+    fn test_reply_merge_request_thread() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(201, Some("{}"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestThread);
+        gitlab.reply(33, "6a9c1750", "Done".to_string()).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions/6a9c1750/notes",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::POST], *client.http_method.borrow());
+        assert!(client.request_body.borrow().contains("Done"));
+    }
+
+    #[test]
+    fn test_resolve_merge_request_thread() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("{}"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestThread);
+        gitlab.resolve(33, "6a9c1750", false).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions/6a9c1750?resolved=false",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::PUT], *client.http_method.borrow());
+    }
+
+    #[test]
+    fn test_edit_merge_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("{}"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestUpdateBodyArgs::builder()
+            .id(33)
+            .comment_id(1234)
+            .comment("LGTM!".to_string())
+            .build()
+            .unwrap();
+        gitlab.update(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/notes/1234",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::PUT], *client.http_method.borrow());
+        assert!(client.request_body.borrow().contains("LGTM!"));
+    }
+
+    #[test]
+    fn test_delete_merge_request_comment() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(204, Some(""), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        gitlab.delete(33, 1234).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/notes/1234",
+            *client.url()
+        );
+        assert_eq!(vec![http::Method::DELETE], *client.http_method.borrow());
+    }
+
+    #[test]
+    fn test_react_to_merge_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(201, Some("{}"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        gitlab.react(33, 1234, Reaction::Hooray).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/notes/1234/award_emoji",
+            *client.url()
+        );
+        assert_eq!(r#"{"name":"tada"}"#, *client.request_body.borrow());
+    }
+
+    #[test]
+    fn test_list_merge_request_commits() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(
+                r#"[{"id": "ed899a2f4b50b4370feeea94676502b42383c746", "title": "Replace sanitize with escape once", "author_name": "Example User", "created_at": "2021-09-20T11:50:22.001+00:00"}]"#
+                    .to_string(),
+            ),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestCommit);
+        let args = MergeRequestCommitListBodyArgs::builder()
+            .id(123)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let commits = gitlab.list(args).unwrap();
+        assert_eq!(1, commits.len());
+        assert_eq!("ed899a2f4b50b4370feeea94676502b42383c746", commits[0].sha);
+        assert_eq!("Example User", commits[0].author);
+        assert_eq!("Replace sanitize with escape once", commits[0].title);
+        assert_eq!("", commits[0].ci_status);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/commits",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_list_merge_request_commits_with_ci_status() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(
+                200,
+                Some(r#"[{"status": "success"}, {"status": "failed"}]"#.to_string()),
+                None,
+            )
+            .add_body(
+                200,
+                Some(r#"[{"id": "ed899a2f", "title": "Fix", "author_name": "tom", "created_at": "2021-09-20T11:50:22.001+00:00"}]"#.to_string()),
+                None,
+            );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestCommit);
+        let args = MergeRequestCommitListBodyArgs::builder()
+            .id(123)
+            .list_args(None)
+            .ci_status(true)
+            .build()
+            .unwrap();
+        let commits = gitlab.list(args).unwrap();
+        assert_eq!("failed", commits[0].ci_status);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/repository/commits/ed899a2f/statuses?per_page=100",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_merge_request_commits_num_pages() {
+        let link_header = "<https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/commits?page=1>; rel=\"next\", <https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/commits?page=2>; rel=\"last\"";
+        let mut headers = Headers::new();
+        headers.set("link", link_header);
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestCommit);
+        let args = MergeRequestCommitListBodyArgs::builder()
+            .id(123)
+            .list_args(None)
+            .build()
+            .unwrap();
+        assert_eq!(Some(2), gitlab.num_pages(args).unwrap());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/commits?page=1",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_list_merge_requests_by_source_branch() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(r#"[{"iid": 1, "state": "merged", "source_branch": "stack/c++#1", "created_at": "2024-01-01T00:00:00Z"}]"#),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::All)
+            .list_args(None)
+            .source_branch(Some("stack/c++#1".to_string()))
+            .build()
+            .unwrap();
+        let merge_requests = gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=all&source_branch=stack%2Fc%2B%2B%231",
+            *client.url()
+        );
+        assert_eq!("merged", merge_requests[0].state);
+    }
+
+    #[test]
+    fn test_merge_request_mergeability() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(
+                r#"{"iid": 123, "draft": true, "has_conflicts": false, "diverged_commits_count": 3, "head_pipeline": {"status": "running"}}"#
+                    .to_string(),
+            ),
+            None,
+        );
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestReadiness);
+        let mergeability = gitlab.mergeability(123).unwrap();
+        assert!(mergeability.draft);
+        assert!(!mergeability.has_conflicts);
+        assert_eq!(Some(3), mergeability.behind_by);
+        assert_eq!("pending", mergeability.ci_status);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123?include_diverged_commits_count=true",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_merge_request_approvals() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(
+                r#"{"approvals_required": 2, "approved_by": [{"user": {"username": "tom"}}]}"#
+                    .to_string(),
+            ),
+            None,
+        );
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestReadiness);
+        let approvals = gitlab.approvals(123).unwrap();
+        assert_eq!(1, approvals.approved);
+        assert_eq!(Some(2), approvals.required);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/approvals",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_merge_request_head_ref() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        assert_eq!("refs/merge-requests/123/head", gitlab.head_ref(123));
+    }
+
+    #[test]
+    fn test_rebase_merge_request() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            202,
+            Some(r#"{"rebase_in_progress": true}"#.to_string()),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        gitlab.rebase(123).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/rebase",
+            *client.url()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+    }
 }
@@ -15,6 +15,7 @@ use crate::{log_info, Result};
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::collections::{hash_map, HashMap};
+use std::io::Write;
 use std::iter::Iterator;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -44,12 +45,56 @@ impl<C> Client<C> {
     }
 
     fn submit<T: Serialize>(&self, request: &Request<T>) -> Result<HttpResponse> {
+        let response = self.call(request)?;
+        let (status, headers, flow_control_headers) = response_metadata(&response);
+        let body = response.into_string().unwrap_or_default();
+        let mut response = HttpResponse::builder()
+            .status(status)
+            .body(body)
+            .headers(headers)
+            .flow_control_headers(flow_control_headers)
+            .build()
+            .unwrap();
+        self.handle_rate_limit(&mut response)?;
+        Ok(response)
+    }
+
+    /// Same as submit, but streams successful response bodies as raw bytes
+    /// into the writer instead of reading them into a String. Error response
+    /// bodies are still read into the returned response's body.
+    fn submit_download<T: Serialize>(
+        &self,
+        request: &Request<T>,
+        writer: &mut dyn Write,
+    ) -> Result<HttpResponse> {
+        let response = self.call(request)?;
+        let (status, headers, flow_control_headers) = response_metadata(&response);
+        let body = if (200..300).contains(&status) {
+            std::io::copy(&mut response.into_reader(), writer)?;
+            writer.flush()?;
+            String::new()
+        } else {
+            response.into_string().unwrap_or_default()
+        };
+        let mut response = HttpResponse::builder()
+            .status(status)
+            .body(body)
+            .headers(headers)
+            .flow_control_headers(flow_control_headers)
+            .build()
+            .unwrap();
+        self.handle_rate_limit(&mut response)?;
+        Ok(response)
+    }
+
+    fn call<T: Serialize>(&self, request: &Request<T>) -> Result<ureq::Response> {
         let ureq_req = match request.method {
             Method::GET => ureq::get(request.url()),
             Method::HEAD => ureq::head(request.url()),
             Method::POST => ureq::post(request.url()),
             Method::PATCH => ureq::patch(request.url()),
             Method::PUT => ureq::put(request.url()),
+            Method::DELETE => ureq::delete(request.url()),
         };
         let ureq_req = request
             .headers()
@@ -58,45 +103,39 @@ impl<C> Client<C> {
         let call = || -> std::result::Result<ureq::Response, ureq::Error> {
             match request.method {
                 Method::GET | Method::HEAD => ureq_req.call(),
+                Method::DELETE if request.body.is_none() => ureq_req.call(),
                 _ => ureq_req.send_json(serde_json::to_value(request.body).unwrap()),
             }
         };
         match call() {
-            Ok(response) | Err(Error::Status(_, response)) => {
-                let status = response.status().into();
-                // Grab headers for pagination and cache.
-                let headers =
-                    response
-                        .headers_names()
-                        .iter()
-                        .fold(Headers::new(), |mut headers, name| {
-                            headers.set(
-                                name.to_lowercase(),
-                                response.header(name.as_str()).unwrap().to_string(),
-                            );
-                            headers
-                        });
-                let rate_limit_header = Rc::new(parse_ratelimit_headers(Some(&headers)));
-                let page_header = Rc::new(parse_page_headers(Some(&headers)));
-                let flow_control_headers = FlowControlHeaders::new(page_header, rate_limit_header);
-                // log debug response headers
-                log_debug!("Response headers: {:?}", headers);
-                let body = response.into_string().unwrap_or_default();
-                let mut response = HttpResponse::builder()
-                    .status(status)
-                    .body(body)
-                    .headers(headers)
-                    .flow_control_headers(flow_control_headers)
-                    .build()
-                    .unwrap();
-                self.handle_rate_limit(&mut response)?;
-                Ok(response)
-            }
+            Ok(response) | Err(Error::Status(_, response)) => Ok(response),
             Err(err) => Err(GRError::HttpTransportError(err.to_string()).into()),
         }
     }
 }
 
+/// Gathers status and headers for pagination, rate limiting and cache from a
+/// response before its body gets consumed.
+fn response_metadata(response: &ureq::Response) -> (i32, Headers, FlowControlHeaders) {
+    let status = response.status().into();
+    let headers = response
+        .headers_names()
+        .iter()
+        .fold(Headers::new(), |mut headers, name| {
+            headers.set(
+                name.to_lowercase(),
+                response.header(name.as_str()).unwrap().to_string(),
+            );
+            headers
+        });
+    let rate_limit_header = Rc::new(parse_ratelimit_headers(Some(&headers)));
+    let page_header = Rc::new(parse_page_headers(Some(&headers)));
+    let flow_control_headers = FlowControlHeaders::new(page_header, rate_limit_header);
+    // log debug response headers
+    log_debug!("Response headers: {:?}", headers);
+    (status, headers, flow_control_headers)
+}
+
 impl<C> Client<C> {
     fn handle_rate_limit(&self, response: &mut HttpResponse) -> Result<()> {
         if let Some(headers) = response.get_ratelimit_headers().borrow() {
@@ -218,6 +257,10 @@ impl<T> Body<T> {
     pub fn add<K: Into<String>>(&mut self, key: K, value: T) {
         self.0.insert(key.into(), value);
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -247,6 +290,21 @@ impl Headers {
     }
 }
 
+/// Percent-encodes a value to be used in a URL query string. Only unreserved
+/// characters are kept as is, so separators such as `&`, `#`, `+` or `,` in
+/// the value are not interpreted by the remote.
+pub fn encode_query_value(value: &str) -> String {
+    value.bytes().fold(String::new(), |mut encoded, byte| {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+        encoded
+    })
+}
+
 #[derive(Builder)]
 #[builder(pattern = "owned")]
 pub struct Request<'a, T> {
@@ -317,6 +375,7 @@ pub enum Method {
     POST,
     PUT,
     PATCH,
+    DELETE,
 }
 
 impl<C: Cache<Resource>> HttpRunner for Client<C> {
@@ -365,6 +424,15 @@ impl<C: Cache<Resource>> HttpRunner for Client<C> {
         }
     }
 
+    fn download<T: Serialize>(
+        &self,
+        cmd: &mut Request<T>,
+        writer: &mut dyn Write,
+    ) -> Result<Self::Response> {
+        // Downloads can be large binary payloads, so they are never cached.
+        self.submit_download(cmd, writer)
+    }
+
     fn api_max_pages<T: Serialize>(&self, cmd: &Request<T>) -> u32 {
         let max_pages = self
             .config
@@ -962,4 +1030,14 @@ mod test {
         assert_eq!(5, responses.len());
         assert_eq!(2, *throttler.throttled());
     }
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!("v1.0_rc-1~2", encode_query_value("v1.0_rc-1~2"));
+        assert_eq!(
+            "needs%20review%20%26%20c%2B%2B%2C%20%231",
+            encode_query_value("needs review & c++, #1")
+        );
+        assert_eq!("%C3%A9t%C3%A9", encode_query_value("été"));
+    }
 }
@@ -15,6 +15,7 @@ use serde::Serialize;
 use std::{
     ffi::OsStr,
     fmt::{self, Display, Formatter},
+    io::Write,
     rc::Rc,
 };
 
@@ -37,6 +38,13 @@ pub trait TaskRunner {
 pub trait HttpRunner {
     type Response;
     fn run<T: Serialize>(&self, cmd: &mut Request<T>) -> Result<Self::Response>;
+    /// Runs the request streaming the response body as raw bytes into the
+    /// writer. Binary safe, used to download files such as job artifacts.
+    fn download<T: Serialize>(
+        &self,
+        cmd: &mut Request<T>,
+        writer: &mut dyn Write,
+    ) -> Result<Self::Response>;
     /// Return the number of API MAX PAGES allowed for the given Request.
     fn api_max_pages<T: Serialize>(&self, cmd: &Request<T>) -> u32;
 }
@@ -54,7 +62,7 @@ pub enum CmdInfo {
     CommitBody(Title, Description),
     Project(Project),
     Members(Vec<Member>),
-    MergeRequest(MergeRequestResponse),
+    MergeRequest(Box<MergeRequestResponse>),
     MergeRequestsList(Vec<MergeRequestResponse>),
     OutgoingCommits(String),
     Ignore,
@@ -135,7 +143,9 @@ impl HttpResponse {
             http::Method::POST => {
                 self.status >= 200 && self.status < 300 || self.status == 409 || self.status == 422
             }
-            http::Method::PATCH | http::Method::PUT => self.status >= 200 && self.status < 300,
+            http::Method::PATCH | http::Method::PUT | http::Method::DELETE => {
+                self.status >= 200 && self.status < 300
+            }
         }
     }
 
@@ -508,6 +518,20 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_response_ok_status_delete_request_204() {
+        assert!(HttpResponse::builder()
+            .status(204)
+            .build()
+            .unwrap()
+            .is_ok(&http::Method::DELETE));
+        assert!(!HttpResponse::builder()
+            .status(404)
+            .build()
+            .unwrap()
+            .is_ok(&http::Method::DELETE));
+    }
+
     #[test]
     fn test_response_not_ok_if_500s_any_case() {
         let methods = [
@@ -515,6 +539,7 @@ mod test {
             http::Method::POST,
             http::Method::PATCH,
             http::Method::PUT,
+            http::Method::DELETE,
         ];
         let not_ok_status = 500..=599;
         for status in not_ok_status {
@@ -73,6 +73,20 @@ fn handle_cli_options(
                 url.path().to_string(),
             )
         }
+        CliOptions::Issue(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::issue::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
         CliOptions::Browse(options) => {
             // Use default config for browsing - does not require auth.
             let config = Arc::new(gr::config::ConfigFile::default());
@@ -3,9 +3,10 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 
 use crate::api_traits::{
-    Cicd, CicdJob, CicdRunner, CodeGist, CommentMergeRequest, ContainerRegistry, Deploy,
-    DeployAsset, MergeRequest, ProjectMember, RemoteProject, RemoteTag, TrendingProjectURL,
-    UserInfo,
+    Cicd, CicdJob, CicdJobArtifact, CicdRunner, CodeGist, CommentIssue, CommentMergeRequest,
+    ContainerRegistry, Deploy, DeployAsset, MergeRequest, MergeRequestCommit,
+    MergeRequestReadiness, MergeRequestThread, ProjectMember, RemoteIssue, RemoteProject,
+    RemoteTag, ReviewMergeRequest, TrendingProjectURL, UserInfo,
 };
 use crate::cache::{filesystem::FileCache, nocache::NoCache};
 use crate::config::{env_token, ConfigFile, NoConfig};
@@ -421,9 +422,16 @@ get!(get_deploy_asset, DeployAsset);
 get!(get_auth_user, UserInfo);
 get!(get_cicd_runner, CicdRunner);
 get!(get_comment_mr, CommentMergeRequest);
+get!(get_review_mr, ReviewMergeRequest);
+get!(get_thread_mr, MergeRequestThread);
+get!(get_commit_mr, MergeRequestCommit);
+get!(get_readiness_mr, MergeRequestReadiness);
 get!(get_trending, TrendingProjectURL);
 get!(get_gist, CodeGist);
 get!(get_cicd_job, CicdJob);
+get!(get_cicd_job_artifact, CicdJobArtifact);
+get!(get_issue, RemoteIssue);
+get!(get_comment_issue, CommentIssue);
 
 pub fn extract_domain_path(repo_cli: &str) -> (String, String) {
     let parts: Vec<&str> = repo_cli.split('/').collect();
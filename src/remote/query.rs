@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::io::Write;
 use std::iter::Iterator;
 use std::sync::Arc;
 
@@ -186,6 +187,54 @@ pub fn get_raw<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
     )
 }
 
+/// Gets a raw response retrying on transport, server and rate limit errors
+/// with an exponential backoff. Useful when polling the same resource over
+/// and over, ex. following a job's log.
+pub fn get_raw_with_backoff<R: HttpRunner<Response = HttpResponse>>(
+    runner: &Arc<R>,
+    url: &str,
+    request_headers: Headers,
+    operation: ApiOperation,
+    backoff_max_retries: u32,
+    backoff_wait_time: u64,
+) -> Result<HttpResponse> {
+    let mut request: http::Request<()> =
+        http::Request::new(url, http::Method::GET).with_api_operation(operation);
+    request.set_headers(request_headers);
+    let mut backoff = Backoff::new(
+        runner,
+        backoff_max_retries,
+        backoff_wait_time,
+        time::now_epoch_seconds,
+        Box::new(Exponential),
+        Box::new(throttle::DynamicFixed),
+    );
+    let response = backoff.retry_on_error(&mut request)?;
+    if !response.is_ok(&http::Method::GET) {
+        return Err(query_error(url, &response).into());
+    }
+    Ok(response)
+}
+
+/// Downloads the resource at the given URL streaming its raw bytes into the
+/// writer. Binary safe, ex. zip archives.
+pub fn download<R: HttpRunner<Response = HttpResponse>>(
+    runner: &Arc<R>,
+    url: &str,
+    request_headers: Headers,
+    operation: ApiOperation,
+    writer: &mut dyn Write,
+) -> Result<HttpResponse> {
+    let mut request: http::Request<()> =
+        http::Request::new(url, http::Method::GET).with_api_operation(operation);
+    request.set_headers(request_headers);
+    let response = runner.download(&mut request, writer)?;
+    if !response.is_ok(&http::Method::GET) {
+        return Err(query_error(url, &response).into());
+    }
+    Ok(response)
+}
+
 fn send_request<R: HttpRunner<Response = HttpResponse>, T: Serialize>(
     runner: &Arc<R>,
     url: &str,
@@ -233,12 +282,54 @@ where
     R: HttpRunner<Response = HttpResponse>,
     T: Clone + Timestamp + Into<DisplayBody>,
 {
-    let request = build_list_request(url, &list_args, request_headers, operation);
+    let paged_data = all_pages(
+        runner,
+        url,
+        &list_args,
+        request_headers,
+        iter_over_sub_array,
+        operation,
+        mapper,
+    )?;
+    sort_filter_by_date(paged_data, list_args)
+}
+
+/// Gets all the pages of a list of resources that carry no date, ex. the
+/// files changed by a merge request. Resources are returned in the order
+/// given by the remote.
+pub fn paged_unsorted<R, T>(
+    runner: &Arc<R>,
+    url: &str,
+    request_headers: Headers,
+    operation: ApiOperation,
+    mapper: impl Fn(&serde_json::Value) -> T,
+) -> Result<Vec<T>>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    T: Clone + Into<DisplayBody>,
+{
+    all_pages(runner, url, &None, request_headers, None, operation, mapper)
+}
+
+fn all_pages<R, T>(
+    runner: &Arc<R>,
+    url: &str,
+    list_args: &Option<ListBodyArgs>,
+    request_headers: Headers,
+    iter_over_sub_array: Option<&str>,
+    operation: ApiOperation,
+    mapper: impl Fn(&serde_json::Value) -> T,
+) -> Result<Vec<T>>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    T: Clone + Into<DisplayBody>,
+{
+    let request = build_list_request(url, list_args, request_headers, operation);
     let mut throttle_time = None;
     let mut throttle_range = None;
     let mut backoff_max_retries = 0;
     let mut backoff_wait_time = 60;
-    if let Some(list_args) = &list_args {
+    if let Some(list_args) = list_args {
         throttle_time = list_args.throttle_time;
         throttle_range = list_args.throttle_range;
         backoff_max_retries = list_args.get_args.backoff_max_retries;
@@ -260,7 +351,7 @@ where
         Box::new(throttle::DynamicFixed),
     );
     let paginator = Paginator::new(runner, request, url, backoff, throttle_strategy);
-    let all_data = paginator
+    paginator
         .map(|response| {
             let response = response?;
             if !response.is_ok(&http::Method::GET) {
@@ -282,7 +373,7 @@ where
                         paged_data.push(mapper(data));
                         paged_data
                     });
-                if let Some(list_args) = &list_args {
+                if let Some(list_args) = list_args {
                     if list_args.flush {
                         display::print(
                             &mut std::io::stdout(),
@@ -302,7 +393,7 @@ where
                         paged_data.push(mapper(data));
                         paged_data
                     });
-            if let Some(list_args) = &list_args {
+            if let Some(list_args) = list_args {
                 if list_args.flush {
                     display::print(
                         &mut std::io::stdout(),
@@ -316,11 +407,7 @@ where
             Ok(paged_data)
         })
         .collect::<Result<Vec<Vec<T>>>>()
-        .map(|paged_data| paged_data.into_iter().flatten().collect());
-    match all_data {
-        Ok(paged_data) => Ok(sort_filter_by_date(paged_data, list_args)?),
-        Err(err) => Err(err),
-    }
+        .map(|paged_data| paged_data.into_iter().flatten().collect())
 }
 
 fn build_list_request<'a>(
@@ -426,4 +513,73 @@ mod test {
         let operation = ApiOperation::Pipeline;
         assert!(num_resources(&client, url, headers, operation).is_err());
     }
+
+    #[test]
+    fn test_get_raw_with_backoff_ok() {
+        let response = HttpResponse::builder()
+            .status(200)
+            .body("Running with gitlab-runner".to_string())
+            .build()
+            .unwrap();
+        let client = Arc::new(MockRunner::new(vec![response]));
+        let url = "https://gitlab.com/api/v4/projects/1/jobs/7/trace";
+        let response =
+            get_raw_with_backoff(&client, url, Headers::new(), ApiOperation::Pipeline, 0, 60)
+                .unwrap();
+        assert_eq!("Running with gitlab-runner", response.body);
+    }
+
+    #[test]
+    fn test_get_raw_with_backoff_error_on_404() {
+        let response = HttpResponse::builder().status(404).build().unwrap();
+        let client = Arc::new(MockRunner::new(vec![response]));
+        let url = "https://gitlab.com/api/v4/projects/1/jobs/7/trace";
+        assert!(
+            get_raw_with_backoff(&client, url, Headers::new(), ApiOperation::Pipeline, 0, 60)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_download_writes_body_to_writer() {
+        let response = HttpResponse::builder()
+            .status(200)
+            .body("PK\u{3}\u{4}".to_string())
+            .build()
+            .unwrap();
+        let client = Arc::new(MockRunner::new(vec![response]));
+        let url = "https://gitlab.com/api/v4/projects/1/jobs/7/artifacts";
+        let mut buf = Vec::new();
+        download(
+            &client,
+            url,
+            Headers::new(),
+            ApiOperation::Pipeline,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(b"PK\x03\x04".to_vec(), buf);
+        assert_eq!(url, *client.url());
+    }
+
+    #[test]
+    fn test_download_error_on_404_nothing_written() {
+        let response = HttpResponse::builder()
+            .status(404)
+            .body("404 Not found".to_string())
+            .build()
+            .unwrap();
+        let client = Arc::new(MockRunner::new(vec![response]));
+        let url = "https://gitlab.com/api/v4/projects/1/jobs/7/artifacts";
+        let mut buf = Vec::new();
+        assert!(download(
+            &client,
+            url,
+            Headers::new(),
+            ApiOperation::Pipeline,
+            &mut buf
+        )
+        .is_err());
+        assert!(buf.is_empty());
+    }
 }
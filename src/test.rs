@@ -55,6 +55,7 @@ pub mod utils {
     pub struct MockRunner<R> {
         responses: RefCell<Vec<R>>,
         cmd: RefCell<String>,
+        cmds: RefCell<Vec<String>>,
         headers: RefCell<Headers>,
         url: RefCell<String>,
         pub api_operation: RefCell<Option<ApiOperation>>,
@@ -71,6 +72,7 @@ pub mod utils {
             Self {
                 responses: RefCell::new(responses),
                 cmd: RefCell::new(String::new()),
+                cmds: RefCell::new(Vec::new()),
                 headers: RefCell::new(Headers::new()),
                 url: RefCell::new(String::new()),
                 api_operation: RefCell::new(None),
@@ -91,6 +93,11 @@ pub mod utils {
             self.cmd.borrow()
         }
 
+        /// Every command run, in order.
+        pub fn cmds(&self) -> Ref<'_, Vec<String>> {
+            self.cmds.borrow()
+        }
+
         pub fn url(&self) -> Ref<String> {
             self.url.borrow()
         }
@@ -126,6 +133,7 @@ pub mod utils {
                     .collect::<Vec<String>>()
                     .join(" "),
             );
+            self.cmds.borrow_mut().push(self.cmd.borrow().clone());
             let response = self.responses.borrow_mut().pop().unwrap();
             *self.run_count.borrow_mut() += 1;
             match response.status {
@@ -149,7 +157,8 @@ pub mod utils {
             match response.status {
                 // 409 Conflict - Merge request already exists. - Gitlab
                 // 422 Conflict - Merge request already exists. - Github
-                200 | 201 | 302 | 409 | 422 => return Ok(response),
+                // 202 Accepted, 204 No Content - Github actions
+                200 | 201 | 202 | 204 | 302 | 409 | 422 => return Ok(response),
                 // RateLimit error code. 403 secondary rate limit, 429 primary
                 // rate limit.
                 403 | 429 => {
@@ -164,6 +173,30 @@ pub mod utils {
             }
         }
 
+        fn download<T: Serialize>(
+            &self,
+            cmd: &mut Request<T>,
+            writer: &mut dyn std::io::Write,
+        ) -> Result<Self::Response> {
+            self.url.replace(cmd.url().to_string());
+            self.headers.replace(cmd.headers().clone());
+            self.api_operation.replace(cmd.api_operation().clone());
+            self.http_method.borrow_mut().push(cmd.method.clone());
+            let mut response = self.responses.borrow_mut().pop().unwrap();
+            match response.status {
+                200 => {
+                    writer.write_all(response.body.as_bytes())?;
+                    response.body.clear();
+                    Ok(response)
+                }
+                403 | 429 => {
+                    let headers = response.get_ratelimit_headers().unwrap_or_default();
+                    Err(error::GRError::RateLimitExceeded(headers).into())
+                }
+                _ => Ok(response),
+            }
+        }
+
         fn api_max_pages<T: Serialize>(&self, _cmd: &Request<T>) -> u32 {
             self.config.get_max_pages(
                 &self
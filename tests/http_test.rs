@@ -443,3 +443,43 @@ fn test_ratelimit_remaining_above_threshold_is_ok() {
     assert!(runner.run(&mut request).is_ok());
     server_mock.assert_hits(1);
 }
+
+#[test]
+fn test_http_download_is_binary_safe() {
+    let server = MockServer::start();
+    // Zip local file header signature followed by non UTF-8 bytes.
+    let body: Vec<u8> = vec![0x50, 0x4b, 0x03, 0x04, 0xff, 0xfe, 0x00, 0x80];
+    let server_mock = server.mock(|when, then| {
+        when.method(GET).path("/jobs/7/artifacts");
+        then.status(200)
+            .header("content-type", "application/octet-stream")
+            .body(body.clone());
+    });
+
+    let runner = Client::new(NoCache, Arc::new(ConfigMock::new()), false);
+    let mut request = Request::<()>::new(&server.url("/jobs/7/artifacts"), Method::GET);
+    let mut buf = Vec::new();
+    let response = runner.download(&mut request, &mut buf).unwrap();
+    assert_eq!(200, response.status);
+    assert!(response.body.is_empty());
+    assert_eq!(body, buf);
+    server_mock.assert();
+}
+
+#[test]
+fn test_http_download_error_status_body_not_written() {
+    let server = MockServer::start();
+    let server_mock = server.mock(|when, then| {
+        when.method(GET).path("/jobs/7/artifacts");
+        then.status(404).body("404 Not found");
+    });
+
+    let runner = Client::new(NoCache, Arc::new(ConfigMock::new()), false);
+    let mut request = Request::<()>::new(&server.url("/jobs/7/artifacts"), Method::GET);
+    let mut buf = Vec::new();
+    let response = runner.download(&mut request, &mut buf).unwrap();
+    assert_eq!(404, response.status);
+    assert_eq!("404 Not found", response.body);
+    assert!(buf.is_empty());
+    server_mock.assert();
+}